@@ -1,5 +1,6 @@
 fn main() {
-    // This would be used if we had GResource files
-    // For now, we'll keep it minimal
+    // The Tux logo and the logo gallery are compiled in, so they are found
+    // wherever the binary runs, including inside Flatpak
+    glib_build_tools::compile_resources(&["."], "data/resources.gresource.xml", "resources.gresource");
     println!("cargo:rerun-if-changed=build.rs");
 }
@@ -8,7 +8,7 @@ set -e
 
 BINARY_NAME="about-this-linux"
 INSTALL_DIR="/usr/local/bin"
-CONFIG_DIR="$HOME/.local/share/about-this-linux"
+CONFIG_DIR="${XDG_CONFIG_HOME:-$HOME/.config}/about-this-linux"
 
 echo "About This Linux v0.3.0 - Installation Script"
 echo "=============================================="
@@ -89,6 +89,13 @@ sudo chmod +x "$INSTALL_DIR/$BINARY_NAME"
 
 echo "✓ Binary installed to $INSTALL_DIR/$BINARY_NAME"
 
+# Install desktop entry and D-Bus activation file
+APP_ID="com.novik.about-this-linux"
+sudo install -Dm644 "data/$APP_ID.desktop" "/usr/local/share/applications/$APP_ID.desktop"
+sudo install -Dm644 "data/$APP_ID.service" "/usr/local/share/dbus-1/services/$APP_ID.service"
+sudo install -Dm644 "tux-logo.png" "/usr/local/share/pixmaps/tux-logo.png"
+echo "✓ Desktop entry installed (right-click the launcher for quick actions)"
+
 # Create config directory
 echo "Creating config directory..."
 mkdir -p "$CONFIG_DIR"
@@ -0,0 +1,102 @@
+use gtk::prelude::*;
+use gtk::{gio, glib, Application};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::configurator::ConfiguratorWindow;
+use crate::journal_window::JournalWindow;
+use crate::main_window::MainWindow;
+use crate::paths;
+use crate::providers::CATEGORY_STORAGE;
+use crate::report;
+
+// Keep the service alive long enough for the clipboard to be read, since a
+// D-Bus activated instance otherwise exits as soon as it goes idle
+const CLIPBOARD_HOLD_SECONDS: u32 = 60;
+
+/// Application actions, also exposed as desktop-file actions through
+/// `org.freedesktop.Application.ActivateAction`
+pub fn register(app: &Application) {
+    // Desktop-file actions can't pass a parameter, so this one is show-tab
+    // with the tab filled in
+    let open_storage = gio::SimpleAction::new("open-storage", None);
+    let app_clone = app.clone();
+    open_storage.connect_activate(move |_, _| {
+        app_clone.activate_action("show-tab", Some(&CATEGORY_STORAGE.to_variant()));
+    });
+    app.add_action(&open_storage);
+
+    let configure = gio::SimpleAction::new("configure", None);
+    let app_clone = app.clone();
+    configure.connect_activate(move |_, _| {
+        let configurator = ConfiguratorWindow::new(&app_clone, paths::default_config_path());
+        configurator.present();
+    });
+    app.add_action(&configure);
+
+    // The name the action had before it became `configure`, still used by
+    // older desktop files and scripts
+    let open_configurator = gio::SimpleAction::new("open-configurator", None);
+    let app_clone = app.clone();
+    open_configurator.connect_activate(move |_, _| app_clone.activate_action("configure", None));
+    app.add_action(&open_configurator);
+
+    let copy_specs = gio::SimpleAction::new("copy-specs", None);
+    let app_clone = app.clone();
+    copy_specs.connect_activate(move |_, _| {
+        let config = crate::create_auto_detected_config(crate::load_saved_settings());
+        let display = match gtk::gdk::Display::default() {
+            Some(display) => display,
+            None => {
+                eprintln!("No display available to copy specs to");
+                return;
+            }
+        };
+        display.clipboard().set_text(&report::overview_report(&config));
+
+        let guard = app_clone.hold();
+        glib::timeout_add_seconds_local_once(CLIPBOARD_HOLD_SECONDS, move || drop(guard));
+    });
+    app.add_action(&copy_specs);
+
+    let detection_log = gio::SimpleAction::new("detection-log", None);
+    let app_clone = app.clone();
+    detection_log.connect_activate(move |_, _| {
+        JournalWindow::new(&app_clone).present();
+    });
+    app.add_action(&detection_log);
+
+    let quit = gio::SimpleAction::new("quit", None);
+    let app_clone = app.clone();
+    quit.connect_activate(move |_, _| app_clone.quit());
+    app.add_action(&quit);
+}
+
+/// Actions on the main window, which `main` keeps track of. `show-tab` opens
+/// or raises it on a tab, through the regular activate so a new window gets
+/// the same flags; `refresh` runs detection again for the visible tab.
+pub fn register_window_actions(
+    app: &Application,
+    current_window: Rc<RefCell<Option<MainWindow>>>,
+    requested_tab: Rc<RefCell<Option<String>>>,
+) {
+    let show_tab = gio::SimpleAction::new("show-tab", Some(glib::VariantTy::STRING));
+    let app_clone = app.clone();
+    show_tab.connect_activate(move |_, parameter| {
+        // An empty name keeps the tab the window is on
+        *requested_tab.borrow_mut() = parameter
+            .and_then(|parameter| parameter.get::<String>())
+            .filter(|tab| !tab.is_empty());
+        app_clone.activate();
+    });
+    app.add_action(&show_tab);
+
+    let refresh = gio::SimpleAction::new("refresh", None);
+    refresh.connect_activate(move |_, _| {
+        match current_window.borrow().as_ref().filter(|window| window.is_open()) {
+            Some(main_window) => main_window.refresh(),
+            None => eprintln!("No window open to refresh"),
+        }
+    });
+    app.add_action(&refresh);
+}
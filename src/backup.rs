@@ -0,0 +1,116 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config::Config;
+
+#[derive(Debug, Clone)]
+pub struct ConfigBackup {
+    pub path: PathBuf,
+    // Seconds since the Unix epoch, taken from the file name
+    pub timestamp: u64,
+    // Tells apart backups made within the same second
+    sequence: u32,
+}
+
+impl ConfigBackup {
+    pub fn load(&self) -> Result<Config> {
+        Config::load(&self.path)
+    }
+}
+
+/// Backups live next to the config in a `backups` directory
+pub fn backup_dir(config_path: &Path) -> PathBuf {
+    config_path
+        .parent()
+        .map(|parent| parent.join("backups"))
+        .unwrap_or_else(|| PathBuf::from("backups"))
+}
+
+fn backup_prefix(config_path: &Path) -> String {
+    let stem = config_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "config".to_string());
+    format!("{}-", stem)
+}
+
+/// Copy the current config into the backup directory, then drop all but the
+/// newest `keep` backups. Does nothing if there is no config yet.
+pub fn backup_config(config_path: &Path, keep: usize) -> Result<()> {
+    if keep == 0 || !config_path.exists() {
+        return Ok(());
+    }
+
+    let dir = backup_dir(config_path);
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create backup directory: {}", dir.display()))?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    // Saves in quick succession, e.g. an import right after a save, get a
+    // counter so they don't overwrite the backup made a moment before
+    let prefix = backup_prefix(config_path);
+    let backup_path = std::iter::once(format!("{}{}.json", prefix, timestamp))
+        .chain((1..).map(|sequence| format!("{}{}-{}.json", prefix, timestamp, sequence)))
+        .map(|name| dir.join(name))
+        .find(|path| !path.exists())
+        .unwrap_or_default();
+
+    std::fs::copy(config_path, &backup_path)
+        .with_context(|| format!("Failed to back up config to {}", backup_path.display()))?;
+
+    for old in list_backups(config_path).into_iter().skip(keep) {
+        if let Err(e) = std::fs::remove_file(&old.path) {
+            eprintln!("Failed to remove old backup {}: {}", old.path.display(), e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Backups of `config_path`, newest first
+pub fn list_backups(config_path: &Path) -> Vec<ConfigBackup> {
+    let prefix = backup_prefix(config_path);
+    let entries = match std::fs::read_dir(backup_dir(config_path)) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut backups: Vec<ConfigBackup> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let stamp = name.strip_prefix(&prefix)?.strip_suffix(".json")?;
+            let (timestamp, sequence) = match stamp.split_once('-') {
+                Some((timestamp, sequence)) => (timestamp.parse::<u64>().ok()?, sequence.parse::<u32>().ok()?),
+                None => (stamp.parse::<u64>().ok()?, 0),
+            };
+            Some(ConfigBackup {
+                path: entry.path(),
+                timestamp,
+                sequence,
+            })
+        })
+        .collect();
+
+    backups.sort_by_key(|backup| std::cmp::Reverse((backup.timestamp, backup.sequence)));
+    backups
+}
+
+/// Replace the config with a backup, backing up the current one first so the
+/// restore itself can be undone
+pub fn restore_backup(backup: &ConfigBackup, config_path: &Path, keep: usize) -> Result<Config> {
+    let config = backup.load()?;
+    // Read it before rotating, which may remove this very backup
+    let content = std::fs::read(&backup.path)
+        .with_context(|| format!("Failed to read backup: {}", backup.path.display()))?;
+
+    // Keep at least one slot so the config being replaced is not lost
+    backup_config(config_path, keep.max(1))?;
+    std::fs::write(config_path, content)
+        .with_context(|| format!("Failed to restore {}", backup.path.display()))?;
+    Ok(config)
+}
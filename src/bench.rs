@@ -0,0 +1,145 @@
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+use crate::journal::{self, JournalEntry, Outcome};
+use crate::providers::ProviderRegistry;
+use crate::system_info;
+
+// A detector's outcome; only the error matters here
+type Detected = Result<(), String>;
+type Detect<'a> = Box<dyn Fn() -> Detected + 'a>;
+
+/// One detector as the window runs it, timed as a whole
+struct Timing {
+    name: String,
+    runs: Vec<Duration>,
+    error: Option<String>,
+    // Steps recorded while it ran the first time: the tools it called
+    steps: Vec<JournalEntry>,
+}
+
+/// Run every detector `runs` times and print how long each took, slowest
+/// first, with the external tools each one called
+pub fn run(config: &Config, runs: u32) {
+    let registry = ProviderRegistry::from_config(config);
+    let mut detectors: Vec<(String, Detect)> = registry
+        .all()
+        .into_iter()
+        .map(|provider| {
+            let name = format!("{} ({})", provider.name(), provider.category());
+            let detect: Detect = Box::new(move || provider.detect().map(|_| ()).map_err(|e| format!("{:#}", e)));
+            (name, detect)
+        })
+        .collect();
+    // Overview rows that no provider covers
+    let extras: [(&str, Detect); 4] = [
+        ("Uptime", Box::new(|| found(system_info::uptime()))),
+        ("Memory usage", Box::new(|| found(system_info::memory_usage()))),
+        ("Desktop", Box::new(|| found(system_info::desktop_environment()))),
+        ("Window manager", Box::new(|| found(system_info::window_manager()))),
+    ];
+    detectors.extend(extras.map(|(name, detect)| (name.to_string(), detect)));
+
+    let runs = runs.max(1);
+    let mut timings: Vec<Timing> = detectors
+        .iter()
+        .map(|(name, _)| Timing {
+            name: name.clone(),
+            runs: Vec::new(),
+            error: None,
+            steps: Vec::new(),
+        })
+        .collect();
+    for run in 0..runs {
+        for ((_, detect), timing) in detectors.iter().zip(timings.iter_mut()) {
+            let from = journal::len();
+            let started = Instant::now();
+            let result = detect();
+            timing.runs.push(started.elapsed());
+            if run == 0 {
+                timing.error = result.err();
+                timing.steps = journal::entries_since(from);
+            }
+        }
+    }
+
+    timings.sort_by_key(|timing| std::cmp::Reverse(average(&timing.runs)));
+    print_table(&timings, runs);
+}
+
+fn found<T>(value: Option<T>) -> Detected {
+    value.map(|_| ()).ok_or_else(|| "not detected".to_string())
+}
+
+fn average(runs: &[Duration]) -> Duration {
+    runs.iter().sum::<Duration>() / runs.len().max(1) as u32
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn print_table(timings: &[Timing], runs: u32) {
+    let width = timings
+        .iter()
+        .map(|timing| timing.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    let total: Duration = timings.iter().map(|timing| average(&timing.runs)).sum();
+
+    if runs > 1 {
+        println!(
+            "{:<width$}  {:>10}  {:>10}  {:>10}",
+            "Detector",
+            "min",
+            "avg",
+            "max",
+            width = width
+        );
+    } else {
+        println!("{:<width$}  {:>10}", "Detector", "time", width = width);
+    }
+    for timing in timings {
+        let avg = millis(average(&timing.runs));
+        let line = if runs > 1 {
+            let min = timing.runs.iter().min().copied().unwrap_or_default();
+            let max = timing.runs.iter().max().copied().unwrap_or_default();
+            format!(
+                "{:<width$}  {:>10}  {:>10}  {:>10}",
+                timing.name,
+                millis(min),
+                avg,
+                millis(max),
+                width = width
+            )
+        } else {
+            format!("{:<width$}  {:>10}", timing.name, avg, width = width)
+        };
+        match &timing.error {
+            Some(error) => println!("{}  failed: {}", line, error),
+            None => println!("{}", line),
+        }
+
+        for step in &timing.steps {
+            let outcome = match &step.outcome {
+                Outcome::Ok => String::new(),
+                Outcome::Fallback(reason) => format!("  fallback: {}", reason),
+                Outcome::Failed(reason) => format!("  failed: {}", reason),
+            };
+            println!(
+                "    {:>10}  [{}] {}{}",
+                millis(step.duration),
+                step.source,
+                step.step,
+                outcome
+            );
+        }
+    }
+    println!(
+        "\nTotal {} per run ({} detectors, {} run{})",
+        millis(total),
+        timings.len(),
+        runs,
+        if runs == 1 { "" } else { "s" }
+    );
+}
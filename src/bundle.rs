@@ -0,0 +1,176 @@
+use anyhow::{bail, Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use gdk_pixbuf::prelude::*;
+use gdk_pixbuf::{Pixbuf, PixbufLoader};
+use gtk::gio;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::backup;
+use crate::config::{Config, COMMAND_FIELDS};
+use crate::paths;
+
+// A bundle is a .tar.gz holding the config and the logo it points at, so a
+// setup can be shared without the machine-specific image path
+const BUNDLE_CONFIG: &str = "overview-conf.json";
+const BUNDLE_LOGO: &str = "logo";
+
+/// Write `config_json` and its logo to a bundle at `dest`. The bundled
+/// config refers to the logo by its name inside the archive.
+pub fn export_bundle(mut config_json: serde_json::Value, dest: &Path) -> Result<()> {
+    let logo_path = config_json
+        .get("distro_image_path")
+        .and_then(|value| value.as_str())
+        .map(|path| path.to_string());
+
+    let file = std::fs::File::create(dest)
+        .with_context(|| format!("Failed to create bundle: {}", dest.display()))?;
+    let mut archive = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+    match logo_path {
+        Some(logo_path) if Path::new(&logo_path).is_file() => {
+            archive
+                .append_path_with_name(&logo_path, BUNDLE_LOGO)
+                .with_context(|| format!("Failed to add logo: {}", logo_path))?;
+            config_json["distro_image_path"] = serde_json::Value::String(BUNDLE_LOGO.to_string());
+        }
+        Some(logo_path) => eprintln!("Logo {} not found, exporting without it", logo_path),
+        None => {}
+    }
+
+    // The base file would not exist on the receiving machine
+    if let Some(object) = config_json.as_object_mut() {
+        object.remove("extends");
+    }
+
+    let content = serde_json::to_vec_pretty(&config_json).context("Failed to serialize config")?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    archive
+        .append_data(&mut header, BUNDLE_CONFIG, content.as_slice())
+        .context("Failed to add config to bundle")?;
+
+    archive
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .with_context(|| format!("Failed to write bundle: {}", dest.display()))?;
+    Ok(())
+}
+
+/// Shell commands the bundled config would run (hooks, custom providers and
+/// the report and update commands), to ask about before importing them
+pub fn bundle_commands(bundle: &Path) -> Result<Vec<String>> {
+    let (config_json, _) = read_bundle(bundle)?;
+    let config: Config = serde_json::from_value(config_json).context("Failed to parse bundled config")?;
+    Ok(config.commands())
+}
+
+/// Replace the config at `config_path` with the bundled one. Unless
+/// `with_commands` is set, the settings that run commands are left out, since
+/// a shared bundle would otherwise run them at the next launch.
+pub fn import_bundle(bundle: &Path, config_path: &Path, keep: usize, with_commands: bool) -> Result<Config> {
+    let (mut config_json, logo) = read_bundle(bundle)?;
+
+    if !with_commands {
+        if let Some(settings) = config_json.as_object_mut() {
+            for field in COMMAND_FIELDS {
+                settings.remove(field);
+            }
+        }
+    }
+
+    if let Some(logo) = logo {
+        let logo_path = store_logo(&logo)?;
+        config_json["distro_image_path"] =
+            serde_json::Value::String(logo_path.to_string_lossy().to_string());
+    }
+
+    // Keep at least one slot so the config being replaced is not lost
+    backup::backup_config(config_path, keep.max(1))?;
+    if let Some(parent) = config_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(config_path, serde_json::to_string_pretty(&config_json)?)
+        .with_context(|| format!("Failed to write config: {}", config_path.display()))?;
+
+    Config::load(config_path)
+}
+
+// The bundled config, checked to parse, and the logo if there is one
+fn read_bundle(bundle: &Path) -> Result<(serde_json::Value, Option<Vec<u8>>)> {
+    let file = std::fs::File::open(bundle)
+        .with_context(|| format!("Failed to open bundle: {}", bundle.display()))?;
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+
+    let mut config_content = None;
+    let mut logo = None;
+    // Only the two known names are read, so nothing is ever unpacked to a
+    // path taken from the archive
+    for entry in archive.entries().context("Failed to read bundle")? {
+        let mut entry = entry.context("Failed to read bundle")?;
+        let name = entry.path()?.to_string_lossy().to_string();
+        let mut content = Vec::new();
+        match name.as_str() {
+            BUNDLE_CONFIG => {
+                entry.read_to_end(&mut content)?;
+                config_content = Some(content);
+            }
+            BUNDLE_LOGO => {
+                entry.read_to_end(&mut content)?;
+                logo = Some(content);
+            }
+            _ => eprintln!("Ignoring unexpected file in bundle: {}", name),
+        }
+    }
+
+    let config_content = match config_content {
+        Some(content) => content,
+        None => bail!("{} does not contain {}", bundle.display(), BUNDLE_CONFIG),
+    };
+    let config_json: serde_json::Value =
+        serde_json::from_slice(&config_content).context("Failed to parse bundled config")?;
+    // Catch type errors before touching the current config
+    serde_json::from_value::<Config>(config_json.clone())
+        .context("Failed to parse bundled config")?;
+    Ok((config_json, logo))
+}
+
+// Write an imported logo to the cache under the extension of its format, so
+// an SVG stays an SVG. Formats without a known extension are converted to PNG.
+fn store_logo(content: &[u8]) -> Result<PathBuf> {
+    let (content_type, _) = gio::content_type_guess(None::<&str>, content);
+    let extension = gio::content_type_get_mime_type(&content_type).and_then(|mime_type| {
+        Pixbuf::formats()
+            .into_iter()
+            .find(|format| format.mime_types().contains(&mime_type))
+            .and_then(|format| format.extensions().first().map(|extension| extension.to_string()))
+    });
+
+    let logo_path = match extension {
+        Some(extension) => {
+            let logo_path = paths::logo_cache_path(&extension);
+            std::fs::write(&logo_path, content)
+                .with_context(|| format!("Failed to write logo: {}", logo_path.display()))?;
+            logo_path
+        }
+        None => {
+            let loader = PixbufLoader::new();
+            let pixbuf = loader
+                .write(content)
+                .and_then(|_| loader.close())
+                .ok()
+                .and_then(|_| loader.pixbuf())
+                .context("The bundled logo is not an image")?;
+            let logo_path = paths::logo_cache_path("png");
+            pixbuf
+                .savev(&logo_path, "png", &[])
+                .with_context(|| format!("Failed to write logo: {}", logo_path.display()))?;
+            logo_path
+        }
+    };
+    Ok(logo_path)
+}
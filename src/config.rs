@@ -1,6 +1,13 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::backup;
+use crate::paths;
+use crate::providers::{InfoProvider, ScriptProvider};
+use crate::units;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -12,19 +19,411 @@ pub struct Config {
     pub startup_disk: String,
     pub graphics: String,
     pub serial_num: String,
+    // Shown instead of what os-release and uname report
+    #[serde(default)]
+    pub distro_name: Option<String>,
+    #[serde(default)]
+    pub distro_version: Option<String>,
+    #[serde(default)]
+    pub kernel: Option<String>,
+    // Overview fields that keep their saved value instead of being detected
+    // again at every launch
+    #[serde(default)]
+    pub locked_fields: Vec<String>,
     pub overview_margins: [i32; 4],
     pub section_space: i32,
     pub logo_space: i32,
     pub system_info_command: String,
     pub software_update_command: String,
+    // Base config this one overrides, relative to this file's directory
+    #[serde(default)]
+    pub extends: Option<String>,
     #[serde(rename = "font-family")]
     pub font_family: Option<String>,
+    // Stylesheet loaded on top of the bundled one
+    #[serde(default)]
+    pub css_path: Option<String>,
+    // Built-in look applied under css_path
+    #[serde(default)]
+    pub theme: ThemePreset,
+    #[serde(default)]
+    pub hooks: HookConfig,
+    // Stack pages to build, in order; custom provider pages not listed here
+    // are appended after them
+    #[serde(default = "default_tabs")]
+    pub tabs: Vec<String>,
+    // Extra (label, value) rows shown after the built-in overview fields
+    #[serde(default)]
+    pub custom_fields: Vec<(String, String)>,
+    // Optional neofetch-style rows below the hardware fields
+    #[serde(default = "default_true")]
+    pub show_uptime: bool,
+    #[serde(default)]
+    pub show_desktop_environment: bool,
+    #[serde(default)]
+    pub show_window_manager: bool,
+    #[serde(default)]
+    pub show_session: bool,
+    #[serde(default)]
+    pub show_region: bool,
+    // Only shown on machines with a battery
+    #[serde(default = "default_true")]
+    pub show_battery: bool,
+    #[serde(default)]
+    pub custom_providers: Vec<ScriptProviderConfig>,
+    #[serde(default = "default_true")]
+    pub battery_saver: bool,
+    // How often uptime, memory use, free space and temperatures are
+    // updated; 0 disables
+    #[serde(default = "default_auto_refresh_seconds")]
+    pub auto_refresh_seconds: u32,
+    #[serde(default)]
+    pub backend: Backend,
+    // Let the window be resized; narrow windows stack the overview
+    #[serde(default)]
+    pub resizable: bool,
+    // Smaller logo, tighter margins and a scrolling overview for small screens
+    #[serde(default)]
+    pub compact: bool,
+    // Mask the serial number until clicked, and the hostname too if asked
+    #[serde(default)]
+    pub privacy_mode: bool,
+    #[serde(default)]
+    pub privacy_hide_hostname: bool,
+    // Stay running with a tray icon so the window opens without detection
+    #[serde(default)]
+    pub background: bool,
+    #[serde(default)]
+    pub placement: Placement,
+    // Connector name (e.g. "DP-1") used when placement is "monitor"
+    #[serde(default)]
+    pub placement_monitor: Option<String>,
+    // Look for package updates at startup and, in the background, every
+    // this many hours; 0 disables
+    #[serde(default)]
+    pub update_check_hours: u32,
+    // Reopen on the tab, size and monitor the window was closed with
+    #[serde(default = "default_true")]
+    pub remember_window: bool,
+    // Number of previous configs kept in the backups directory on save
+    #[serde(default = "default_backup_count")]
+    pub backup_count: usize,
+    #[serde(default)]
+    pub units: UnitSystem,
+    #[serde(default)]
+    pub temperature_unit: TemperatureUnit,
+    // strftime-style format used wherever a date is shown
+    #[serde(default = "default_date_format")]
+    pub date_format: String,
+    // Why a detected field fell back, keyed by field name; never saved
+    #[serde(skip)]
+    pub detection_errors: HashMap<String, String>,
+    // Problems fixed up by validate(), shown as a banner in the main window
+    #[serde(skip)]
+    pub validation_warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    // Shell out to fastfetch, lsblk, df and friends
+    #[default]
+    Commands,
+    // Use the sysinfo crate and sysfs only
+    Native,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Placement {
+    // Leave it to GTK and the window manager
+    #[default]
+    Default,
+    // Center on the monitor under the pointer
+    Pointer,
+    // Center on `placement_monitor`
+    Monitor,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemePreset {
+    // Plain GTK styling from data/style.css
+    #[default]
+    Default,
+    // Ventura/Sonoma About panel lookalike from data/macos.css
+    Macos,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnitSystem {
+    // Powers of 1024, labelled GiB/TiB
+    #[default]
+    Binary,
+    // Powers of 1000, labelled GB/TB as on drive packaging
+    Decimal,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+// Fields holding file paths, which may use `~`, `$HOME` or be relative to
+// the config file
+const PATH_FIELDS: [&str; 3] = ["distro_image_path", "css_path", "extends"];
+
+// Set once save_field has backed up the file this session
+static FIELD_BACKUP_TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// Set one top-level `key` in the config file at `path`, for tweaks made in
+/// the window such as dragging tabs or dropping a logo. Only the first call
+/// in a session backs the file up, so the backups aren't rotated away by
+/// every drag and drop. The rest of the file is kept, though comments and
+/// formatting are not.
+pub fn save_field(path: &Path, key: &str, value: serde_json::Value, backup_count: usize) -> Result<()> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+    let mut json: serde_json::Value = serde_json::from_str(&strip_json_comments(&content))
+        .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+    match json.as_object_mut() {
+        Some(object) => object.insert(key.to_string(), value),
+        None => anyhow::bail!("Config file is not a JSON object: {}", path.display()),
+    };
+
+    if !FIELD_BACKUP_TAKEN.swap(true, Ordering::Relaxed) {
+        if let Err(e) = backup::backup_config(path, backup_count) {
+            eprintln!("Failed to back up previous config: {}", e);
+        }
+    }
+    std::fs::write(path, serde_json::to_string_pretty(&json)?)
+        .with_context(|| format!("Failed to write config file: {}", path.display()))
+}
+
+/// A config file as JSON with its `extends` chain merged in
+pub fn read_resolved_json(path: &Path) -> Result<serde_json::Value> {
+    read_json(path, &mut Vec::new())
+}
+
+// Follows `extends` so a file only needs the fields it changes
+fn read_json(path: &Path, chain: &mut Vec<PathBuf>) -> Result<serde_json::Value> {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if chain.contains(&canonical) {
+        anyhow::bail!("Config extends itself: {}", path.display());
+    }
+    chain.push(canonical);
+
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+    let mut json: serde_json::Value = serde_json::from_str(&strip_json_comments(&content))
+        .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+
+    // Resolved per file, so a base config's paths stay relative to the base
+    let config_dir = path.parent().unwrap_or(Path::new("."));
+    for field in PATH_FIELDS {
+        if let Some(serde_json::Value::String(value)) = json.get_mut(field) {
+            // The Tux placeholder names the logo compiled into the binary,
+            // not a file next to the config
+            if field == "distro_image_path" && value == BUNDLED_LOGO {
+                continue;
+            }
+            *value = paths::expand(value, config_dir);
+        }
+    }
+
+    let base_path = match json.get("extends").and_then(|value| value.as_str()) {
+        Some(base) => PathBuf::from(base),
+        None => return Ok(json),
+    };
+    let mut merged = read_json(&base_path, chain)
+        .with_context(|| format!("Failed to load base config of {}", path.display()))?;
+    merge_json(&mut merged, json);
+    Ok(merged)
+}
+
+// Blank out `//` comments, as written by `--dump-default-config`. Newlines
+// are kept so parse errors still point at the right line.
+fn strip_json_comments(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            stripped.push(c);
+            match c {
+                '\\' => stripped.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if c == '/' && chars.peek() == Some(&'/') {
+            while chars.peek().is_some_and(|&next| next != '\n') {
+                chars.next();
+            }
+        } else {
+            in_string = c == '"';
+            stripped.push(c);
+        }
+    }
+
+    stripped
+}
+
+// Objects are merged key by key; anything else in `overlay` replaces `base`
+fn merge_json(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+// Shown above each key by `to_documented_json`, in this order
+const FIELD_DOCS: [(&str, &str); 47] = [
+    ("distro_image_path", "Path to the distro logo image"),
+    ("distro_image_size", "Logo [width, height] in pixels (16-1024)"),
+    ("hostname", "Device name or model"),
+    ("cpu", "Processor"),
+    ("memory", "RAM"),
+    ("startup_disk", "Boot disk"),
+    ("graphics", "GPU"),
+    ("serial_num", "System serial number"),
+    ("distro_name", "Distribution name shown instead of the detected one, or null"),
+    ("distro_version", "Distribution version shown instead of the detected one, or null"),
+    ("kernel", "Kernel version shown instead of the detected one, or null"),
+    ("locked_fields", "Overview fields that keep their saved value instead of being detected at launch"),
+    ("overview_margins", "Overview [left, right, top, bottom] margins (0-400)"),
+    ("section_space", "Spacing between sections (0-400)"),
+    ("logo_space", "Space between the logo and the information (0-400)"),
+    ("system_info_command", "Command run by the \"System Report\" button"),
+    ("software_update_command", "Command run by the \"Software Update\" button"),
+    ("extends", "Base config to inherit from, relative to this file"),
+    ("font-family", "Font family for all labels, or null for the default"),
+    ("css_path", "Stylesheet loaded on top of the bundled one"),
+    ("theme", "\"default\" or \"macos\" for a macOS About panel lookalike"),
+    ("hooks", "Commands run on open, refresh and export (on_open, on_refresh, on_export)"),
+    ("tabs", "Tabs to show, in order"),
+    ("custom_fields", "Extra [label, value] rows shown after the overview fields"),
+    ("show_uptime", "Show an Uptime row in the overview"),
+    ("show_desktop_environment", "Show a Desktop Environment row in the overview"),
+    ("show_window_manager", "Show a Window Manager row in the overview"),
+    ("show_session", "Show a Session row (Wayland or X11, seat, local or remote, login time) in the overview"),
+    ("show_region", "Show a Language & Region row (locale, keyboard layout, time zone) in the overview"),
+    ("show_battery", "Show a Battery row with the charge in the overview, on machines with a battery"),
+    ("custom_providers", "Script-based info providers: name, command, format and category"),
+    ("battery_saver", "Skip expensive detectors while on battery"),
+    ("auto_refresh_seconds", "Seconds between updates of uptime, memory, free space and temperatures, 0 disables"),
+    ("backend", "\"commands\" or \"native\" (sysinfo and sysfs only)"),
+    ("resizable", "Allow resizing; below 560 px wide the logo moves above the information"),
+    ("compact", "Half-size logo and margins with a scrolling overview, for small screens and tiling"),
+    ("privacy_mode", "Mask the serial number, in the window and in reports, until clicked"),
+    ("privacy_hide_hostname", "Mask the hostname as well when privacy_mode is on"),
+    ("background", "Keep running with a tray icon after the window is closed"),
+    ("placement", "\"default\", \"pointer\" or \"monitor\""),
+    ("placement_monitor", "Connector name (e.g. \"DP-1\") used when placement is \"monitor\""),
+    ("update_check_hours", "Check for package updates at startup and every N hours in the background, 0 disables"),
+    ("remember_window", "Reopen on the last tab, size and monitor"),
+    ("backup_count", "Previous configs kept in backups/ on save, 0 disables"),
+    ("units", "\"binary\" (GiB) or \"decimal\" (GB)"),
+    ("temperature_unit", "\"celsius\" or \"fahrenheit\""),
+    ("date_format", "strftime-style format for dates"),
+];
+
+#[derive(Debug, Default)]
+pub struct ConfigCheck {
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Settings whose values are run as shell commands
+pub const COMMAND_FIELDS: [&str; 4] = ["hooks", "custom_providers", "system_info_command", "software_update_command"];
+
+// Overview fields detected at launch, which locked_fields can pin
+pub const DETECTED_FIELDS: [&str; 6] = ["hostname", "cpu", "memory", "startup_disk", "graphics", "serial_num"];
+
+pub const IMAGE_SIZE_RANGE: (i32, i32) = (16, 1024);
+pub const MARGIN_RANGE: (i32, i32) = (0, 400);
+pub const SPACING_RANGE: (i32, i32) = (0, 400);
+
+fn clamp_field(name: &str, value: &mut i32, (min, max): (i32, i32), warnings: &mut Vec<String>) {
+    let clamped = (*value).clamp(min, max);
+    if clamped != *value {
+        warnings.push(format!(
+            "{} = {} is outside {}..{}, using {}",
+            name, value, min, max, clamped
+        ));
+        *value = clamped;
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// `distro_image_path` of the Tux logo compiled into the binary
+pub const BUNDLED_LOGO: &str = "tux-logo.png";
+
+pub const DEFAULT_TABS: [&str; 6] = ["overview", "display", "storage", "power", "support", "service"];
+
+fn default_tabs() -> Vec<String> {
+    DEFAULT_TABS.iter().map(|tab| tab.to_string()).collect()
+}
+
+fn default_backup_count() -> usize {
+    5
+}
+
+fn default_auto_refresh_seconds() -> u32 {
+    5
+}
+
+fn default_date_format() -> String {
+    units::DEFAULT_DATE_FORMAT.to_string()
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HookConfig {
+    pub on_open: Option<String>,
+    pub on_refresh: Option<String>,
+    pub on_export: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptProviderConfig {
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub format: ScriptFormat,
+    // Page to render into; defaults to a page of its own named after the provider
+    #[serde(default)]
+    pub category: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScriptFormat {
+    #[default]
+    Auto,
+    Lines,
+    Json,
 }
 
 impl Config {
     pub fn default() -> Self {
         Config {
-            distro_image_path: "tux-logo.png".to_string(),
+            distro_image_path: BUNDLED_LOGO.to_string(),
             distro_image_size: [512, 512],
             hostname: "".to_string(),
             cpu: "".to_string(),
@@ -32,46 +431,146 @@ impl Config {
             startup_disk: "".to_string(),
             graphics: "".to_string(),
             serial_num: "".to_string(),
+            distro_name: None,
+            distro_version: None,
+            kernel: None,
+            locked_fields: Vec::new(),
             overview_margins: [60, 60, 60, 60],
             section_space: 20,
             logo_space: 60,
             system_info_command: "".to_string(),
             software_update_command: "".to_string(),
+            extends: None,
             font_family: None,
+            css_path: None,
+            theme: ThemePreset::default(),
+            hooks: HookConfig::default(),
+            tabs: default_tabs(),
+            custom_fields: Vec::new(),
+            show_uptime: true,
+            show_desktop_environment: false,
+            show_window_manager: false,
+            show_session: false,
+            show_region: false,
+            show_battery: true,
+            custom_providers: Vec::new(),
+            battery_saver: true,
+            auto_refresh_seconds: default_auto_refresh_seconds(),
+            backend: Backend::default(),
+            resizable: false,
+            compact: false,
+            privacy_mode: false,
+            privacy_hide_hostname: false,
+            background: false,
+            placement: Placement::default(),
+            placement_monitor: None,
+            update_check_hours: 0,
+            remember_window: true,
+            backup_count: default_backup_count(),
+            units: UnitSystem::default(),
+            temperature_unit: TemperatureUnit::default(),
+            date_format: default_date_format(),
+            detection_errors: HashMap::new(),
+            validation_warnings: Vec::new(),
         }
     }
 
-    pub fn load(path: &PathBuf) -> Result<Self> {
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
-        
-        let mut config: Config = serde_json::from_str(&content)
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = read_json(path, &mut Vec::new())?;
+        let mut config: Config = serde_json::from_value(json)
             .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
-        
-        // Check if the image path exists, if not, use tux-logo.png as fallback
+
+        config.apply_env_overrides();
+        config.validation_warnings = config.validate();
+
+        // A logo that doesn't exist (any more) falls back to the bundled Tux,
+        // after validate() has noted it
         if !std::path::Path::new(&config.distro_image_path).exists() {
-            // Try different possible paths for tux-logo.png
-            let fallback_paths = vec![
-                "tux-logo.png".to_string(),
-                "./tux-logo.png".to_string(),
-                format!("{}/tux-logo.png", std::env::current_dir().unwrap_or_default().to_string_lossy()),
-            ];
-            
-            let mut found_fallback = false;
-            for fallback_path in fallback_paths {
-                if std::path::Path::new(&fallback_path).exists() {
-                    config.distro_image_path = fallback_path;
-                    found_fallback = true;
-                    break;
-                }
+            config.distro_image_path = BUNDLED_LOGO.to_string();
+        }
+
+        Ok(config)
+    }
+
+    /// Stricter checks than `load` for `--check-config`: the file is not
+    /// patched up, unknown keys and unreadable images are errors, and the
+    /// fix-ups `validate` would apply are reported as warnings
+    pub fn check(path: &Path) -> ConfigCheck {
+        let mut check = ConfigCheck::default();
+
+        let json = match read_json(path, &mut Vec::new()) {
+            Ok(json) => json,
+            Err(e) => {
+                check.errors.push(format!("{:#}", e));
+                return check;
             }
-            
-            if !found_fallback {
-                config.distro_image_path = "tux-logo.png".to_string();
+        };
+
+        // Every key Config knows about, including the optional ones
+        let known_keys = match serde_json::to_value(Config::default()) {
+            Ok(serde_json::Value::Object(map)) => map.keys().cloned().collect::<Vec<_>>(),
+            _ => Vec::new(),
+        };
+        if let Some(object) = json.as_object() {
+            for key in object.keys().filter(|key| !known_keys.contains(key)) {
+                check.errors.push(format!("Unknown key \"{}\"", key));
             }
         }
-        
-        Ok(config)
+
+        let mut config: Config = match serde_json::from_value(json) {
+            Ok(config) => config,
+            Err(e) => {
+                check.errors.push(format!("Invalid config: {}", e));
+                return check;
+            }
+        };
+
+        if Path::new(&config.distro_image_path).exists() {
+            if let Err(e) = gdk_pixbuf::Pixbuf::from_file(&config.distro_image_path) {
+                check.errors.push(format!(
+                    "Cannot load distro image {}: {}",
+                    config.distro_image_path, e
+                ));
+            }
+        }
+        if let Some(css_path) = config.css_path.as_deref() {
+            if let Err(e) = std::fs::read_to_string(css_path) {
+                check.errors.push(format!("Cannot read css_path {}: {}", css_path, e));
+            }
+        }
+
+        check.warnings = config.validate();
+        check
+    }
+
+    /// Pretty JSON with a `//` comment above each key, for
+    /// `--dump-default-config`. `load` skips the comments.
+    pub fn to_documented_json(&self) -> Result<String> {
+        let mut object = match serde_json::to_value(self).context("Failed to serialize config")? {
+            serde_json::Value::Object(object) => object,
+            _ => anyhow::bail!("Config did not serialize to an object"),
+        };
+
+        let mut entries = Vec::new();
+        for (key, doc) in FIELD_DOCS {
+            if let Some(value) = object.remove(key) {
+                entries.push((key.to_string(), Some(doc), value));
+            }
+        }
+        // Anything without docs yet still belongs in the output
+        entries.extend(object.into_iter().map(|(key, value)| (key, None, value)));
+
+        let mut output = String::from("{\n");
+        for (i, (key, doc, value)) in entries.iter().enumerate() {
+            if let Some(doc) = doc {
+                output.push_str(&format!("  // {}\n", doc));
+            }
+            let value = serde_json::to_string_pretty(value)?.replace('\n', "\n  ");
+            let separator = if i + 1 < entries.len() { "," } else { "" };
+            output.push_str(&format!("  {}: {}{}\n", serde_json::to_string(key)?, value, separator));
+        }
+        output.push('}');
+        Ok(output)
     }
 
     pub fn save(&self, path: &PathBuf) -> Result<()> {
@@ -82,6 +581,10 @@ impl Config {
             std::fs::create_dir_all(parent)
                 .context("Failed to create config directory")?;
         }
+
+        if let Err(e) = backup::backup_config(path, self.backup_count) {
+            eprintln!("Failed to back up previous config: {}", e);
+        }
         
         std::fs::write(path, content)
             .with_context(|| format!("Failed to write config file: {}", path.display()))?;
@@ -89,22 +592,152 @@ impl Config {
         Ok(())
     }
 
-    pub fn validate(&self) -> Result<()> {
-        if !std::path::Path::new(&self.distro_image_path).exists() {
-            return Err(anyhow::anyhow!(
-                "Distro image not found: {}",
-                self.distro_image_path
-            ));
+    /// Let `ATL_*` environment variables override individual fields, so one
+    /// config can be shared across kiosk or demo machines
+    pub fn apply_env_overrides(&mut self) {
+        let text_fields: [(&str, &str, &mut String); 7] = [
+            ("ATL_HOSTNAME", "hostname", &mut self.hostname),
+            ("ATL_CPU", "cpu", &mut self.cpu),
+            ("ATL_MEMORY", "memory", &mut self.memory),
+            ("ATL_STARTUP_DISK", "startup_disk", &mut self.startup_disk),
+            ("ATL_GRAPHICS", "graphics", &mut self.graphics),
+            ("ATL_SERIAL", "serial_num", &mut self.serial_num),
+            ("ATL_LOGO", "distro_image_path", &mut self.distro_image_path),
+        ];
+
+        for (var, field, value) in text_fields {
+            if let Ok(override_value) = std::env::var(var) {
+                *value = override_value;
+                // An explicit value is not a detection failure
+                self.detection_errors.remove(field);
+            }
         }
-        
-        if self.distro_image_size[0] <= 0 || self.distro_image_size[1] <= 0 {
-            return Err(anyhow::anyhow!("Invalid image size"));
+
+        if let Ok(size) = std::env::var("ATL_LOGO_SIZE") {
+            let parsed = size
+                .split_once('x')
+                .and_then(|(w, h)| Some([w.trim().parse().ok()?, h.trim().parse().ok()?]));
+            match parsed {
+                Some(size) => self.distro_image_size = size,
+                None => eprintln!("Ignoring ATL_LOGO_SIZE={}: expected WIDTHxHEIGHT", size),
+            }
         }
-        
-        if self.overview_margins.len() != 4 {
-            return Err(anyhow::anyhow!("Invalid margins array"));
+
+        if let Ok(font_family) = std::env::var("ATL_FONT_FAMILY") {
+            self.font_family = Some(font_family);
         }
-        
-        Ok(())
+    }
+
+    /// Whether `field` keeps its saved value instead of being detected at launch
+    pub fn is_locked(&self, field: &str) -> bool {
+        self.locked_fields.iter().any(|locked| locked == field)
+    }
+
+    /// Put back `saved`'s value of every field it locks, e.g. over freshly
+    /// detected ones
+    pub fn restore_locked(&mut self, saved: &Config) {
+        for field in &saved.locked_fields {
+            let (value, saved_value) = match field.as_str() {
+                "hostname" => (&mut self.hostname, &saved.hostname),
+                "cpu" => (&mut self.cpu, &saved.cpu),
+                "memory" => (&mut self.memory, &saved.memory),
+                "startup_disk" => (&mut self.startup_disk, &saved.startup_disk),
+                "graphics" => (&mut self.graphics, &saved.graphics),
+                "serial_num" => (&mut self.serial_num, &saved.serial_num),
+                _ => continue,
+            };
+            *value = saved_value.clone();
+            // Not detected, so nothing fell back either
+            self.detection_errors.remove(field);
+        }
+    }
+
+    /// Every shell command the config would run, as "setting: command"
+    pub fn commands(&self) -> Vec<String> {
+        let hooks = [
+            ("hooks.on_open", &self.hooks.on_open),
+            ("hooks.on_refresh", &self.hooks.on_refresh),
+            ("hooks.on_export", &self.hooks.on_export),
+        ];
+        let mut commands: Vec<String> = hooks
+            .into_iter()
+            .filter_map(|(name, command)| command.as_ref().map(|command| format!("{}: {}", name, command)))
+            .collect();
+        for provider in &self.custom_providers {
+            commands.push(format!("custom_providers \"{}\": {}", provider.name, provider.command));
+        }
+        for (name, command) in [
+            ("system_info_command", &self.system_info_command),
+            ("software_update_command", &self.software_update_command),
+        ] {
+            if !command.is_empty() {
+                commands.push(format!("{}: {}", name, command));
+            }
+        }
+        commands
+    }
+
+    /// What `validate` would fix up, without changing anything
+    pub fn problems(&self) -> Vec<String> {
+        self.clone().validate()
+    }
+
+    /// Clamp out-of-range values to sane bounds, returning a warning for each
+    /// fix. Nothing is printed; callers show the warnings where they fit.
+    pub fn validate(&mut self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        // The bundled Tux is loaded from the resources, not this path
+        if self.distro_image_path != BUNDLED_LOGO && !std::path::Path::new(&self.distro_image_path).exists() {
+            warnings.push(format!("Distro image not found: {}", self.distro_image_path));
+        }
+
+        for (i, axis) in ["width", "height"].iter().enumerate() {
+            clamp_field(
+                &format!("distro_image_size {}", axis),
+                &mut self.distro_image_size[i],
+                IMAGE_SIZE_RANGE,
+                &mut warnings,
+            );
+        }
+
+        for (i, side) in ["start", "end", "top", "bottom"].iter().enumerate() {
+            clamp_field(
+                &format!("overview_margins {}", side),
+                &mut self.overview_margins[i],
+                MARGIN_RANGE,
+                &mut warnings,
+            );
+        }
+
+        let provider_tabs: Vec<String> = self
+            .custom_providers
+            .iter()
+            .map(|provider| ScriptProvider::new(provider.clone()).category().to_string())
+            .collect();
+        self.tabs.retain(|tab| {
+            let known = DEFAULT_TABS.contains(&tab.as_str()) || provider_tabs.contains(tab);
+            if !known {
+                warnings.push(format!("Unknown tab \"{}\" in tabs, ignoring it", tab));
+            }
+            known
+        });
+        if self.tabs.is_empty() {
+            warnings.push("tabs is empty, showing the default tabs".to_string());
+            self.tabs = default_tabs();
+        }
+
+        self.locked_fields.retain(|field| {
+            let known = DETECTED_FIELDS.contains(&field.as_str());
+            if !known {
+                warnings.push(format!("Unknown field \"{}\" in locked_fields, ignoring it", field));
+            }
+            known
+        });
+
+        clamp_field("section_space", &mut self.section_space, SPACING_RANGE, &mut warnings);
+        clamp_field("logo_space", &mut self.logo_space, SPACING_RANGE, &mut warnings);
+
+        warnings
     }
 }
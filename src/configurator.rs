@@ -1,23 +1,139 @@
+use anyhow::{Context, Result};
 use gtk::prelude::*;
 use gtk::{
-    glib, Application, ApplicationWindow, Box, Button, Entry, FileChooserDialog, HeaderBar,
-    Image, Label, Orientation, ResponseType, ScrolledWindow, Stack, StackSwitcher,
+    gio, glib, Application, ApplicationWindow, Box, Button, CheckButton, Entry, FileChooserNative, HeaderBar,
+    Label, ListBox, Orientation, ResponseType, ScrolledWindow, SpinButton, Stack, Window,
 };
-use gdk_pixbuf::Pixbuf;
-use std::cell::RefCell;
-use std::path::PathBuf;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::time::Duration;
 
-use crate::config::Config;
+use crate::backup;
+use crate::bundle;
+use crate::config::{self, Config};
+use crate::logo_editor;
+use crate::logo_gallery;
 use crate::main_window::MainWindow;
+use crate::paths;
+use crate::profiles;
+use crate::report;
+use crate::status::Status;
 use crate::system_info::SystemInfo;
+use crate::theme;
+use crate::units;
+use crate::utils;
 use crate::{AUTHOR, AUTHOR_EMAIL, VERSION, ORIGINAL_AUTHOR, ORIGINAL_REPO, CURRENT_REPO};
 
+// Wizard pages as (stack name, title), in the order they are stepped through
+const STEPS: [(&str, &str); 5] = [
+    ("detect", "Detect"),
+    ("customize", "Customize"),
+    ("appearance", "Appearance"),
+    ("review", "Review"),
+    ("save", "Save"),
+];
+
+// Entries on the Customize page as (label, field)
+const ENTRY_FIELDS: [(&str, &str); 6] = [
+    ("Hostname/Model", "hostname"),
+    ("CPU", "cpu"),
+    ("Memory", "memory"),
+    ("Startup Disk", "startup_disk"),
+    ("Graphics", "graphics"),
+    ("Serial Number", "serial_num"),
+];
+
+// Spin buttons in the Layout section, with the range validate() clamps to
+const LAYOUT_FIELDS: [(&str, &str, (i32, i32)); 8] = [
+    ("logo_width", "Logo Width", config::IMAGE_SIZE_RANGE),
+    ("logo_height", "Logo Height", config::IMAGE_SIZE_RANGE),
+    ("margin_start", "Left Margin", config::MARGIN_RANGE),
+    ("margin_end", "Right Margin", config::MARGIN_RANGE),
+    ("margin_top", "Top Margin", config::MARGIN_RANGE),
+    ("margin_bottom", "Bottom Margin", config::MARGIN_RANGE),
+    ("section_space", "Section Spacing", config::SPACING_RANGE),
+    ("logo_space", "Logo Spacing", config::SPACING_RANGE),
+];
+
+const LOGO_THUMBNAIL_SIZE: i32 = 64;
+
+// Edits are gathered for this long before the preview is rebuilt
+const PREVIEW_DELAY: Duration = Duration::from_millis(300);
+
 pub struct ConfiguratorWindow {
     window: ApplicationWindow,
     config_path: PathBuf,
     config: Rc<RefCell<Config>>,
-    preview_image: Rc<RefCell<Option<Image>>>,
+    // Whether `config` came from an existing config file
+    existing: bool,
+    // The config as the configurator opened it, for Discard Changes
+    loaded: Rc<Config>,
+    // What the reset buttons go back to: the loaded values, or the detected
+    // ones once detection has run
+    reference: Rc<RefCell<Config>>,
+    preview: LivePreview,
+    entries: Rc<RefCell<HashMap<String, Entry>>>,
+}
+
+// The Review page: the main window's own overview, rendered from the
+// in-memory config and rebuilt as it is edited. Edits also go to the
+// `on_change` callbacks, which keep the validation up to date.
+#[derive(Clone)]
+struct LivePreview {
+    slot: Box,
+    renderer: MainWindow,
+    config: Rc<RefCell<Config>>,
+    pending: Rc<Cell<bool>>,
+    listeners: Rc<RefCell<Vec<ChangeListener>>>,
+}
+
+type ChangeListener = std::boxed::Box<dyn Fn(&Config)>;
+
+impl LivePreview {
+    fn new(config: Rc<RefCell<Config>>) -> Self {
+        let slot = Box::new(Orientation::Vertical, 0);
+        slot.set_vexpand(true);
+        slot.add_css_class(theme::PREVIEW_FONT_CLASS);
+        LivePreview {
+            slot,
+            renderer: MainWindow::preview(config.clone()),
+            config,
+            pending: Rc::new(Cell::new(false)),
+            listeners: Rc::new(RefCell::new(Vec::new())),
+        }
+    }
+
+    fn on_change(&self, listener: impl Fn(&Config) + 'static) {
+        listener(&self.config.borrow());
+        self.listeners.borrow_mut().push(std::boxed::Box::new(listener));
+    }
+
+    /// Rebuild once edits settle. Every entry, spin button, Auto toggle and
+    /// logo choice calls this after writing to the shared config, which is
+    /// how unsaved edits reach the preview.
+    fn schedule(&self) {
+        if self.pending.replace(true) {
+            return;
+        }
+        let preview = self.clone();
+        glib::timeout_add_local_once(PREVIEW_DELAY, move || {
+            preview.pending.set(false);
+            preview.rebuild();
+            let config = preview.config.borrow();
+            for listener in preview.listeners.borrow().iter() {
+                listener(&config);
+            }
+        });
+    }
+
+    fn rebuild(&self) {
+        while let Some(child) = self.slot.first_child() {
+            self.slot.remove(&child);
+        }
+        self.slot.append(&self.renderer.overview_preview());
+    }
 }
 
 impl ConfiguratorWindow {
@@ -30,17 +146,35 @@ impl ConfiguratorWindow {
             .resizable(true)
             .build();
 
-        let config = Rc::new(RefCell::new(Config::default()));
-        let preview_image = Rc::new(RefCell::new(None));
+        // An existing config is edited rather than started over, and detection
+        // previews and backup times follow its unit preferences
+        let saved = Config::load(&config_path).ok();
+        if let Some(saved) = &saved {
+            units::configure(saved);
+        }
+        let existing = saved.is_some();
+
+        let loaded = saved.unwrap_or_else(Config::default);
+        let config = Rc::new(RefCell::new(loaded.clone()));
+        let preview = LivePreview::new(config.clone());
 
         let configurator = ConfiguratorWindow {
             window,
             config_path,
             config,
-            preview_image,
+            existing,
+            reference: Rc::new(RefCell::new(loaded.clone())),
+            loaded: Rc::new(loaded),
+            preview,
+            entries: Rc::new(RefCell::new(HashMap::new())),
         };
 
         configurator.build_ui();
+        if existing {
+            // Cloned first: filling the entries writes back into the config
+            let current = configurator.config.borrow().clone();
+            fill_entries(&configurator.entries.borrow(), &current);
+        }
         configurator
     }
 
@@ -50,25 +184,82 @@ impl ConfiguratorWindow {
         header_bar.set_show_title_buttons(true);
         self.window.set_titlebar(Some(&header_bar));
 
-        // Create stack and stack switcher (same as main window)
+        // One page per wizard step, walked through with Back and Next
         let stack = Stack::new();
         stack.set_transition_type(gtk::StackTransitionType::SlideLeftRight);
         stack.set_transition_duration(500);
+        stack.set_vexpand(true);
+
+        let progress = Label::new(None);
+        header_bar.set_title_widget(Some(&progress));
+        header_bar.pack_end(&self.create_about_button());
 
-        let stack_switcher = StackSwitcher::new();
-        stack_switcher.set_stack(Some(&stack));
-        header_bar.set_title_widget(Some(&stack_switcher));
+        self.create_system_detection_page(&stack);
+        self.create_customize_pages(&stack);
+        self.create_preview_page(&stack);
 
-        // Create configuration tabs
-        self.create_system_detection_tab(&stack);
-        self.create_manual_config_tab(&stack);
-        self.create_preview_tab(&stack);
-        self.create_about_tab(&stack);
+        let nav_box = Box::new(Orientation::Horizontal, 10);
+        nav_box.set_margin_start(40);
+        nav_box.set_margin_end(40);
+        nav_box.set_margin_bottom(20);
+
+        let back_button = Button::with_label("Back");
+        nav_box.append(&back_button);
+
+        // Why Next didn't move on
+        let problem_label = Label::new(None);
+        problem_label.set_hexpand(true);
+        problem_label.set_wrap(true);
+        problem_label.add_css_class(Status::Warning.css_class());
+        nav_box.append(&problem_label);
+
+        let next_button = Button::with_label("Next");
+        next_button.set_css_classes(&["suggested-action"]);
+        nav_box.append(&next_button);
+
+        let stack_clone = stack.clone();
+        back_button.connect_clicked(move |_| {
+            let current = step_index(&stack_clone);
+            if current > 0 {
+                stack_clone.set_visible_child_name(STEPS[current - 1].0);
+            }
+        });
+
+        let stack_clone = stack.clone();
+        let config_clone = self.config.clone();
+        let problem_clone = problem_label.clone();
+        next_button.connect_clicked(move |_| {
+            let current = step_index(&stack_clone);
+            match step_problem(STEPS[current].0, &config_clone.borrow()) {
+                Some(problem) => problem_clone.set_text(&problem),
+                None if current + 1 < STEPS.len() => stack_clone.set_visible_child_name(STEPS[current + 1].0),
+                None => {}
+            }
+        });
 
-        self.window.set_child(Some(&stack));
+        // Detection, restore and import replace the whole config, so the
+        // preview is brought up to date whenever it is shown
+        let preview = self.preview.clone();
+        let update_nav = move |stack: &Stack| {
+            let current = step_index(stack);
+            progress.set_markup(&step_markup(current));
+            back_button.set_sensitive(current > 0);
+            next_button.set_visible(current + 1 < STEPS.len());
+            problem_label.set_text("");
+            if STEPS[current].0 == "review" {
+                preview.rebuild();
+            }
+        };
+        update_nav(&stack);
+        stack.connect_visible_child_name_notify(update_nav);
+
+        let content = Box::new(Orientation::Vertical, 0);
+        content.append(&stack);
+        content.append(&nav_box);
+        self.window.set_child(Some(&content));
     }
 
-    fn create_system_detection_tab(&self, stack: &Stack) {
+    fn create_system_detection_page(&self, stack: &Stack) {
         let main_box = Box::new(Orientation::Vertical, 20);
         main_box.set_margin_start(40);
         main_box.set_margin_end(40);
@@ -83,7 +274,8 @@ impl ConfiguratorWindow {
         let info_label = Label::new(Some(
             "This will automatically detect your system information using fastfetch and dmidecode.\n\
             Please ensure you have both tools installed before continuing.\n\
-            Root access may be required for some hardware information.",
+            Root access may be required for some hardware information.\n\
+            You can also skip this step and fill everything in yourself.",
         ));
         info_label.set_halign(gtk::Align::Start);
         main_box.append(&info_label);
@@ -92,6 +284,9 @@ impl ConfiguratorWindow {
         detect_button.set_halign(gtk::Align::Start);
 
         let config_clone = self.config.clone();
+        let reference = self.reference.clone();
+        let entries = self.entries.clone();
+        let preview = self.preview.clone();
         let window_clone = self.window.clone();
         detect_button.connect_clicked(move |button| {
             button.set_sensitive(false);
@@ -99,21 +294,29 @@ impl ConfiguratorWindow {
 
             // Run detection in background
             let config_clone = config_clone.clone();
+            let reference = reference.clone();
+            let entries = entries.clone();
+            let preview = preview.clone();
             let button_clone = button.clone();
             let window_clone = window_clone.clone();
 
             glib::spawn_future_local(async move {
                 match SystemInfo::detect() {
                     Ok(system_info) => {
+                        // Shown in the Customize page straight away, so
+                        // nothing is lost if no logo is picked
+                        system_info.apply_to(&mut config_clone.borrow_mut());
+                        system_info.apply_to(&mut reference.borrow_mut());
+                        let detected = config_clone.borrow().clone();
+                        fill_entries(&entries.borrow(), &detected);
+
                         // Show file chooser for distro image
-                        let file_chooser = FileChooserDialog::new(
+                        let file_chooser = FileChooserNative::new(
                             Some("Select Distro Logo"),
                             Some(&window_clone),
                             gtk::FileChooserAction::Open,
-                            &[
-                                ("Cancel", ResponseType::Cancel),
-                                ("Open", ResponseType::Accept),
-                            ],
+                            Some("Open"),
+                            Some("Cancel"),
                         );
 
                         // Add image filters
@@ -122,35 +325,23 @@ impl ConfiguratorWindow {
                         filter.add_mime_type("image/*");
                         file_chooser.add_filter(&filter);
 
+                        // Without a new image the current logo is kept
+                        let parent = window_clone.clone();
                         file_chooser.connect_response(move |dialog, response| {
                             if response == ResponseType::Accept {
-                                if let Some(file) = dialog.file() {
-                                    if let Some(path) = file.path() {
-                                        // Copy image to config directory
-                                        let config_dir = ConfiguratorWindow::get_config_dir();
-                                        let dest_path = config_dir.join("distro-logo.png");
-                                        
-                                        if let Err(e) = std::fs::copy(&path, &dest_path) {
-                                            eprintln!("Failed to copy image: {}", e);
-                                        } else {
-                                            let config = system_info.to_config(dest_path.to_string_lossy().to_string());
-                                            *config_clone.borrow_mut() = config;
-                                        }
-                                    } else {
-                                        // If no image selected, use tux-logo.png as fallback
-                                        let config = system_info.to_config("tux-logo.png".to_string());
-                                        *config_clone.borrow_mut() = config;
-                                    }
-                                } else {
-                                    // If no file selected, use tux-logo.png as fallback
-                                    let config = system_info.to_config("tux-logo.png".to_string());
-                                    *config_clone.borrow_mut() = config;
+                                if let Some(path) = dialog.file().and_then(|file| file.path()) {
+                                    let config_clone = config_clone.clone();
+                                    let preview = preview.clone();
+                                    logo_editor::edit(&parent, &path, move |logo_path| {
+                                        config_clone.borrow_mut().distro_image_path =
+                                            logo_path.to_string_lossy().to_string();
+                                        preview.schedule();
+                                    });
                                 }
                             }
-                            dialog.close();
                         });
 
-                        file_chooser.present();
+                        utils::show_file_chooser(&file_chooser);
                     }
                     Err(e) => {
                         eprintln!("Failed to detect system info: {}", e);
@@ -174,47 +365,18 @@ impl ConfiguratorWindow {
 
         main_box.append(&detect_button);
 
-        let next_button = Button::with_label("Next: Manual Configuration");
-        next_button.set_halign(gtk::Align::Start);
-        
-        let stack_clone = stack.clone();
-        next_button.connect_clicked(move |_| {
-            stack_clone.set_visible_child_name("manual_config");
-        });
-
-        main_box.append(&next_button);
-
-        stack.add_titled(&main_box, Some("system_detection"), "Auto Detection");
+        stack.add_titled(&main_box, Some("detect"), "Detect");
     }
 
-    fn create_manual_config_tab(&self, stack: &Stack) {
-        let scrolled = ScrolledWindow::new();
-        scrolled.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+    // The Customize, Appearance and Save pages, which share the widgets that
+    // restore, import and discard fill in
+    fn create_customize_pages(&self, stack: &Stack) {
+        let main_box = page_box("Customize");
 
-        let main_box = Box::new(Orientation::Vertical, 15);
-        main_box.set_margin_start(40);
-        main_box.set_margin_end(40);
-        main_box.set_margin_top(40);
-        main_box.set_margin_bottom(40);
-
-        let title_label = Label::new(None);
-        title_label.set_markup("<span font-size='large'><b>Manual Configuration</b></span>");
-        title_label.set_halign(gtk::Align::Start);
-        main_box.append(&title_label);
+        let entries = self.entries.clone();
+        let mut auto = HashMap::new();
 
-        // Create entry fields for all configuration options
-        let fields = vec![
-            ("Hostname/Model", "hostname"),
-            ("CPU", "cpu"),
-            ("Memory", "memory"),
-            ("Startup Disk", "startup_disk"),
-            ("Graphics", "graphics"),
-            ("Serial Number", "serial_num"),
-        ];
-
-        let entries = Rc::new(RefCell::new(std::collections::HashMap::new()));
-
-        for (label_text, field_name) in fields {
+        for (label_text, field_name) in ENTRY_FIELDS {
             let field_box = Box::new(Orientation::Vertical, 5);
             
             let label = Label::new(Some(label_text));
@@ -223,32 +385,121 @@ impl ConfiguratorWindow {
 
             let entry = Entry::new();
             entry.set_placeholder_text(Some(&format!("Enter {}", label_text.to_lowercase())));
-            field_box.append(&entry);
+            let config_clone = self.config.clone();
+            let preview = self.preview.clone();
+            let field = field_name.to_string();
+            entry.connect_changed(move |entry| {
+                set_field(&mut config_clone.borrow_mut(), &field, &entry.text());
+                preview.schedule();
+            });
+            let row = with_reset_button(&entry, field_name, self.reference.clone());
+
+            // Ticked fields are detected again at every launch; the value
+            // typed here is only used once it is unticked
+            let auto_check = CheckButton::with_label("Auto");
+            auto_check.set_tooltip_text(Some("Detect again at every launch instead of keeping this value"));
+            auto_check.set_active(!self.config.borrow().is_locked(field_name));
+            let config_clone = self.config.clone();
+            let preview = self.preview.clone();
+            auto_check.connect_toggled(move |check| {
+                set_locked(&mut config_clone.borrow_mut(), field_name, !check.is_active());
+                preview.schedule();
+            });
+            row.append(&auto_check);
+            field_box.append(&row);
 
             entries.borrow_mut().insert(field_name.to_string(), entry);
+            auto.insert(field_name.to_string(), auto_check);
             main_box.append(&field_box);
         }
 
+        let auto = Rc::new(auto);
+        stack.add_titled(&scrolled_page(&main_box), Some("customize"), "Customize");
+
+        let main_box = page_box("Appearance");
+
+        // Font family, checked against the installed fonts while typing
+        let font_box = Box::new(Orientation::Vertical, 5);
+
+        let font_label = Label::new(Some("Font Family"));
+        font_label.set_halign(gtk::Align::Start);
+        font_box.append(&font_label);
+
+        let font_entry = Entry::new();
+        font_entry.set_placeholder_text(Some("Default system font"));
+        font_box.append(&with_reset_button(&font_entry, "font_family", self.reference.clone()));
+
+        let font_status = Label::new(None);
+        font_status.set_halign(gtk::Align::Start);
+        font_box.append(&font_status);
+
+        let config_clone = self.config.clone();
+        let preview = self.preview.clone();
+        font_entry.connect_changed(move |entry| {
+            let family = entry.text();
+            set_field(&mut config_clone.borrow_mut(), "font_family", &family);
+            preview.schedule();
+            font_status.remove_css_class(Status::Ok.css_class());
+            font_status.remove_css_class(Status::Warning.css_class());
+
+            let available = theme::font_available(entry, &family);
+            if family.trim().is_empty() {
+                font_status.set_text("");
+            } else if available {
+                font_status.set_text("Installed");
+                font_status.add_css_class(Status::Ok.css_class());
+            } else {
+                font_status.set_text("Not installed, the default font will be used");
+                font_status.add_css_class(Status::Warning.css_class());
+            }
+
+            // Live preview in the Review page
+            let family = if available { Some(family.as_str()) } else { None };
+            theme::apply_font_family(entry, theme::PREVIEW_FONT_CLASS, family);
+        });
+
+        entries.borrow_mut().insert("font_family".to_string(), font_entry);
+        main_box.append(&font_box);
+
         // Image selection
         let image_box = Box::new(Orientation::Vertical, 5);
         let image_label = Label::new(Some("Distro Logo"));
         image_label.set_halign(gtk::Align::Start);
         image_box.append(&image_label);
 
+        // Bundled logos, with the running distribution's picked to start with
+        // unless an existing config already has a logo
+        let config_clone = self.config.clone();
+        let preview = self.preview.clone();
+        let use_gallery_logo = move |index: usize| match logo_gallery::install(index) {
+            Ok(path) => {
+                config_clone.borrow_mut().distro_image_path = path;
+                preview.schedule();
+            }
+            Err(e) => eprintln!("Failed to use bundled logo: {:#}", e),
+        };
+        let detected = logo_gallery::detected();
+        if !self.existing {
+            use_gallery_logo(detected);
+        }
+        let gallery = logo_gallery::picker(use_gallery_logo);
+        if !self.existing {
+            logo_gallery::select(&gallery, detected);
+        }
+        image_box.append(&gallery);
+
         let image_button = Button::with_label("Select Image File");
         let config_clone = self.config.clone();
-        let preview_image_clone = self.preview_image.clone();
+        let preview = self.preview.clone();
         let window_clone = self.window.clone();
 
         image_button.connect_clicked(move |_| {
-            let file_chooser = FileChooserDialog::new(
+            let file_chooser = FileChooserNative::new(
                 Some("Select Distro Logo"),
                 Some(&window_clone),
                 gtk::FileChooserAction::Open,
-                &[
-                    ("Cancel", ResponseType::Cancel),
-                    ("Open", ResponseType::Accept),
-                ],
+                Some("Open"),
+                Some("Cancel"),
             );
 
             let filter = gtk::FileFilter::new();
@@ -257,39 +508,119 @@ impl ConfiguratorWindow {
             file_chooser.add_filter(&filter);
 
             let config_clone = config_clone.clone();
-            let preview_image_clone = preview_image_clone.clone();
+            let preview = preview.clone();
+            let parent = window_clone.clone();
 
             file_chooser.connect_response(move |dialog, response| {
                 if response == ResponseType::Accept {
-                    if let Some(file) = dialog.file() {
-                        if let Some(path) = file.path() {
-                            let config_dir = ConfiguratorWindow::get_config_dir();
-                            let dest_path = config_dir.join("distro-logo.png");
-                            
-                            if let Err(e) = std::fs::copy(&path, &dest_path) {
-                                eprintln!("Failed to copy image: {}", e);
-                            } else {
-                                config_clone.borrow_mut().distro_image_path = dest_path.to_string_lossy().to_string();
-                                
-                                // Update preview image
-                                if let Ok(pixbuf) = Pixbuf::from_file_at_scale(&dest_path, 100, 100, true) {
-                                    let image = Image::from_pixbuf(Some(&pixbuf));
-                                    *preview_image_clone.borrow_mut() = Some(image);
-                                }
-                            }
-                        }
+                    if let Some(path) = dialog.file().and_then(|file| file.path()) {
+                        let config_clone = config_clone.clone();
+                        let preview = preview.clone();
+                        logo_editor::edit(&parent, &path, move |logo_path| {
+                            config_clone.borrow_mut().distro_image_path = logo_path.to_string_lossy().to_string();
+                            preview.schedule();
+                        });
                     }
                 }
-                dialog.close();
             });
 
-            file_chooser.present();
+            utils::show_file_chooser(&file_chooser);
         });
 
-        image_box.append(&image_button);
+        let image_buttons = Box::new(Orientation::Horizontal, 10);
+        image_buttons.append(&image_button);
+
+        let find_button = Button::with_label("Find Logos on This System…");
+        find_button.set_tooltip_text(Some("Choose from the logos installed by your distribution and icon theme"));
+        let config_clone = self.config.clone();
+        let preview = self.preview.clone();
+        let gallery_clone = gallery.clone();
+        let window_clone = self.window.clone();
+        find_button.connect_clicked(move |_| {
+            let config_clone = config_clone.clone();
+            let preview = preview.clone();
+            let gallery_clone = gallery_clone.clone();
+            let parent = window_clone.clone();
+            ConfiguratorWindow::show_logo_finder(&window_clone, move |path| {
+                let config_clone = config_clone.clone();
+                let preview = preview.clone();
+                let gallery_clone = gallery_clone.clone();
+                logo_editor::edit(&parent, &path, move |logo_path| {
+                    config_clone.borrow_mut().distro_image_path = logo_path.to_string_lossy().to_string();
+                    gallery_clone.unselect_all();
+                    preview.schedule();
+                });
+            });
+        });
+        image_buttons.append(&find_button);
+        image_box.append(&image_buttons);
+
+        let drop_hint = Label::new(Some("Or drop an image file here"));
+        drop_hint.set_halign(gtk::Align::Start);
+        drop_hint.add_css_class("dim-label");
+        image_box.append(&drop_hint);
+
+        let logo_warning = Label::new(None);
+        logo_warning.set_halign(gtk::Align::Start);
+        logo_warning.set_wrap(true);
+        logo_warning.add_css_class(Status::Warning.css_class());
+        image_box.append(&logo_warning);
+
+        let config_clone = self.config.clone();
+        let preview = self.preview.clone();
+        let gallery_clone = gallery.clone();
+        logo_editor::accept_drops(&image_box, &self.window, move |logo_path| {
+            config_clone.borrow_mut().distro_image_path = logo_path.to_string_lossy().to_string();
+            gallery_clone.unselect_all();
+            preview.schedule();
+        });
         main_box.append(&image_box);
 
-        // Save button
+        // Layout, the values people tweak most when theming
+        let layout_label = Label::new(Some("Layout"));
+        layout_label.set_halign(gtk::Align::Start);
+        main_box.append(&layout_label);
+
+        let layout_grid = gtk::Grid::new();
+        layout_grid.set_row_spacing(6);
+        layout_grid.set_column_spacing(12);
+        let mut layout = HashMap::new();
+        for (row, (field_name, label_text, (min, max))) in LAYOUT_FIELDS.into_iter().enumerate() {
+            let label = Label::new(Some(label_text));
+            label.set_halign(gtk::Align::Start);
+            layout_grid.attach(&label, 0, row as i32, 1, 1);
+
+            let spin = SpinButton::with_range(min as f64, max as f64, 1.0);
+            spin.set_value(layout_value(&self.config.borrow(), field_name) as f64);
+            let config_clone = self.config.clone();
+            let preview = self.preview.clone();
+            spin.connect_value_changed(move |spin| {
+                set_layout_value(&mut config_clone.borrow_mut(), field_name, spin.value_as_int());
+                preview.schedule();
+            });
+            layout_grid.attach(&spin, 1, row as i32, 1, 1);
+            layout.insert(field_name.to_string(), spin);
+        }
+        main_box.append(&layout_grid);
+        let layout = Rc::new(layout);
+        stack.add_titled(&scrolled_page(&main_box), Some("appearance"), "Appearance");
+
+        let main_box = page_box("Save");
+        let save_info = Label::new(Some(
+            "Save the configuration and open About this Linux, or export it to use on another machine.",
+        ));
+        save_info.set_halign(gtk::Align::Start);
+        save_info.set_wrap(true);
+        main_box.append(&save_info);
+
+        // Errors have to be fixed before saving; warnings are what loading
+        // the config would fix up by itself
+        let problems_label = Label::new(None);
+        problems_label.set_halign(gtk::Align::Start);
+        problems_label.set_wrap(true);
+        problems_label.add_css_class(Status::Warning.css_class());
+        main_box.append(&problems_label);
+
         let save_box = Box::new(Orientation::Horizontal, 10);
         let save_button = Button::with_label("Save Configuration");
         save_button.set_css_classes(&["suggested-action"]);
@@ -304,23 +635,14 @@ impl ConfiguratorWindow {
             let mut config = config_clone.borrow_mut();
             let entries = entries_clone.borrow();
 
-            if let Some(entry) = entries.get("hostname") {
-                config.hostname = entry.text().to_string();
-            }
-            if let Some(entry) = entries.get("cpu") {
-                config.cpu = entry.text().to_string();
+            for (field_name, entry) in entries.iter() {
+                set_field(&mut config, field_name, &entry.text());
             }
-            if let Some(entry) = entries.get("memory") {
-                config.memory = entry.text().to_string();
-            }
-            if let Some(entry) = entries.get("startup_disk") {
-                config.startup_disk = entry.text().to_string();
-            }
-            if let Some(entry) = entries.get("graphics") {
-                config.graphics = entry.text().to_string();
-            }
-            if let Some(entry) = entries.get("serial_num") {
-                config.serial_num = entry.text().to_string();
+
+            // Restore and import can bring in values the earlier steps would refuse
+            if let Some(problem) = STEPS.iter().find_map(|(step, _)| step_problem(step, &config)) {
+                utils::show_error_dialog(Some(window_clone.upcast_ref()), "Configuration", &problem);
+                return;
             }
 
             // Save configuration
@@ -349,75 +671,439 @@ impl ConfiguratorWindow {
 
         save_box.append(&save_button);
 
-        let preview_button = Button::with_label("Preview");
-        let stack_clone = stack.clone();
-        preview_button.connect_clicked(move |_| {
-            stack_clone.set_visible_child_name("preview");
+        let restore_button = Button::with_label("Restore previous configuration…");
+        let entries_clone = entries.clone();
+        let layout_clone = layout.clone();
+        let auto_clone = auto.clone();
+        let config_clone = self.config.clone();
+        let config_path = self.config_path.clone();
+        let window_clone = self.window.clone();
+        restore_button.connect_clicked(move |_| {
+            ConfiguratorWindow::show_restore_dialog(
+                &window_clone,
+                &config_path,
+                config_clone.clone(),
+                entries_clone.clone(),
+                layout_clone.clone(),
+                auto_clone.clone(),
+            );
+        });
+        save_box.append(&restore_button);
+
+        let discard_button = Button::with_label("Discard Changes");
+        discard_button.set_tooltip_text(Some("Go back to the configuration as it was when the configurator opened"));
+        let entries_clone = entries.clone();
+        let layout_clone = layout.clone();
+        let auto_clone = auto.clone();
+        let gallery_clone = gallery.clone();
+        let config_clone = self.config.clone();
+        let reference = self.reference.clone();
+        let loaded = self.loaded.clone();
+        let preview = self.preview.clone();
+        discard_button.connect_clicked(move |_| {
+            *config_clone.borrow_mut() = (*loaded).clone();
+            *reference.borrow_mut() = (*loaded).clone();
+            fill_entries(&entries_clone.borrow(), &loaded);
+            fill_layout(&layout_clone, &loaded);
+            fill_auto(&auto_clone, &loaded);
+            gallery_clone.unselect_all();
+            preview.schedule();
+        });
+        save_box.append(&discard_button);
+
+        let export_button = Button::with_label("Export…");
+        export_button.set_tooltip_text(Some(
+            "Save the configuration and its logo as a .tar.gz bundle, or just the configuration as .json",
+        ));
+        let config_clone = self.config.clone();
+        let window_clone = self.window.clone();
+        export_button.connect_clicked(move |_| {
+            ConfiguratorWindow::show_export_dialog(&window_clone, config_clone.clone());
         });
+        save_box.append(&export_button);
 
-        save_box.append(&preview_button);
+        let import_button = Button::with_label("Import…");
+        import_button.set_tooltip_text(Some("Load a configuration bundle or a .json configuration"));
+        let entries_clone = entries.clone();
+        let layout_clone = layout.clone();
+        let auto_clone = auto.clone();
+        let config_clone = self.config.clone();
+        let config_path = self.config_path.clone();
+        let window_clone = self.window.clone();
+        import_button.connect_clicked(move |_| {
+            ConfiguratorWindow::show_import_dialog(
+                &window_clone,
+                &config_path,
+                config_clone.clone(),
+                entries_clone.clone(),
+                layout_clone.clone(),
+                auto_clone.clone(),
+            );
+        });
+        save_box.append(&import_button);
         main_box.append(&save_box);
+        main_box.append(&self.create_profile_row());
+
+        let reset_box = Box::new(Orientation::Horizontal, 10);
 
-        scrolled.set_child(Some(&main_box));
-        stack.add_titled(&scrolled, Some("manual_config"), "Manual Config");
+        let reset_button = Button::with_label("Reset to Defaults…");
+        reset_button.set_tooltip_text(Some("Start over from the default settings and detect the system again"));
+        let entries_clone = entries.clone();
+        let layout_clone = layout.clone();
+        let auto_clone = auto.clone();
+        let gallery_clone = gallery.clone();
+        let config_clone = self.config.clone();
+        let reference = self.reference.clone();
+        let preview = self.preview.clone();
+        let window_clone = self.window.clone();
+        reset_button.connect_clicked(move |reset_button| {
+            let entries_clone = entries_clone.clone();
+            let layout_clone = layout_clone.clone();
+            let auto_clone = auto_clone.clone();
+            let gallery_clone = gallery_clone.clone();
+            let config_clone = config_clone.clone();
+            let reference = reference.clone();
+            let preview = preview.clone();
+            let reset_button = reset_button.clone();
+            utils::confirm(
+                window_clone.upcast_ref(),
+                "Reset to the default settings?",
+                "Every field, the logo and the layout go back to their defaults and the system is detected \
+                again. Nothing is saved until you save the configuration.",
+                "Reset",
+                move || {
+                    // Detection can take a while with SMART and lshw, so it
+                    // runs off the main thread
+                    reset_button.set_sensitive(false);
+                    reset_button.set_label("Detecting…");
+                    let reset_button = reset_button.clone();
+                    let entries_clone = entries_clone.clone();
+                    let layout_clone = layout_clone.clone();
+                    let auto_clone = auto_clone.clone();
+                    let gallery_clone = gallery_clone.clone();
+                    let config_clone = config_clone.clone();
+                    let reference = reference.clone();
+                    let preview = preview.clone();
+                    glib::spawn_future_local(async move {
+                        let detected = gio::spawn_blocking(SystemInfo::detect).await;
+                        reset_button.set_sensitive(true);
+                        reset_button.set_label("Reset to Defaults…");
+
+                        let mut defaults = Config::default();
+                        if let Ok(Ok(system_info)) = detected {
+                            system_info.apply_to(&mut defaults);
+                        }
+                        let logo = logo_gallery::detected();
+                        match logo_gallery::install(logo) {
+                            Ok(path) => defaults.distro_image_path = path,
+                            Err(e) => eprintln!("Failed to use bundled logo: {:#}", e),
+                        }
+                        *config_clone.borrow_mut() = defaults.clone();
+                        *reference.borrow_mut() = defaults.clone();
+                        fill_entries(&entries_clone.borrow(), &defaults);
+                        fill_layout(&layout_clone, &defaults);
+                        fill_auto(&auto_clone, &defaults);
+                        logo_gallery::select(&gallery_clone, logo);
+                        preview.schedule();
+                    });
+                },
+            );
+        });
+        reset_box.append(&reset_button);
+
+        let delete_button = Button::with_label("Delete Saved Configuration…");
+        delete_button.add_css_class("destructive-action");
+        delete_button.set_sensitive(self.config_path.exists());
+        let config_path = self.config_path.clone();
+        let window_clone = self.window.clone();
+        delete_button.connect_clicked(move |_| {
+            let config_path = config_path.clone();
+            let parent = window_clone.clone();
+            utils::confirm(
+                window_clone.upcast_ref(),
+                "Delete the saved configuration?",
+                &format!(
+                    "{} is removed and About this Linux detects everything again at the next launch. \
+                    A copy is kept with the backups, so it can still be restored.",
+                    config_path.display()
+                ),
+                "Delete",
+                move || {
+                    let keep = Config::load(&config_path)
+                        .map(|config| config.backup_count)
+                        .unwrap_or(Config::default().backup_count);
+                    // The dialog promised a copy, so there is one even with
+                    // backups turned off, and no delete without it
+                    if let Err(e) = backup::backup_config(&config_path, keep.max(1)) {
+                        eprintln!("Failed to back up the config before deleting it: {:#}", e);
+                        utils::show_error_dialog(
+                            Some(parent.upcast_ref()),
+                            "Delete Failed",
+                            &format!("The configuration was not deleted because it could not be backed up: {:#}", e),
+                        );
+                        return;
+                    }
+                    match std::fs::remove_file(&config_path) {
+                        // Start over from a config that doesn't exist
+                        Ok(()) => reopen(&parent, config_path.clone()),
+                        Err(e) => {
+                            eprintln!("Failed to delete {}: {}", config_path.display(), e);
+                            utils::show_error_dialog(
+                                Some(parent.upcast_ref()),
+                                "Delete Failed",
+                                &format!("Failed to delete {}: {}", config_path.display(), e),
+                            );
+                        }
+                    }
+                },
+            );
+        });
+        reset_box.append(&delete_button);
+        main_box.append(&reset_box);
+
+        let entries_clone = entries.clone();
+        self.preview.on_change(move |config| {
+            for (label, field) in ENTRY_FIELDS {
+                if let Some(entry) = entries_clone.borrow().get(field) {
+                    mark_entry(entry, field_problem(config, label, field).as_deref());
+                }
+            }
+            let logo = logo_problem(config);
+            logo_warning.set_text(logo.as_deref().unwrap_or(""));
+
+            let errors: Vec<String> = STEPS.iter().filter_map(|(step, _)| step_problem(step, config)).collect();
+            let lines: Vec<String> = errors
+                .iter()
+                .map(|error| format!("Error: {}", error))
+                .chain(config.problems().into_iter().map(|warning| format!("Warning: {}", warning)))
+                .collect();
+            problems_label.set_text(&lines.join("\n"));
+            save_button.set_sensitive(errors.is_empty());
+        });
+
+        stack.add_titled(&main_box, Some("save"), "Save");
     }
 
-    fn create_preview_tab(&self, stack: &Stack) {
-        let main_box = Box::new(Orientation::Vertical, 20);
-        main_box.set_margin_start(40);
-        main_box.set_margin_end(40);
-        main_box.set_margin_top(40);
-        main_box.set_margin_bottom(40);
+    // Which profile is being edited, and saving the current settings as a
+    // new one. Switching reopens the configurator on the other profile.
+    fn create_profile_row(&self) -> Box {
+        let profile_box = Box::new(Orientation::Horizontal, 10);
+        profile_box.append(&Label::new(Some("Profile")));
+
+        // The main config, or the one from --config-path, comes first
+        let first = if self.config_path.starts_with(paths::profiles_dir()) {
+            paths::main_config_path()
+        } else {
+            self.config_path.clone()
+        };
+        let first_label = if first == paths::main_config_path() {
+            "Default".to_string()
+        } else {
+            first.display().to_string()
+        };
+        let names = profiles::list();
+        let labels: Vec<&str> = std::iter::once(first_label.as_str())
+            .chain(names.iter().map(String::as_str))
+            .collect();
+        let profile_paths: Vec<PathBuf> = std::iter::once(first)
+            .chain(names.iter().map(|name| paths::profile_path(name)))
+            .collect();
+
+        let selector = gtk::DropDown::from_strings(&labels);
+        let current = profile_paths.iter().position(|path| *path == self.config_path).unwrap_or(0);
+        selector.set_selected(current as u32);
+        let window_clone = self.window.clone();
+        selector.connect_selected_notify(move |selector| {
+            if let Some(path) = profile_paths.get(selector.selected() as usize) {
+                reopen(&window_clone, path.clone());
+            }
+        });
+        profile_box.append(&selector);
 
-        let title_label = Label::new(None);
-        title_label.set_markup("<span font-size='large'><b>Configuration Preview</b></span>");
-        title_label.set_halign(gtk::Align::Start);
-        main_box.append(&title_label);
+        let save_as_button = Button::with_label("Save as new profile…");
+        let config_clone = self.config.clone();
+        let window_clone = self.window.clone();
+        save_as_button.connect_clicked(move |_| {
+            ConfiguratorWindow::show_save_profile_dialog(&window_clone, config_clone.clone());
+        });
+        profile_box.append(&save_as_button);
+        profile_box
+    }
 
-        // Create a preview area that mimics the main window overview
-        let preview_box = Box::new(Orientation::Horizontal, 60);
-        preview_box.set_halign(gtk::Align::Center);
+    fn show_save_profile_dialog(parent: &ApplicationWindow, config: Rc<RefCell<Config>>) {
+        let dialog = Window::builder()
+            .title("Save as New Profile")
+            .transient_for(parent)
+            .modal(true)
+            .resizable(false)
+            .build();
 
-        // Placeholder for image
-        let image_placeholder = Label::new(Some("Image will\nappear here\nafter selection"));
-        image_placeholder.set_halign(gtk::Align::Center);
-        image_placeholder.set_valign(gtk::Align::Start);
-        preview_box.append(&image_placeholder);
+        let main_box = Box::new(Orientation::Vertical, 15);
+        main_box.set_margin_start(20);
+        main_box.set_margin_end(20);
+        main_box.set_margin_top(20);
+        main_box.set_margin_bottom(20);
 
-        // Info section
-        let info_box = Box::new(Orientation::Vertical, 20);
-        info_box.set_valign(gtk::Align::Start);
+        let hint = Label::new(Some("Open it later with --profile NAME."));
+        hint.set_halign(gtk::Align::Start);
+        main_box.append(&hint);
 
-        let preview_distro = Label::new(Some("Distro name will appear here"));
-        preview_distro.set_halign(gtk::Align::Start);
-        info_box.append(&preview_distro);
+        let name_entry = Entry::new();
+        name_entry.set_placeholder_text(Some("Profile name"));
+        main_box.append(&name_entry);
 
-        let preview_version = Label::new(Some("Version will appear here"));
-        preview_version.set_halign(gtk::Align::Start);
-        info_box.append(&preview_version);
+        let buttons_box = Box::new(Orientation::Horizontal, 10);
+        buttons_box.set_halign(gtk::Align::End);
 
-        let preview_info = Label::new(Some("System information will appear here"));
-        preview_info.set_halign(gtk::Align::Start);
-        info_box.append(&preview_info);
+        let cancel_button = Button::with_label("Cancel");
+        let dialog_clone = dialog.clone();
+        cancel_button.connect_clicked(move |_| dialog_clone.close());
+        buttons_box.append(&cancel_button);
 
-        preview_box.append(&info_box);
-        main_box.append(&preview_box);
+        let save_button = Button::with_label("Save");
+        save_button.set_css_classes(&["suggested-action"]);
+        let dialog_clone = dialog.clone();
+        let parent = parent.clone();
+        save_button.connect_clicked(move |_| {
+            let name = name_entry.text().trim().to_string();
+            let path = paths::profile_path(&name);
+            let config = config.borrow();
+            let problem = profiles::check_name(&name)
+                .err()
+                .map(|e| e.to_string())
+                .or_else(|| path.exists().then(|| format!("There is already a profile called \"{}\"", name)))
+                .or_else(|| STEPS.iter().find_map(|(step, _)| step_problem(step, &config)));
+            if let Some(problem) = problem {
+                utils::show_error_dialog(Some(dialog_clone.upcast_ref()), "Profile", &problem);
+                return;
+            }
 
-        let back_button = Button::with_label("Back to Configuration");
-        let stack_clone = stack.clone();
-        back_button.connect_clicked(move |_| {
-            stack_clone.set_visible_child_name("manual_config");
+            match config.save(&path) {
+                Ok(()) => {
+                    dialog_clone.close();
+                    reopen(&parent, path);
+                }
+                Err(e) => {
+                    eprintln!("Failed to save profile: {:#}", e);
+                    utils::show_error_dialog(Some(dialog_clone.upcast_ref()), "Profile", &format!("{:#}", e));
+                }
+            }
+        });
+        buttons_box.append(&save_button);
+        main_box.append(&buttons_box);
+
+        dialog.set_child(Some(&main_box));
+        dialog.present();
+    }
+
+    // Thumbnails of every logo detection would consider; `on_pick` gets the
+    // chosen one's path
+    fn show_logo_finder(parent: &ApplicationWindow, on_pick: impl Fn(PathBuf) + 'static) {
+        let candidates = crate::logo_candidates();
+        if candidates.is_empty() {
+            utils::show_info_dialog(
+                Some(parent.upcast_ref()),
+                "Logos",
+                "No distribution logos were found on this system. Pick one from the gallery or an image file instead.",
+            );
+            return;
+        }
+
+        let dialog = Window::builder()
+            .title("Logos on This System")
+            .transient_for(parent)
+            .modal(true)
+            .default_width(560)
+            .default_height(400)
+            .build();
+
+        let main_box = Box::new(Orientation::Vertical, 15);
+        main_box.set_margin_start(20);
+        main_box.set_margin_end(20);
+        main_box.set_margin_top(20);
+        main_box.set_margin_bottom(20);
+
+        let flow_box = gtk::FlowBox::new();
+        flow_box.set_selection_mode(gtk::SelectionMode::Single);
+        flow_box.set_homogeneous(true);
+        flow_box.set_row_spacing(10);
+        flow_box.set_column_spacing(10);
+        for (path, _) in &candidates {
+            let entry = Box::new(Orientation::Vertical, 5);
+            let thumbnail = gdk_pixbuf::Pixbuf::from_file_at_scale(path, LOGO_THUMBNAIL_SIZE, LOGO_THUMBNAIL_SIZE, true)
+                .map(|pixbuf| gtk::Image::from_pixbuf(Some(&pixbuf)))
+                .unwrap_or_else(|_| gtk::Image::from_icon_name("image-missing"));
+            thumbnail.set_pixel_size(LOGO_THUMBNAIL_SIZE);
+            entry.append(&thumbnail);
+            let name = Path::new(path).file_name().map(|name| name.to_string_lossy().to_string());
+            let label = Label::new(name.as_deref());
+            label.set_ellipsize(gtk::pango::EllipsizeMode::Middle);
+            label.set_max_width_chars(16);
+            entry.append(&label);
+            entry.set_tooltip_text(Some(path));
+            flow_box.insert(&entry, -1);
+        }
+
+        let scrolled = ScrolledWindow::new();
+        scrolled.set_vexpand(true);
+        scrolled.set_child(Some(&flow_box));
+        main_box.append(&scrolled);
+
+        let cancel_button = Button::with_label("Cancel");
+        cancel_button.set_halign(gtk::Align::End);
+        let dialog_clone = dialog.clone();
+        cancel_button.connect_clicked(move |_| dialog_clone.close());
+        main_box.append(&cancel_button);
+
+        let dialog_clone = dialog.clone();
+        flow_box.connect_child_activated(move |_, child| {
+            if let Some((path, _)) = usize::try_from(child.index()).ok().and_then(|index| candidates.get(index)) {
+                dialog_clone.close();
+                on_pick(PathBuf::from(path));
+            }
         });
 
-        main_box.append(&back_button);
+        dialog.set_child(Some(&main_box));
+        dialog.present();
+    }
 
-        stack.add_titled(&main_box, Some("preview"), "Preview");
+    fn create_preview_page(&self, stack: &Stack) {
+        let main_box = page_box("Review");
+
+        // GTK picks light or dark for the whole display, so the preview is
+        // only painted like one; enough to see a logo against both
+        let background_box = Box::new(Orientation::Horizontal, 10);
+        background_box.append(&Label::new(Some("Background")));
+        let background = gtk::DropDown::from_strings(&["Desktop theme", "Light", "Dark"]);
+        let slot = self.preview.slot.clone();
+        background.connect_selected_notify(move |background| {
+            slot.remove_css_class("atl-preview-light");
+            slot.remove_css_class("atl-preview-dark");
+            match background.selected() {
+                1 => slot.add_css_class("atl-preview-light"),
+                2 => slot.add_css_class("atl-preview-dark"),
+                _ => {}
+            }
+        });
+        background_box.append(&background);
+        main_box.append(&background_box);
+
+        // The same overview the main window shows, without clicks or focus
+        let preview_scrolled = ScrolledWindow::new();
+        preview_scrolled.set_vexpand(true);
+        preview_scrolled.set_child(Some(&self.preview.slot));
+        main_box.append(&preview_scrolled);
+        self.preview.rebuild();
+
+        stack.add_titled(&main_box, Some("review"), "Review");
     }
 
-    fn create_about_tab(&self, stack: &Stack) {
+    fn create_about_button(&self) -> gtk::MenuButton {
         let about_box = Box::new(Orientation::Vertical, 20);
-        about_box.set_halign(gtk::Align::Center);
-        about_box.set_valign(gtk::Align::Center);
+        about_box.set_margin_start(20);
+        about_box.set_margin_end(20);
+        about_box.set_margin_top(20);
+        about_box.set_margin_bottom(20);
 
         let title_label = Label::new(None);
         title_label.set_markup(&format!("<b>About this Linux v{} - Configuration</b>", VERSION));
@@ -435,8 +1121,8 @@ impl ConfiguratorWindow {
 
         let info_label = Label::new(Some(
             "Configuration wizard for About this Linux\n\
-            Choose automatic detection or manual configuration\n\
-            to set up your system information display.",
+            Detect your system, adjust what was found and how it looks,\n\
+            then review and save it.",
         ));
         info_label.set_halign(gtk::Align::Center);
         about_box.append(&info_label);
@@ -449,19 +1135,530 @@ impl ConfiguratorWindow {
             ORIGINAL_REPO, ORIGINAL_AUTHOR, CURRENT_REPO
         ));
         attribution_label.set_halign(gtk::Align::Center);
+        utils::handle_links(&attribution_label, || {});
         about_box.append(&attribution_label);
 
-        stack.add_titled(&about_box, Some("about"), "About");
+        let popover = gtk::Popover::new();
+        popover.set_child(Some(&about_box));
+        let about_button = gtk::MenuButton::new();
+        about_button.set_icon_name("help-about-symbolic");
+        about_button.set_tooltip_text(Some("About"));
+        about_button.set_popover(Some(&popover));
+        about_button
+    }
+
+    fn show_restore_dialog(
+        parent: &ApplicationWindow,
+        config_path: &Path,
+        config: Rc<RefCell<Config>>,
+        entries: Rc<RefCell<HashMap<String, Entry>>>,
+        layout: Rc<HashMap<String, SpinButton>>,
+        auto: Rc<HashMap<String, CheckButton>>,
+    ) {
+        let dialog = Window::builder()
+            .title("Restore Previous Configuration")
+            .transient_for(parent)
+            .modal(true)
+            .default_width(600)
+            .default_height(360)
+            .build();
+
+        let main_box = Box::new(Orientation::Vertical, 15);
+        main_box.set_margin_start(20);
+        main_box.set_margin_end(20);
+        main_box.set_margin_top(20);
+        main_box.set_margin_bottom(20);
+
+        let backups = backup::list_backups(config_path);
+
+        let content_box = Box::new(Orientation::Horizontal, 15);
+        content_box.set_vexpand(true);
+
+        let list_box = ListBox::new();
+        list_box.set_selection_mode(gtk::SelectionMode::Single);
+        for entry in &backups {
+            let label = Label::new(Some(&format_backup_time(entry.timestamp)));
+            label.set_halign(gtk::Align::Start);
+            label.set_margin_start(10);
+            label.set_margin_end(10);
+            label.set_margin_top(6);
+            label.set_margin_bottom(6);
+            list_box.append(&label);
+        }
+
+        let list_scrolled = ScrolledWindow::new();
+        list_scrolled.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+        list_scrolled.set_size_request(200, -1);
+        list_scrolled.set_child(Some(&list_box));
+        content_box.append(&list_scrolled);
+
+        let preview_label = Label::new(Some(if backups.is_empty() {
+            "No backups yet. One is made every time the configuration is saved."
+        } else {
+            "Select a backup to preview it."
+        }));
+        preview_label.set_halign(gtk::Align::Start);
+        preview_label.set_valign(gtk::Align::Start);
+        preview_label.set_selectable(true);
+        preview_label.set_wrap(true);
+        preview_label.add_css_class("monospace");
+
+        let preview_scrolled = ScrolledWindow::new();
+        preview_scrolled.set_hexpand(true);
+        preview_scrolled.set_child(Some(&preview_label));
+        content_box.append(&preview_scrolled);
+
+        main_box.append(&content_box);
+
+        let buttons_box = Box::new(Orientation::Horizontal, 10);
+        buttons_box.set_halign(gtk::Align::End);
+
+        let cancel_button = Button::with_label("Cancel");
+        let dialog_clone = dialog.clone();
+        cancel_button.connect_clicked(move |_| dialog_clone.close());
+        buttons_box.append(&cancel_button);
+
+        let restore_button = Button::with_label("Restore");
+        restore_button.set_css_classes(&["suggested-action"]);
+        restore_button.set_sensitive(false);
+        buttons_box.append(&restore_button);
+
+        main_box.append(&buttons_box);
+
+        let backups = Rc::new(backups);
+
+        let backups_clone = backups.clone();
+        let restore_button_clone = restore_button.clone();
+        list_box.connect_row_selected(move |_, row| {
+            let selected = row.and_then(|row| backups_clone.get(row.index() as usize));
+            restore_button_clone.set_sensitive(selected.is_some());
+
+            if let Some(entry) = selected {
+                match entry.load() {
+                    Ok(config) => {
+                        let mut text = report::format_rows(&report::overview_fields(&config));
+                        text.push_str(&format!("\nLogo: {}", config.distro_image_path));
+                        preview_label.set_text(&text);
+                    }
+                    Err(e) => preview_label.set_text(&format!("Cannot read backup: {}", e)),
+                }
+            }
+        });
+
+        let config_path = config_path.to_path_buf();
+        let dialog_clone = dialog.clone();
+        let parent_clone = parent.clone();
+        restore_button.connect_clicked(move |_| {
+            let selected = list_box
+                .selected_row()
+                .and_then(|row| backups.get(row.index() as usize));
+            let entry = match selected {
+                Some(entry) => entry,
+                None => return,
+            };
+
+            let keep = config.borrow().backup_count;
+            match backup::restore_backup(entry, &config_path, keep) {
+                Ok(restored) => {
+                    fill_entries(&entries.borrow(), &restored);
+                    fill_layout(&layout, &restored);
+                    fill_auto(&auto, &restored);
+                    *config.borrow_mut() = restored;
+                    dialog_clone.close();
+                }
+                Err(e) => {
+                    eprintln!("Failed to restore config: {}", e);
+                    utils::show_error_dialog(
+                        Some(parent_clone.upcast_ref()),
+                        "Restore Failed",
+                        &format!("Failed to restore configuration: {}", e),
+                    );
+                }
+            }
+        });
+
+        dialog.set_child(Some(&main_box));
+        dialog.present();
+    }
+
+    fn show_export_dialog(parent: &ApplicationWindow, config: Rc<RefCell<Config>>) {
+        let file_chooser = FileChooserNative::new(
+            Some("Export Configuration"),
+            Some(parent),
+            gtk::FileChooserAction::Save,
+            Some("Export"),
+            Some("Cancel"),
+        );
+        file_chooser.set_current_name("about-this-linux.tar.gz");
+        add_config_filters(&file_chooser);
+
+        let parent_clone = parent.clone();
+        file_chooser.connect_response(move |dialog, response| {
+            if response == ResponseType::Accept {
+                if let Some(path) = dialog.file().and_then(|file| file.path()) {
+                    let result = if is_json(&path) {
+                        export_json(&config.borrow(), &path)
+                    } else {
+                        serde_json::to_value(&*config.borrow())
+                            .map_err(anyhow::Error::from)
+                            .and_then(|config_json| bundle::export_bundle(config_json, &path))
+                    };
+                    if let Err(e) = result {
+                        eprintln!("Failed to export configuration: {}", e);
+                        utils::show_error_dialog(
+                            Some(parent_clone.upcast_ref()),
+                            "Export Failed",
+                            &format!("Failed to export configuration: {}", e),
+                        );
+                    }
+                }
+            }
+        });
+
+        utils::show_file_chooser(&file_chooser);
     }
 
-    fn get_config_dir() -> PathBuf {
-        let mut config_dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/tmp"));
-        config_dir.push(".local/share/about-this-linux");
-        std::fs::create_dir_all(&config_dir).unwrap_or_default();
-        config_dir
+    fn show_import_dialog(
+        parent: &ApplicationWindow,
+        config_path: &Path,
+        config: Rc<RefCell<Config>>,
+        entries: Rc<RefCell<HashMap<String, Entry>>>,
+        layout: Rc<HashMap<String, SpinButton>>,
+        auto: Rc<HashMap<String, CheckButton>>,
+    ) {
+        let file_chooser = FileChooserNative::new(
+            Some("Import Configuration"),
+            Some(parent),
+            gtk::FileChooserAction::Open,
+            Some("Import"),
+            Some("Cancel"),
+        );
+        add_config_filters(&file_chooser);
+
+        let config_path = config_path.to_path_buf();
+        let parent_clone = parent.clone();
+        file_chooser.connect_response(move |dialog, response| {
+            if response != ResponseType::Accept {
+                return;
+            }
+            let Some(path) = dialog.file().and_then(|file| file.path()) else {
+                return;
+            };
+
+            // A bundle is saved straight away since its logo comes with it; a
+            // .json config waits for Save Configuration
+            let keep = config.borrow().backup_count;
+            let config = config.clone();
+            let entries = entries.clone();
+            let layout = layout.clone();
+            let auto = auto.clone();
+            let parent = parent_clone.clone();
+            let config_path = config_path.clone();
+            let import_path = path.clone();
+            let import = move |with_commands: bool| {
+                let result = if is_json(&import_path) {
+                    Config::load(&import_path)
+                } else {
+                    bundle::import_bundle(&import_path, &config_path, keep, with_commands)
+                };
+                match result {
+                    Ok(imported) => {
+                        fill_entries(&entries.borrow(), &imported);
+                        fill_layout(&layout, &imported);
+                        fill_auto(&auto, &imported);
+                        *config.borrow_mut() = imported;
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to import configuration: {}", e);
+                        utils::show_error_dialog(
+                            Some(parent.upcast_ref()),
+                            "Import Failed",
+                            &format!("Failed to import configuration: {}", e),
+                        );
+                    }
+                }
+            };
+
+            // Commands from a shared bundle would run at the next launch, so
+            // they are shown and only imported if the user agrees
+            let commands = if is_json(&path) {
+                Vec::new()
+            } else {
+                bundle::bundle_commands(&path).unwrap_or_default()
+            };
+            if commands.is_empty() {
+                import(false);
+            } else {
+                ask_about_commands(&parent_clone, &commands, import);
+            }
+        });
+
+        utils::show_file_chooser(&file_chooser);
     }
 
     pub fn present(&self) {
         self.window.present();
     }
 }
+
+// Ask whether the commands of an imported bundle should come with it;
+// `import` gets the answer, and isn't called at all on Cancel
+fn ask_about_commands(parent: &ApplicationWindow, commands: &[String], import: impl Fn(bool) + 'static) {
+    let dialog = gtk::MessageDialog::new(
+        Some(parent),
+        gtk::DialogFlags::MODAL,
+        gtk::MessageType::Warning,
+        gtk::ButtonsType::None,
+        "Import the commands in this bundle?",
+    );
+    dialog.set_secondary_text(Some(&format!(
+        "This bundle runs these commands on your system:\n\n{}\n\n\
+        Only import them if you trust whoever made the bundle.",
+        commands.join("\n")
+    )));
+    dialog.add_button("Cancel", ResponseType::Cancel);
+    dialog.add_button("Import Without Commands", ResponseType::Reject);
+    let button = dialog.add_button("Import With Commands", ResponseType::Accept);
+    button.add_css_class("destructive-action");
+    dialog.set_default_response(ResponseType::Reject);
+
+    dialog.connect_response(move |dialog, response| {
+        dialog.close();
+        match response {
+            ResponseType::Accept => import(true),
+            ResponseType::Reject => import(false),
+            _ => {}
+        }
+    });
+    dialog.present();
+}
+
+// Edit another config in a fresh configurator instead of this one
+fn reopen(window: &ApplicationWindow, config_path: PathBuf) {
+    let Some(app) = window.application() else {
+        return;
+    };
+    ConfiguratorWindow::new(&app, config_path).present();
+    window.close();
+}
+
+// A wizard page with its title
+fn page_box(title: &str) -> Box {
+    let main_box = Box::new(Orientation::Vertical, 15);
+    main_box.set_margin_start(40);
+    main_box.set_margin_end(40);
+    main_box.set_margin_top(40);
+    main_box.set_margin_bottom(20);
+
+    let title_label = Label::new(None);
+    title_label.set_markup(&format!("<span font-size='large'><b>{}</b></span>", title));
+    title_label.set_halign(gtk::Align::Start);
+    main_box.append(&title_label);
+    main_box
+}
+
+fn scrolled_page(main_box: &Box) -> ScrolledWindow {
+    let scrolled = ScrolledWindow::new();
+    scrolled.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+    scrolled.set_child(Some(main_box));
+    scrolled
+}
+
+fn step_index(stack: &Stack) -> usize {
+    let name = stack.visible_child_name();
+    STEPS
+        .iter()
+        .position(|(step, _)| Some(*step) == name.as_deref())
+        .unwrap_or(0)
+}
+
+// "Detect › Customize › …" with the current step in bold and the ones
+// still ahead dimmed
+fn step_markup(current: usize) -> String {
+    STEPS
+        .iter()
+        .enumerate()
+        .map(|(i, (_, title))| match i.cmp(&current) {
+            std::cmp::Ordering::Less => title.to_string(),
+            std::cmp::Ordering::Equal => format!("<b>{}</b>", title),
+            std::cmp::Ordering::Greater => format!("<span alpha='50%'>{}</span>", title),
+        })
+        .collect::<Vec<_>>()
+        .join(" › ")
+}
+
+// What keeps a step from being finished, if anything
+fn step_problem(step: &str, config: &Config) -> Option<String> {
+    match step {
+        "customize" => ENTRY_FIELDS
+            .iter()
+            .find_map(|(label, field)| field_problem(config, label, field)),
+        "appearance" => logo_problem(config),
+        _ => None,
+    }
+}
+
+// Fields left on Auto are detected at launch; the others are shown as typed
+fn field_problem(config: &Config, label: &str, field: &str) -> Option<String> {
+    (config.is_locked(field) && field_text(config, field).trim().is_empty())
+        .then(|| format!("{} is empty: fill it in or tick Auto", label))
+}
+
+fn logo_problem(config: &Config) -> Option<String> {
+    let logo = &config.distro_image_path;
+    let loads = logo == config::BUNDLED_LOGO || gdk_pixbuf::Pixbuf::file_info(logo).is_some();
+    (!loads).then(|| format!("The logo {} can't be opened: pick another one", logo))
+}
+
+// Put a warning icon in `entry` while `problem` is set
+fn mark_entry(entry: &Entry, problem: Option<&str>) {
+    let icon = problem.map(|_| "dialog-warning-symbolic");
+    entry.set_icon_from_icon_name(gtk::EntryIconPosition::Secondary, icon);
+    entry.set_icon_tooltip_text(gtk::EntryIconPosition::Secondary, problem);
+}
+
+// Bundles and plain configs, with everything importable listed first
+fn add_config_filters(file_chooser: &FileChooserNative) {
+    let all = gtk::FileFilter::new();
+    all.set_name(Some("Bundles and configurations"));
+    let bundles = gtk::FileFilter::new();
+    bundles.set_name(Some("Configuration bundles (.tar.gz)"));
+    for pattern in ["*.tar.gz", "*.tgz"] {
+        all.add_pattern(pattern);
+        bundles.add_pattern(pattern);
+    }
+    let configs = gtk::FileFilter::new();
+    configs.set_name(Some("Configurations (.json)"));
+    all.add_pattern("*.json");
+    configs.add_pattern("*.json");
+    file_chooser.add_filter(&all);
+    file_chooser.add_filter(&bundles);
+    file_chooser.add_filter(&configs);
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+}
+
+// Just the config, without touching the config directory's backups
+fn export_json(config: &Config, path: &Path) -> Result<()> {
+    let content = serde_json::to_string_pretty(config).context("Failed to serialize config")?;
+    std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+// Store a Customize entry's text in the config
+fn set_field(config: &mut Config, field_name: &str, value: &str) {
+    match field_name {
+        "hostname" => config.hostname = value.to_string(),
+        "cpu" => config.cpu = value.to_string(),
+        "memory" => config.memory = value.to_string(),
+        "startup_disk" => config.startup_disk = value.to_string(),
+        "graphics" => config.graphics = value.to_string(),
+        "serial_num" => config.serial_num = value.to_string(),
+        "font_family" => {
+            let family = value.trim();
+            config.font_family = if family.is_empty() { None } else { Some(family.to_string()) };
+        }
+        _ => {}
+    }
+}
+
+// A Customize entry's text for a config
+fn field_text(config: &Config, field_name: &str) -> String {
+    match field_name {
+        "hostname" => config.hostname.clone(),
+        "cpu" => config.cpu.clone(),
+        "memory" => config.memory.clone(),
+        "startup_disk" => config.startup_disk.clone(),
+        "graphics" => config.graphics.clone(),
+        "serial_num" => config.serial_num.clone(),
+        "font_family" => config.font_family.clone().unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+// Show a loaded config in the Customize entries
+fn fill_entries(entries: &HashMap<String, Entry>, config: &Config) {
+    for (field_name, entry) in entries {
+        entry.set_text(&field_text(config, field_name));
+    }
+}
+
+// The entry with a button that puts back its value from `reference`,
+// usable only while the text differs from it
+fn with_reset_button(entry: &Entry, field_name: &'static str, reference: Rc<RefCell<Config>>) -> Box {
+    let row = Box::new(Orientation::Horizontal, 5);
+    entry.set_hexpand(true);
+    row.append(entry);
+
+    let reset_button = Button::from_icon_name("edit-undo-symbolic");
+    reset_button.add_css_class("flat");
+    reset_button.set_tooltip_text(Some("Reset to the detected or saved value"));
+    reset_button.set_sensitive(false);
+
+    let button_clone = reset_button.clone();
+    let reference_clone = reference.clone();
+    entry.connect_changed(move |entry| {
+        button_clone.set_sensitive(entry.text() != field_text(&reference_clone.borrow(), field_name));
+    });
+    let entry_clone = entry.clone();
+    reset_button.connect_clicked(move |_| {
+        let value = field_text(&reference.borrow(), field_name);
+        entry_clone.set_text(&value);
+    });
+    row.append(&reset_button);
+    row
+}
+
+fn set_locked(config: &mut Config, field_name: &str, locked: bool) {
+    config.locked_fields.retain(|field| field != field_name);
+    if locked {
+        config.locked_fields.push(field_name.to_string());
+    }
+}
+
+// Show a loaded config's locked fields in the Auto check buttons
+fn fill_auto(auto: &HashMap<String, CheckButton>, config: &Config) {
+    for (field_name, check) in auto {
+        check.set_active(!config.is_locked(field_name));
+    }
+}
+
+// Show a loaded config's layout in the spin buttons
+fn fill_layout(layout: &HashMap<String, SpinButton>, config: &Config) {
+    for (field_name, spin) in layout {
+        spin.set_value(layout_value(config, field_name) as f64);
+    }
+}
+
+fn layout_value(config: &Config, field_name: &str) -> i32 {
+    match field_name {
+        "logo_width" => config.distro_image_size[0],
+        "logo_height" => config.distro_image_size[1],
+        "margin_start" => config.overview_margins[0],
+        "margin_end" => config.overview_margins[1],
+        "margin_top" => config.overview_margins[2],
+        "margin_bottom" => config.overview_margins[3],
+        "section_space" => config.section_space,
+        "logo_space" => config.logo_space,
+        _ => 0,
+    }
+}
+
+fn set_layout_value(config: &mut Config, field_name: &str, value: i32) {
+    match field_name {
+        "logo_width" => config.distro_image_size[0] = value,
+        "logo_height" => config.distro_image_size[1] = value,
+        "margin_start" => config.overview_margins[0] = value,
+        "margin_end" => config.overview_margins[1] = value,
+        "margin_top" => config.overview_margins[2] = value,
+        "margin_bottom" => config.overview_margins[3] = value,
+        "section_space" => config.section_space = value,
+        "logo_space" => config.logo_space = value,
+        _ => {}
+    }
+}
+
+fn format_backup_time(timestamp: u64) -> String {
+    units::format_timestamp(timestamp as i64)
+}
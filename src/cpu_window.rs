@@ -0,0 +1,170 @@
+use gtk::prelude::*;
+use gtk::{Box, Grid, HeaderBar, Label, Orientation, ScrolledWindow, Window};
+
+use crate::status::{self, Status};
+use crate::system_info::{self, CpuDetails};
+
+/// Microcode, caches, instruction sets and vulnerability mitigations of the
+/// processor, opened by clicking the Processor row of the overview
+pub struct CpuWindow {
+    window: Window,
+}
+
+impl CpuWindow {
+    pub fn new(parent: &impl IsA<Window>, cpu: &str) -> Self {
+        let window = Window::builder()
+            .title("Processor Details")
+            .transient_for(parent)
+            .default_width(520)
+            .default_height(560)
+            .build();
+
+        let header_bar = HeaderBar::new();
+        header_bar.set_show_title_buttons(true);
+        window.set_titlebar(Some(&header_bar));
+
+        let main_box = Box::new(Orientation::Vertical, 10);
+        main_box.set_margin_start(20);
+        main_box.set_margin_end(20);
+        main_box.set_margin_top(20);
+        main_box.set_margin_bottom(20);
+
+        let title = Label::new(Some(cpu));
+        title.add_css_class("atl-field-value");
+        title.set_halign(gtk::Align::Start);
+        title.set_wrap(true);
+        title.set_xalign(0.0);
+        title.set_selectable(true);
+        main_box.append(&title);
+
+        append_details(&main_box, &system_info::cpu_details());
+
+        let scrolled = ScrolledWindow::new();
+        scrolled.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+        scrolled.set_vexpand(true);
+        scrolled.set_child(Some(&main_box));
+        window.set_child(Some(&scrolled));
+
+        CpuWindow { window }
+    }
+
+    pub fn present(&self) {
+        self.window.present();
+    }
+}
+
+fn append_details(main_box: &Box, details: &CpuDetails) {
+    append_section_title(main_box, "Microcode");
+    match &details.microcode {
+        Some(revision) => {
+            let revision = value_label(&format!("Revision {}", revision));
+            revision.set_margin_start(20);
+            main_box.append(&revision);
+        }
+        None => append_note(main_box, "The microcode revision is not available"),
+    }
+    // Without the package only the revision in the firmware is loaded, which
+    // may predate the latest Spectre-class fixes
+    if let Some(package) = details.missing_microcode_package {
+        let text = format!("No microcode update package found; install {}", package);
+        let warning = status::status_widget(Status::Warning, &text);
+        warning.set_margin_start(20);
+        main_box.append(&warning);
+    }
+
+    append_section_title(main_box, "Caches");
+    if details.caches.is_empty() {
+        append_note(main_box, "Cache sizes are not available");
+    } else {
+        let grid = section_grid();
+        for (row, (name, size)) in details.caches.iter().enumerate() {
+            grid.attach(&name_label(name), 0, row as i32, 1, 1);
+            grid.attach(&value_label(size), 1, row as i32, 1, 1);
+        }
+        main_box.append(&grid);
+    }
+
+    append_section_title(main_box, "Instruction Sets");
+    if details.instruction_sets.is_empty() {
+        append_note(main_box, "No notable instruction set extensions found");
+    } else {
+        let sets = value_label(&details.instruction_sets.join(", "));
+        sets.set_margin_start(20);
+        sets.set_wrap(true);
+        sets.set_xalign(0.0);
+        main_box.append(&sets);
+    }
+
+    append_section_title(main_box, "Vulnerabilities");
+    if details.vulnerabilities.is_empty() {
+        append_note(main_box, "The kernel does not report vulnerability status");
+        return;
+    }
+
+    let exposed = details
+        .vulnerabilities
+        .iter()
+        .filter(|(_, state)| Status::from_vulnerability(state) != Status::Ok)
+        .count();
+    let summary = if exposed == 0 {
+        status::status_widget(Status::Ok, "All known vulnerabilities are mitigated or do not apply")
+    } else {
+        let text = format!("{} of {} need attention", exposed, details.vulnerabilities.len());
+        status::status_widget(Status::Warning, &text)
+    };
+    summary.set_margin_start(20);
+    main_box.append(&summary);
+
+    let grid = section_grid();
+    for (row, (name, state)) in details.vulnerabilities.iter().enumerate() {
+        grid.attach(&name_label(name), 0, row as i32, 1, 1);
+        let state_widget = status::status_widget(Status::from_vulnerability(state), state);
+        if let Some(label) = state_widget.last_child().and_downcast::<Label>() {
+            label.set_wrap(true);
+            label.set_xalign(0.0);
+            label.set_selectable(true);
+        }
+        grid.attach(&state_widget, 1, row as i32, 1, 1);
+    }
+    main_box.append(&grid);
+}
+
+fn append_section_title(main_box: &Box, title: &str) {
+    let label = Label::new(None);
+    label.set_markup(&format!("<b>{}</b>", title));
+    label.set_halign(gtk::Align::Start);
+    label.set_margin_top(10);
+    main_box.append(&label);
+}
+
+fn append_note(main_box: &Box, text: &str) {
+    let label = Label::new(Some(text));
+    label.add_css_class("dim-label");
+    label.set_halign(gtk::Align::Start);
+    label.set_margin_start(20);
+    main_box.append(&label);
+}
+
+fn section_grid() -> Grid {
+    let grid = Grid::new();
+    grid.set_row_spacing(6);
+    grid.set_column_spacing(20);
+    grid.set_margin_start(20);
+    grid
+}
+
+fn name_label(text: &str) -> Label {
+    let label = Label::new(Some(text));
+    label.add_css_class("atl-field-name");
+    label.set_halign(gtk::Align::Start);
+    label.set_valign(gtk::Align::Start);
+    label
+}
+
+fn value_label(text: &str) -> Label {
+    let label = Label::new(Some(text));
+    label.add_css_class("atl-field-value");
+    label.set_halign(gtk::Align::Start);
+    label.set_selectable(true);
+    label
+}
@@ -0,0 +1,117 @@
+use crate::host;
+use crate::updates::PackageManager;
+
+/// An external program a detector shells out to
+struct Tool {
+    program: &'static str,
+    // What is shown as "Unknown" or left out without it
+    degrades: &'static str,
+    // Package per package manager: pacman, apt, dnf, zypper
+    packages: [&'static str; 4],
+}
+
+const TOOLS: [Tool; 10] = [
+    Tool {
+        program: "fastfetch",
+        degrades: "Hostname, processor and graphics fall back to slower sources",
+        packages: ["fastfetch", "fastfetch", "fastfetch", "fastfetch"],
+    },
+    Tool {
+        program: "lsblk",
+        degrades: "Startup disk and the disks on the Storage tab",
+        packages: ["util-linux", "util-linux", "util-linux", "util-linux"],
+    },
+    Tool {
+        program: "df",
+        degrades: "Free space on the Storage tab",
+        packages: ["coreutils", "coreutils", "coreutils", "coreutils"],
+    },
+    Tool {
+        program: "smartctl",
+        degrades: "Temperature and health of SATA disks",
+        packages: ["smartmontools", "smartmontools", "smartmontools", "smartmontools"],
+    },
+    Tool {
+        program: "nvme",
+        degrades: "Temperature of NVMe disks",
+        packages: ["nvme-cli", "nvme-cli", "nvme-cli", "nvme-cli"],
+    },
+    Tool {
+        program: "xrandr",
+        degrades: "Display details on X11",
+        packages: ["xorg-xrandr", "x11-xserver-utils", "xrandr", "xrandr"],
+    },
+    Tool {
+        program: "wlr-randr",
+        degrades: "Display details on wlroots compositors (sway, Hyprland, river)",
+        packages: ["wlr-randr", "wlr-randr", "wlr-randr", "wlr-randr"],
+    },
+    Tool {
+        program: "dmidecode",
+        degrades: "Serial number and memory details (needs root)",
+        packages: ["dmidecode", "dmidecode", "dmidecode", "dmidecode"],
+    },
+    Tool {
+        program: "lshw",
+        degrades: "Memory details when dmidecode can't read them",
+        packages: ["lshw", "lshw", "lshw", "lshw"],
+    },
+    Tool {
+        program: "hw-probe",
+        degrades: "Sharing a hardware probe with linux-hardware.org",
+        packages: ["hw-probe", "hw-probe", "hw-probe", "hw-probe"],
+    },
+];
+
+pub fn all_installed() -> bool {
+    TOOLS.iter().all(|tool| host::find_program(tool.program).is_some())
+}
+
+/// Print which of the optional tools are installed, what is missing without
+/// the others and how to install them. False if any is missing.
+pub fn print_report() -> bool {
+    let manager = PackageManager::detect();
+    let width = TOOLS.iter().map(|tool| tool.program.len()).max().unwrap_or(0);
+    let mut missing = Vec::new();
+
+    for tool in &TOOLS {
+        match host::find_program(tool.program) {
+            Some(path) => println!("{:<width$}  found    {}", tool.program, path.display(), width = width),
+            None => {
+                println!("{:<width$}  missing  {}", tool.program, tool.degrades, width = width);
+                missing.push(tool);
+            }
+        }
+    }
+
+    if missing.is_empty() {
+        println!("\nAll optional tools are installed.");
+        return true;
+    }
+
+    println!();
+    match manager {
+        Some(manager) => {
+            let mut packages: Vec<&str> = missing.iter().map(|tool| tool.package(manager)).collect();
+            packages.dedup();
+            println!("Install the missing tools with:\n  {}", manager.install_command(&packages));
+        }
+        None => println!("Install the missing tools with your package manager."),
+    }
+    println!(
+        "With \"backend\": \"native\" in the config only xrandr or wlr-randr are used, \
+        but disk temperatures and health are not shown."
+    );
+    false
+}
+
+impl Tool {
+    fn package(&self, manager: PackageManager) -> &'static str {
+        match manager {
+            PackageManager::Pacman => self.packages[0],
+            PackageManager::Apt => self.packages[1],
+            PackageManager::Dnf => self.packages[2],
+            PackageManager::Zypper => self.packages[3],
+        }
+    }
+}
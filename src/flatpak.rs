@@ -0,0 +1,139 @@
+use anyhow::{bail, Result};
+use serde::Serialize;
+
+use crate::{host, system_info, units};
+
+// Installed Flatpak apps and runtimes across the user and system
+// installations, grouped by the remote they came from
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FlatpakInventory {
+    pub apps: usize,
+    pub runtimes: usize,
+    // Installed size in bytes, as `flatpak list` reports it
+    pub size: u64,
+    pub remotes: Vec<RemoteUsage>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RemoteUsage {
+    pub name: String,
+    pub apps: usize,
+    pub runtimes: usize,
+    pub size: u64,
+}
+
+impl RemoteUsage {
+    /// "34 apps, 12 runtimes, 5.2 GB"
+    pub fn summary(&self) -> String {
+        if self.apps == 0 && self.runtimes == 0 {
+            return "Nothing installed".to_string();
+        }
+        format!(
+            "{}, {}, {}",
+            count(self.apps, "app"),
+            count(self.runtimes, "runtime"),
+            units::format_bytes(self.size)
+        )
+    }
+}
+
+impl FlatpakInventory {
+    pub fn rows(&self) -> Vec<(String, String)> {
+        let mut rows = vec![
+            ("Flatpak Apps".to_string(), self.apps.to_string()),
+            ("Flatpak Runtimes".to_string(), self.runtimes.to_string()),
+            ("Flatpak Size".to_string(), units::format_bytes(self.size)),
+        ];
+        for remote in &self.remotes {
+            rows.push((format!("Flatpak Remote {}", remote.name), remote.summary()));
+        }
+        rows
+    }
+}
+
+fn count(n: usize, noun: &str) -> String {
+    if n == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", n, noun)
+    }
+}
+
+/// Whether the flatpak tool is installed on the host
+pub fn available() -> bool {
+    !system_info::detection_disabled() && host::find_program("flatpak").is_some()
+}
+
+pub fn detect() -> Result<FlatpakInventory> {
+    if system_info::detection_disabled() {
+        bail!("detection is disabled (--no-detect)");
+    }
+
+    let mut inventory = FlatpakInventory::default();
+    // Remotes with nothing installed from them are listed too
+    if let Ok(remotes) = system_info::run_tool("flatpak", &["remotes", "--columns=name"]) {
+        for name in remotes.lines().map(str::trim).filter(|name| !name.is_empty()) {
+            remote_entry(&mut inventory.remotes, name);
+        }
+    }
+
+    let installed = system_info::run_tool("flatpak", &["list", "--columns=ref,origin,size"])?;
+    for line in installed.lines() {
+        let mut fields = line.split('\t').map(str::trim);
+        let (Some(reference), Some(origin), Some(size)) = (fields.next(), fields.next(), fields.next()) else {
+            continue;
+        };
+        // Refs are "app/org.gnome.Maps/x86_64/stable" or "runtime/..."
+        let is_app = match reference.split('/').next() {
+            Some("app") => true,
+            Some("runtime") => false,
+            _ => continue,
+        };
+        let size = parse_size(size).unwrap_or(0);
+        let remote = remote_entry(&mut inventory.remotes, origin);
+        remote.size += size;
+        inventory.size += size;
+        if is_app {
+            remote.apps += 1;
+            inventory.apps += 1;
+        } else {
+            remote.runtimes += 1;
+            inventory.runtimes += 1;
+        }
+    }
+    Ok(inventory)
+}
+
+fn remote_entry<'a>(remotes: &'a mut Vec<RemoteUsage>, name: &str) -> &'a mut RemoteUsage {
+    let index = match remotes.iter().position(|remote| remote.name == name) {
+        Some(index) => index,
+        None => {
+            remotes.push(RemoteUsage {
+                name: name.to_string(),
+                ..Default::default()
+            });
+            remotes.len() - 1
+        }
+    };
+    &mut remotes[index]
+}
+
+// flatpak prints sizes like g_format_size, e.g. "1.2 GB" or "845.3 kB",
+// with a non-breaking space before the unit
+fn parse_size(text: &str) -> Option<u64> {
+    let mut parts = text.split_whitespace();
+    let value: f64 = parts.next()?.replace(',', ".").parse().ok()?;
+    let multiplier = match parts.next().unwrap_or("bytes") {
+        "bytes" | "byte" | "B" => 1.0,
+        "kB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        "TB" => 1e12,
+        "KiB" => 1024.0,
+        "MiB" => 1024.0 * 1024.0,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        "TiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some((value * multiplier) as u64)
+}
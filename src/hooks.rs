@@ -0,0 +1,66 @@
+use std::path::Path;
+use std::process::Command;
+
+use crate::config::Config;
+use crate::report;
+
+#[derive(Debug, Clone, Copy)]
+pub enum HookEvent {
+    Open,
+    Refresh,
+    Export,
+}
+
+impl HookEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            HookEvent::Open => "open",
+            HookEvent::Refresh => "refresh",
+            HookEvent::Export => "export",
+        }
+    }
+}
+
+/// Run the hook configured for `event` in the background.
+///
+/// The command is passed to `sh -c` with the following environment:
+/// `ATL_EVENT`, `ATL_SUMMARY`, `ATL_REPORT_PATH` and, for exports,
+/// `ATL_EXPORT_PATH`.
+pub fn run_hook(config: &Config, event: HookEvent, export_path: Option<&Path>) {
+    let command = match event {
+        HookEvent::Open => &config.hooks.on_open,
+        HookEvent::Refresh => &config.hooks.on_refresh,
+        HookEvent::Export => &config.hooks.on_export,
+    };
+
+    let command = match command {
+        Some(command) if !command.trim().is_empty() => command.clone(),
+        _ => return,
+    };
+
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c")
+        .arg(&command)
+        .env("ATL_EVENT", event.name())
+        .env("ATL_SUMMARY", report::summary_line(config));
+
+    match report::write_report(&report::overview_report(config)) {
+        Ok(path) => {
+            cmd.env("ATL_REPORT_PATH", path);
+        }
+        Err(e) => eprintln!("Failed to write report for {} hook: {}", event.name(), e),
+    }
+
+    if let Some(path) = export_path {
+        cmd.env("ATL_EXPORT_PATH", path);
+    }
+
+    // Wait on a separate thread so the UI never blocks and the child is reaped
+    std::thread::spawn(move || match cmd.status() {
+        Ok(status) if !status.success() => {
+            eprintln!("Hook for {} event exited with {}", event.name(), status);
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("Failed to run hook for {} event: {}", event.name(), e),
+    });
+}
@@ -0,0 +1,57 @@
+use gtk::glib;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+// Inside a Flatpak sandbox the host's tools and files are not where the
+// detectors expect them: commands go through `flatpak-spawn --host` and
+// files are read from the host's filesystem under /run/host.
+const HOST_ROOT: &str = "/run/host";
+const FLATPAK_INFO: &str = "/.flatpak-info";
+
+pub fn in_flatpak() -> bool {
+    static IN_FLATPAK: OnceLock<bool> = OnceLock::new();
+    *IN_FLATPAK.get_or_init(|| Path::new(FLATPAK_INFO).exists())
+}
+
+/// A `Command` that runs `program` on the host, even from inside Flatpak.
+/// Environment variables set on it don't reach the host program.
+pub fn command(program: &str) -> Command {
+    if !in_flatpak() {
+        return Command::new(program);
+    }
+    let mut command = Command::new("flatpak-spawn");
+    command.arg("--host").arg(program);
+    command
+}
+
+/// Full path of `program` on the host, if it is installed
+pub fn find_program(program: &str) -> Option<PathBuf> {
+    if !in_flatpak() {
+        return glib::find_program_in_path(program);
+    }
+    let output = command("sh")
+        .args(["-c", "command -v \"$1\"", "sh", program])
+        .output()
+        .ok()?;
+    let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !path.is_empty()).then(|| PathBuf::from(path))
+}
+
+/// Where a host file such as /etc/os-release can be read. Flatpak always
+/// exposes os-release; the rest of /etc and /usr only with the host-os and
+/// host-etc filesystem permissions, so the sandbox's own file is the fallback.
+pub fn path(path: &str) -> PathBuf {
+    if !in_flatpak() {
+        return PathBuf::from(path);
+    }
+    if path == "/etc/os-release" || path == "/usr/lib/os-release" {
+        return Path::new(HOST_ROOT).join("os-release");
+    }
+    let on_host = Path::new(HOST_ROOT).join(path.trim_start_matches('/'));
+    if on_host.exists() {
+        on_host
+    } else {
+        PathBuf::from(path)
+    }
+}
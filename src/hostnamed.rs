@@ -0,0 +1,134 @@
+use anyhow::{Context, Result};
+use gtk::prelude::*;
+use gtk::{gio, glib};
+use std::sync::OnceLock;
+
+use crate::journal;
+
+// systemd-hostnamed knows the machine's identity from DMI, device tree and
+// /etc/machine-info, without parsing any tool's output
+const HOSTNAME1_NAME: &str = "org.freedesktop.hostname1";
+const HOSTNAME1_PATH: &str = "/org/freedesktop/hostname1";
+const CALL_TIMEOUT_MS: i32 = 2000;
+
+#[derive(Debug, Clone, Default)]
+pub struct HostIdentity {
+    pub pretty_hostname: Option<String>,
+    // laptop, desktop, vm, container, server, tablet, convertible, ...
+    pub chassis: Option<String>,
+    pub icon_name: Option<String>,
+    pub vendor: Option<String>,
+    pub model: Option<String>,
+    pub machine_id: Option<String>,
+}
+
+impl HostIdentity {
+    /// "Lenovo ThinkPad X1 Carbon Gen 9", without repeating the vendor when
+    /// the model already starts with it
+    pub fn device_name(&self) -> Option<String> {
+        let model = self.model.as_deref()?;
+        match self.vendor.as_deref() {
+            Some(vendor) if !model.to_lowercase().starts_with(&vendor.to_lowercase()) => {
+                Some(format!("{} {}", vendor, model))
+            }
+            _ => Some(model.to_string()),
+        }
+    }
+}
+
+/// What hostnamed reports, asked once per session; None without systemd
+pub fn identity() -> Option<&'static HostIdentity> {
+    static IDENTITY: OnceLock<Option<HostIdentity>> = OnceLock::new();
+    IDENTITY
+        .get_or_init(|| journal::timed("host identity", "hostnamed", query).ok())
+        .as_ref()
+}
+
+/// Themed icon for the kind of machine: a laptop, a desktop, a virtual
+/// machine and so on, falling back to a generic computer
+pub fn chassis_icon() -> gio::ThemedIcon {
+    let identity = identity();
+    let chassis_name = match identity.and_then(|identity| identity.chassis.as_deref()) {
+        Some("laptop") | Some("convertible") => Some("computer-laptop"),
+        Some("vm") | Some("container") => Some("computer-vm"),
+        Some("server") => Some("network-server"),
+        Some("tablet") => Some("computer-tablet"),
+        Some("handset") => Some("phone"),
+        _ => None,
+    };
+
+    let mut names: Vec<&str> = Vec::new();
+    names.extend(identity.and_then(|identity| identity.icon_name.as_deref()));
+    names.extend(chassis_name);
+    names.push("computer");
+    gio::ThemedIcon::from_names(&names)
+}
+
+/// The product serial number from hostnamed (systemd 253 and later), which
+/// can read the DMI serial that is only readable by root
+pub fn hardware_serial() -> Option<&'static str> {
+    static SERIAL: OnceLock<Option<String>> = OnceLock::new();
+    SERIAL
+        .get_or_init(|| journal::timed("hardware serial", "hostnamed", query_serial).ok())
+        .as_deref()
+}
+
+fn query_serial() -> Result<String> {
+    let connection = gio::bus_get_sync(gio::BusType::System, gio::Cancellable::NONE).context("No system bus")?;
+    let reply = connection
+        .call_sync(
+            Some(HOSTNAME1_NAME),
+            HOSTNAME1_PATH,
+            HOSTNAME1_NAME,
+            "GetHardwareSerial",
+            None,
+            None,
+            gio::DBusCallFlags::NONE,
+            CALL_TIMEOUT_MS,
+            gio::Cancellable::NONE,
+        )
+        .context("hostnamed doesn't report the hardware serial")?;
+    let serial = reply.child_value(0).str().map(|serial| serial.trim().to_string()).unwrap_or_default();
+    anyhow::ensure!(!serial.is_empty(), "No hardware serial");
+    Ok(serial)
+}
+
+fn query() -> Result<HostIdentity> {
+    let connection = gio::bus_get_sync(gio::BusType::System, gio::Cancellable::NONE).context("No system bus")?;
+    let reply = connection
+        .call_sync(
+            Some(HOSTNAME1_NAME),
+            HOSTNAME1_PATH,
+            "org.freedesktop.DBus.Properties",
+            "GetAll",
+            Some(&(HOSTNAME1_NAME,).to_variant()),
+            None,
+            gio::DBusCallFlags::NONE,
+            CALL_TIMEOUT_MS,
+            gio::Cancellable::NONE,
+        )
+        .context("hostnamed is not available")?;
+
+    let properties = glib::VariantDict::new(Some(&reply.child_value(0)));
+    let text = |name: &str| {
+        properties
+            .lookup_value(name, None)
+            .and_then(|value| value.str().map(|text| text.trim().to_string()))
+            .filter(|text| !text.is_empty())
+    };
+    // Only systemd 256 and later have MachineID, as 16 raw bytes
+    let machine_id = properties
+        .lookup_value("MachineID", None)
+        .and_then(|value| value.get::<Vec<u8>>())
+        .filter(|bytes| !bytes.is_empty())
+        .map(|bytes| bytes.iter().map(|byte| format!("{:02x}", byte)).collect());
+
+    Ok(HostIdentity {
+        pretty_hostname: text("PrettyHostname"),
+        chassis: text("Chassis"),
+        icon_name: text("IconName"),
+        vendor: text("HardwareVendor"),
+        model: text("HardwareModel"),
+        machine_id,
+    })
+}
@@ -0,0 +1,64 @@
+use anyhow::{Context, Result};
+use gtk::glib;
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::os::unix::io::AsRawFd;
+use std::rc::Rc;
+use std::time::Duration;
+
+pub const SUBSYSTEM_BLOCK: &str = "block";
+pub const SUBSYSTEM_DRM: &str = "drm";
+pub const SUBSYSTEM_USB: &str = "usb";
+
+// Plugging in a single device produces a burst of events; wait for it to
+// settle before re-running detection.
+const SETTLE_DELAY: Duration = Duration::from_millis(500);
+
+/// Watch udev for devices being added or removed and call `on_change` with
+/// the affected subsystems once each burst of events has settled.
+pub fn watch<F: Fn(&BTreeSet<String>) + 'static>(on_change: F) -> Result<()> {
+    let socket = udev::MonitorBuilder::new()
+        .and_then(|builder| builder.match_subsystem(SUBSYSTEM_BLOCK))
+        .and_then(|builder| builder.match_subsystem(SUBSYSTEM_DRM))
+        .and_then(|builder| builder.match_subsystem(SUBSYSTEM_USB))
+        .and_then(|builder| builder.listen())
+        .context("Failed to set up udev monitor")?;
+
+    let pending: Rc<RefCell<BTreeSet<String>>> = Rc::new(RefCell::new(BTreeSet::new()));
+    let on_change = Rc::new(on_change);
+    let fd = socket.as_raw_fd();
+
+    glib::source::unix_fd_add_local(fd, glib::IOCondition::IN, move |_, _| {
+        let first_in_burst = pending.borrow().is_empty();
+
+        for event in socket.iter() {
+            let relevant = match event.event_type() {
+                udev::EventType::Add | udev::EventType::Remove => true,
+                // Monitor hotplug arrives as a change on the DRM card
+                udev::EventType::Change => {
+                    event.subsystem().and_then(|s| s.to_str()) == Some(SUBSYSTEM_DRM)
+                }
+                _ => false,
+            };
+
+            if let Some(subsystem) = event.subsystem().and_then(|s| s.to_str()) {
+                if relevant {
+                    pending.borrow_mut().insert(subsystem.to_string());
+                }
+            }
+        }
+
+        if first_in_burst && !pending.borrow().is_empty() {
+            let pending = pending.clone();
+            let on_change = on_change.clone();
+            glib::timeout_add_local_once(SETTLE_DELAY, move || {
+                let subsystems = std::mem::take(&mut *pending.borrow_mut());
+                on_change(&subsystems);
+            });
+        }
+
+        glib::ControlFlow::Continue
+    });
+
+    Ok(())
+}
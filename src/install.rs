@@ -0,0 +1,174 @@
+use anyhow::{bail, Context, Result};
+use gtk::gdk_pixbuf::Pixbuf;
+use std::path::{Path, PathBuf};
+
+use crate::{host, paths};
+
+const APP_ID: &str = "com.novik.about-this-linux";
+const ICON_NAME: &str = "tux-logo";
+const ICON_SIZES: [i32; 8] = [16, 22, 24, 32, 48, 64, 128, 256];
+
+const DESKTOP_ENTRY: &str = include_str!("../data/com.novik.about-this-linux.desktop");
+const DBUS_SERVICE: &str = include_str!("../data/com.novik.about-this-linux.service");
+const SEARCH_PROVIDER: &str = include_str!("../data/com.novik.about-this-linux.search-provider.ini");
+const KRUNNER_ENTRY: &str = include_str!("../data/com.novik.about-this-linux.krunner.desktop");
+
+// Desktop entry, D-Bus activation, GNOME Shell and KRunner search
+fn integration_paths() -> [PathBuf; 4] {
+    let data = paths::data_home();
+    [
+        data.join("applications").join(format!("{}.desktop", APP_ID)),
+        data.join("dbus-1/services").join(format!("{}.service", APP_ID)),
+        data.join("gnome-shell/search-providers")
+            .join(format!("{}.search-provider.ini", APP_ID)),
+        data.join("krunner/dbusplugins").join(format!("{}.desktop", APP_ID)),
+    ]
+}
+
+/// Every file `install` may write, so `uninstall` removes the same set
+fn installed_files() -> Vec<PathBuf> {
+    let mut files = integration_paths().to_vec();
+    files.push(autostart_path());
+    files.extend(ICON_SIZES.iter().map(|&size| icon_path(size)));
+    files
+}
+
+fn icon_path(size: i32) -> PathBuf {
+    paths::data_home().join(format!("icons/hicolor/{}x{}/apps/{}.png", size, size, ICON_NAME))
+}
+
+fn autostart_path() -> PathBuf {
+    paths::autostart_dir().join(format!("{}.desktop", APP_ID))
+}
+
+/// Install the desktop entry, its D-Bus activation and search integration
+/// and the icon for the current user, pointing at the running binary.
+/// With `autostart` it also starts in the background at login.
+pub fn install(autostart: bool, quiet: bool) -> Result<()> {
+    if host::in_flatpak() {
+        bail!("Flatpak already installs the desktop entry and icon");
+    }
+
+    let binary = std::env::current_exe().context("Failed to find the running binary")?;
+    let exec = exec_path(&binary);
+    let [desktop, service, search, krunner] = integration_paths();
+
+    write_file(&desktop, &with_exec(DESKTOP_ENTRY, &exec))?;
+    write_file(&service, &with_exec(DBUS_SERVICE, &exec))?;
+    write_file(&search, SEARCH_PROVIDER)?;
+    write_file(&krunner, KRUNNER_ENTRY)?;
+    for size in ICON_SIZES {
+        save_icon(size)?;
+    }
+
+    let autostart_file = autostart_path();
+    if autostart {
+        write_file(&autostart_file, &autostart_entry(&exec))?;
+    } else {
+        remove_file(&autostart_file)?;
+    }
+
+    if !quiet {
+        println!("Installed {} for {}", APP_ID, binary.display());
+        println!("  Desktop entry: {}", desktop.display());
+        println!("  Icons: {}", paths::data_home().join("icons/hicolor").display());
+        if autostart {
+            println!("  Autostart: {}", autostart_file.display());
+        }
+    }
+    Ok(())
+}
+
+/// Remove everything `install` added. Settings are kept.
+pub fn uninstall(quiet: bool) -> Result<()> {
+    let mut removed = 0;
+    for path in installed_files() {
+        if remove_file(&path)? {
+            removed += 1;
+        }
+    }
+
+    if !quiet {
+        match removed {
+            0 => println!("Nothing to uninstall"),
+            _ => println!(
+                "Removed {} files; the config in {} is kept",
+                removed,
+                paths::config_dir().display()
+            ),
+        }
+    }
+    Ok(())
+}
+
+// Desktop entries split Exec on spaces, so quote the path if it has any
+fn exec_path(binary: &Path) -> String {
+    let path = binary.to_string_lossy();
+    if path.contains(' ') {
+        format!("\"{}\"", path.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        path.to_string()
+    }
+}
+
+// The shipped files run the binary from $PATH (or /usr/local/bin)
+fn with_exec(template: &str, exec: &str) -> String {
+    template
+        .lines()
+        .map(|line| match line.strip_prefix("Exec=") {
+            // gapplication is found on $PATH and finds the app by its id
+            Some(command) if !command.starts_with("gapplication ") => match command.split_once(' ') {
+                Some((_, args)) => format!("Exec={} {}", exec, args),
+                None => format!("Exec={}", exec),
+            },
+            _ => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n"
+}
+
+fn autostart_entry(exec: &str) -> String {
+    format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name=About this Linux\n\
+         Comment=Tray icon for About this Linux\n\
+         Exec={} --daemon\n\
+         Icon={}\n\
+         Terminal=false\n\
+         NoDisplay=true\n\
+         X-GNOME-Autostart-enabled=true\n",
+        exec, ICON_NAME
+    )
+}
+
+fn save_icon(size: i32) -> Result<()> {
+    let path = icon_path(size);
+    let icon = Pixbuf::from_resource_at_scale(paths::TUX_LOGO_RESOURCE, size, size, true)
+        .context("Failed to load the bundled icon")?;
+    create_parent(&path)?;
+    icon.savev(&path, "png", &[])
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn write_file(path: &Path, content: &str) -> Result<()> {
+    create_parent(path)?;
+    std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn create_parent(path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    Ok(())
+}
+
+// True if there was a file to remove
+fn remove_file(path: &Path) -> Result<bool> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e).with_context(|| format!("Failed to remove {}", path.display())),
+    }
+}
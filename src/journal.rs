@@ -0,0 +1,94 @@
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+// Session-wide record of every detection step, shown in the Detection log
+// window so users can attach it to bug reports.
+
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    Ok,
+    Fallback(String),
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct JournalEntry {
+    // Time since the session started
+    pub at: Duration,
+    pub step: String,
+    pub source: String,
+    pub duration: Duration,
+    pub outcome: Outcome,
+}
+
+impl fmt::Display for JournalEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let outcome = match &self.outcome {
+            Outcome::Ok => "ok".to_string(),
+            Outcome::Fallback(reason) => format!("fallback: {}", reason),
+            Outcome::Failed(reason) => format!("failed: {}", reason),
+        };
+        write!(
+            f,
+            "+{:>8.3}s  [{}] {} ({} ms) {}",
+            self.at.as_secs_f64(),
+            self.source,
+            self.step,
+            self.duration.as_millis(),
+            outcome
+        )
+    }
+}
+
+static SESSION_START: OnceLock<Instant> = OnceLock::new();
+static ENTRIES: Mutex<Vec<JournalEntry>> = Mutex::new(Vec::new());
+
+fn session_start() -> Instant {
+    *SESSION_START.get_or_init(Instant::now)
+}
+
+pub fn record(step: &str, source: &str, duration: Duration, outcome: Outcome) {
+    let entry = JournalEntry {
+        at: session_start().elapsed(),
+        step: step.to_string(),
+        source: source.to_string(),
+        duration,
+        outcome,
+    };
+
+    if let Ok(mut entries) = ENTRIES.lock() {
+        entries.push(entry);
+    }
+}
+
+/// Run `f` and record how long it took and whether it succeeded
+pub fn timed<T, E: fmt::Display>(
+    step: &str,
+    source: &str,
+    f: impl FnOnce() -> Result<T, E>,
+) -> Result<T, E> {
+    // Make sure the session clock starts before the first step does
+    session_start();
+    let started = Instant::now();
+    let result = f();
+    let outcome = match &result {
+        Ok(_) => Outcome::Ok,
+        Err(e) => Outcome::Failed(e.to_string()),
+    };
+    record(step, source, started.elapsed(), outcome);
+    result
+}
+
+/// How many entries have been recorded so far
+pub fn len() -> usize {
+    ENTRIES.lock().map(|entries| entries.len()).unwrap_or_default()
+}
+
+/// Entries recorded after the first `from` ones
+pub fn entries_since(from: usize) -> Vec<JournalEntry> {
+    match ENTRIES.lock() {
+        Ok(entries) => entries.iter().skip(from).cloned().collect(),
+        Err(_) => Vec::new(),
+    }
+}
@@ -0,0 +1,119 @@
+use gtk::prelude::*;
+use gtk::{glib, Application, Box, Button, HeaderBar, Label, ListBox, Orientation, ScrolledWindow, SearchEntry, Window};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::journal::{self, Outcome};
+use crate::status::Status;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub struct JournalWindow {
+    window: Window,
+}
+
+impl JournalWindow {
+    pub fn new(app: &Application) -> Self {
+        let window = Window::builder()
+            .application(app)
+            .title("Detection Log")
+            .default_width(760)
+            .default_height(420)
+            .build();
+
+        let header_bar = HeaderBar::new();
+        header_bar.set_show_title_buttons(true);
+        window.set_titlebar(Some(&header_bar));
+
+        let search_entry = SearchEntry::new();
+        search_entry.set_placeholder_text(Some("Filter by tool, step or outcome"));
+        header_bar.set_title_widget(Some(&search_entry));
+
+        let copy_button = Button::with_label("Copy");
+        copy_button.set_tooltip_text(Some("Copy the whole log for a bug report"));
+        header_bar.pack_end(&copy_button);
+
+        let list_box = ListBox::new();
+        list_box.set_selection_mode(gtk::SelectionMode::None);
+
+        let filter_entry = search_entry.clone();
+        list_box.set_filter_func(move |row| {
+            let filter = filter_entry.text().to_lowercase();
+            if filter.is_empty() {
+                return true;
+            }
+            row.child()
+                .and_downcast::<Box>()
+                .and_then(|row_box| row_box.first_child())
+                .and_downcast::<Label>()
+                .map(|label| label.text().to_lowercase().contains(&filter))
+                .unwrap_or(false)
+        });
+
+        let list_clone = list_box.clone();
+        search_entry.connect_search_changed(move |_| list_clone.invalidate_filter());
+
+        let scrolled = ScrolledWindow::new();
+        scrolled.set_policy(gtk::PolicyType::Automatic, gtk::PolicyType::Automatic);
+        scrolled.set_vexpand(true);
+        scrolled.set_child(Some(&list_box));
+        window.set_child(Some(&scrolled));
+
+        let lines = Rc::new(RefCell::new(Vec::new()));
+        let shown = Rc::new(Cell::new(0));
+        append_new_entries(&list_box, &shown, &lines);
+
+        let lines_clone = lines.clone();
+        copy_button.connect_clicked(move |button| {
+            button.clipboard().set_text(&lines_clone.borrow().join("\n"));
+        });
+
+        // Keep the view live while detection continues in the background
+        let weak_window = window.downgrade();
+        glib::timeout_add_local(POLL_INTERVAL, move || {
+            match weak_window.upgrade() {
+                Some(window) if window.is_visible() => {
+                    append_new_entries(&list_box, &shown, &lines);
+                    glib::ControlFlow::Continue
+                }
+                _ => glib::ControlFlow::Break,
+            }
+        });
+
+        JournalWindow { window }
+    }
+
+    pub fn present(&self) {
+        self.window.present();
+    }
+}
+
+fn append_new_entries(list_box: &ListBox, shown: &Cell<usize>, lines: &RefCell<Vec<String>>) {
+    let entries = journal::entries_since(shown.get());
+    shown.set(shown.get() + entries.len());
+
+    for entry in entries {
+        let text = entry.to_string();
+
+        let row_box = Box::new(Orientation::Horizontal, 8);
+        row_box.set_margin_start(10);
+        row_box.set_margin_end(10);
+        row_box.set_margin_top(2);
+        row_box.set_margin_bottom(2);
+
+        let label = Label::new(Some(&text));
+        label.set_halign(gtk::Align::Start);
+        label.set_selectable(true);
+        label.add_css_class("monospace");
+        match entry.outcome {
+            Outcome::Ok => {}
+            Outcome::Fallback(_) => label.add_css_class(Status::Warning.css_class()),
+            Outcome::Failed(_) => label.add_css_class(Status::Critical.css_class()),
+        }
+        row_box.append(&label);
+
+        list_box.append(&row_box);
+        lines.borrow_mut().push(text);
+    }
+}
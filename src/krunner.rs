@@ -0,0 +1,103 @@
+use gtk::prelude::*;
+use gtk::{gio, glib};
+use std::collections::HashMap;
+
+use crate::search_provider;
+
+// KRunner D-Bus runner, the Plasma counterpart of the GNOME search provider:
+// typing in KRunner or the launcher search offers the matching tabs. Plasma
+// finds it through data/com.novik.about-this-linux.krunner.desktop.
+const OBJECT_PATH: &str = "/com/novik/about-this-linux/KRunner";
+const INTERFACE: &str = "org.kde.krunner1";
+
+// Match types from KRunner's QueryMatch
+const POSSIBLE_MATCH: i32 = 30;
+const EXACT_MATCH: i32 = 100;
+
+const INTERFACE_XML: &str = r#"
+<node>
+  <interface name="org.kde.krunner1">
+    <method name="Actions">
+      <arg name="actions" type="a(sss)" direction="out"/>
+    </method>
+    <method name="Match">
+      <arg name="query" type="s" direction="in"/>
+      <arg name="matches" type="a(sssida{sv})" direction="out"/>
+    </method>
+    <method name="Run">
+      <arg name="matchId" type="s" direction="in"/>
+      <arg name="actionId" type="s" direction="in"/>
+    </method>
+  </interface>
+</node>
+"#;
+
+type Match = (String, String, String, i32, f64, HashMap<String, glib::Variant>);
+
+/// Export the runner on the application's bus name
+pub fn register(app: &gtk::Application) {
+    let connection = match app.dbus_connection() {
+        Some(connection) => connection,
+        None => return,
+    };
+
+    let interface = match gio::DBusNodeInfo::for_xml(INTERFACE_XML)
+        .ok()
+        .and_then(|node| node.lookup_interface(INTERFACE))
+    {
+        Some(interface) => interface,
+        None => return,
+    };
+
+    let result = connection.register_object(
+        OBJECT_PATH,
+        &interface,
+        |_, _, _, _, method, parameters, invocation| match method {
+            "Actions" => {
+                // No extra actions per match, running one opens its tab
+                let actions: Vec<(String, String, String)> = Vec::new();
+                invocation.return_value(Some(&(actions,).to_variant()));
+            }
+            "Match" => {
+                let (query,) = parameters.get::<(String,)>().unwrap_or_default();
+                invocation.return_value(Some(&(matches(&query),).to_variant()));
+            }
+            "Run" => {
+                let tab = parameters.child_value(0).get::<String>().unwrap_or_default();
+                search_provider::show_tab(&tab);
+                invocation.return_value(None);
+            }
+            _ => invocation.return_dbus_error(
+                "org.freedesktop.DBus.Error.UnknownMethod",
+                &format!("Unknown method {}", method),
+            ),
+        },
+        |_, _, _, _, _| ().to_variant(),
+        |_, _, _, _, _, _| false,
+    );
+    if let Err(e) = result {
+        eprintln!("Failed to export the KRunner runner: {}", e);
+    }
+}
+
+fn matches(query: &str) -> Vec<Match> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_string).collect();
+    search_provider::find(&terms)
+        .into_iter()
+        .map(|result| {
+            let exact = result.title.eq_ignore_ascii_case(query.trim());
+            let properties = HashMap::from([
+                ("subtext".to_string(), result.description.to_variant()),
+                ("category".to_string(), "About this Linux".to_variant()),
+            ]);
+            (
+                result.tab,
+                result.title,
+                result.icon.to_string(),
+                if exact { EXACT_MATCH } else { POSSIBLE_MATCH },
+                if exact { 1.0 } else { 0.7 },
+                properties,
+            )
+        })
+        .collect()
+}
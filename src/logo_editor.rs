@@ -0,0 +1,258 @@
+use anyhow::{Context, Result};
+use gdk_pixbuf::{Colorspace, InterpType, Pixbuf};
+use gtk::gdk::prelude::GdkCairoContextExt;
+use gtk::prelude::*;
+use gtk::{gdk, gio, Box, Button, DrawingArea, DropDown, Label, Orientation, Scale, Widget, Window};
+use std::cell::Cell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use crate::paths;
+use crate::utils;
+
+// Side of the square the logo is framed in
+const CANVAS_SIZE: i32 = 320;
+const MAX_ZOOM: f64 = 8.0;
+const OUTPUT_SIZES: [i32; 4] = [128, 256, 512, 1024];
+const DEFAULT_OUTPUT_SIZE: u32 = 1;
+
+// How the logo sits in the square: zoom 1 fits the whole image, pan is the
+// offset of its centre as a fraction of the square
+#[derive(Clone, Copy)]
+struct Framing {
+    zoom: f64,
+    pan: (f64, f64),
+}
+
+impl Framing {
+    // Top-left corner and scale of the image in a `size` square
+    fn placement(&self, pixbuf: &Pixbuf, size: f64) -> (f64, f64, f64) {
+        let (width, height) = (pixbuf.width() as f64, pixbuf.height() as f64);
+        let scale = self.zoom * size / width.max(height);
+        let x = size * (0.5 + self.pan.0) - width * scale / 2.0;
+        let y = size * (0.5 + self.pan.1) - height * scale / 2.0;
+        (x, y, scale)
+    }
+
+    fn untouched(&self) -> bool {
+        self.zoom == 1.0 && self.pan == (0.0, 0.0)
+    }
+}
+
+/// Let the user pan, zoom and crop `source` to a square and write it to
+/// `paths::logo_path()` as a PNG at the chosen size. An SVG that isn't moved
+/// or zoomed is copied as it is. `on_saved` gets the written path; nothing
+/// happens on cancel.
+pub fn edit(parent: &impl IsA<Window>, source: &Path, on_saved: impl Fn(PathBuf) + 'static) {
+    let pixbuf = match load(source) {
+        Ok(pixbuf) => pixbuf,
+        Err(e) => {
+            eprintln!("Failed to load logo: {}", e);
+            utils::show_error_dialog(Some(parent.upcast_ref()), "Logo", &format!("{:#}", e));
+            return;
+        }
+    };
+
+    let dialog = Window::builder()
+        .title("Adjust Logo")
+        .transient_for(parent)
+        .modal(true)
+        .resizable(false)
+        .build();
+
+    let main_box = Box::new(Orientation::Vertical, 15);
+    main_box.set_margin_start(20);
+    main_box.set_margin_end(20);
+    main_box.set_margin_top(20);
+    main_box.set_margin_bottom(20);
+
+    let hint = Label::new(Some(if is_svg(source) {
+        "Drag to move the logo and zoom to crop it. Left as it is, the SVG is kept; \
+        otherwise it is saved as a square PNG."
+    } else {
+        "Drag to move the logo and zoom to crop it. It is saved as a square."
+    }));
+    hint.set_wrap(true);
+    hint.set_max_width_chars(40);
+    main_box.append(&hint);
+
+    let framing = Rc::new(Cell::new(Framing {
+        zoom: 1.0,
+        pan: (0.0, 0.0),
+    }));
+
+    let canvas = DrawingArea::new();
+    canvas.set_content_width(CANVAS_SIZE);
+    canvas.set_content_height(CANVAS_SIZE);
+    canvas.set_halign(gtk::Align::Center);
+    canvas.add_css_class("frame");
+    let draw_pixbuf = pixbuf.clone();
+    let draw_framing = framing.clone();
+    canvas.set_draw_func(move |_, cr, width, height| {
+        let size = width.min(height) as f64;
+        let (x, y, scale) = draw_framing.get().placement(&draw_pixbuf, size);
+        cr.rectangle(0.0, 0.0, size, size);
+        cr.clip();
+        cr.translate(x, y);
+        cr.scale(scale, scale);
+        cr.set_source_pixbuf(&draw_pixbuf, 0.0, 0.0);
+        let _ = cr.paint();
+    });
+
+    // Dragging moves the logo under the pointer
+    let drag = gtk::GestureDrag::new();
+    let drag_start = Rc::new(Cell::new((0.0, 0.0)));
+    let start = drag_start.clone();
+    let begin_framing = framing.clone();
+    drag.connect_drag_begin(move |_, _, _| start.set(begin_framing.get().pan));
+    let update_framing = framing.clone();
+    let update_canvas = canvas.clone();
+    drag.connect_drag_update(move |_, offset_x, offset_y| {
+        let (start_x, start_y) = drag_start.get();
+        let size = CANVAS_SIZE as f64;
+        let mut current = update_framing.get();
+        current.pan = (
+            (start_x + offset_x / size).clamp(-0.5, 0.5),
+            (start_y + offset_y / size).clamp(-0.5, 0.5),
+        );
+        update_framing.set(current);
+        update_canvas.queue_draw();
+    });
+    canvas.add_controller(drag);
+    main_box.append(&canvas);
+
+    let zoom_box = Box::new(Orientation::Horizontal, 10);
+    zoom_box.append(&Label::new(Some("Zoom")));
+    let zoom = Scale::with_range(Orientation::Horizontal, 1.0, MAX_ZOOM, 0.05);
+    zoom.set_hexpand(true);
+    zoom.set_draw_value(false);
+    let zoom_framing = framing.clone();
+    let zoom_canvas = canvas.clone();
+    zoom.connect_value_changed(move |scale| {
+        let mut current = zoom_framing.get();
+        current.zoom = scale.value();
+        zoom_framing.set(current);
+        zoom_canvas.queue_draw();
+    });
+    zoom_box.append(&zoom);
+    main_box.append(&zoom_box);
+
+    let size_box = Box::new(Orientation::Horizontal, 10);
+    size_box.append(&Label::new(Some("Size")));
+    let size_labels: Vec<String> = OUTPUT_SIZES.iter().map(|size| format!("{} × {}", size, size)).collect();
+    let size_labels: Vec<&str> = size_labels.iter().map(String::as_str).collect();
+    let output_size = DropDown::from_strings(&size_labels);
+    output_size.set_selected(DEFAULT_OUTPUT_SIZE);
+    size_box.append(&output_size);
+    main_box.append(&size_box);
+
+    let buttons_box = Box::new(Orientation::Horizontal, 10);
+    buttons_box.set_halign(gtk::Align::End);
+
+    let cancel_button = Button::with_label("Cancel");
+    let dialog_clone = dialog.clone();
+    cancel_button.connect_clicked(move |_| dialog_clone.close());
+    buttons_box.append(&cancel_button);
+
+    let save_button = Button::with_label("Save");
+    save_button.set_css_classes(&["suggested-action"]);
+    let dialog_clone = dialog.clone();
+    let source = source.to_path_buf();
+    save_button.connect_clicked(move |_| {
+        let size = OUTPUT_SIZES[(output_size.selected() as usize).min(OUTPUT_SIZES.len() - 1)];
+        let result = if is_svg(&source) && framing.get().untouched() {
+            // .svg or .svgz, as the source
+            let extension = source.extension().and_then(|extension| extension.to_str()).unwrap_or("svg");
+            let path = paths::logo_path(extension);
+            std::fs::copy(&source, &path)
+                .map(|_| path.clone())
+                .with_context(|| format!("Failed to write {}", path.display()))
+        } else {
+            let path = paths::logo_path("png");
+            save(&pixbuf, framing.get(), size, &path).map(|()| path)
+        };
+        match result {
+            Ok(path) => {
+                on_saved(path);
+                dialog_clone.close();
+            }
+            Err(e) => {
+                eprintln!("Failed to save logo: {:#}", e);
+                utils::show_error_dialog(Some(&dialog_clone), "Logo", &format!("{:#}", e));
+            }
+        }
+    });
+    buttons_box.append(&save_button);
+    main_box.append(&buttons_box);
+
+    dialog.set_child(Some(&main_box));
+    dialog.present();
+}
+
+/// Open image files dropped on `widget` in the editor, as if picked with a
+/// file chooser
+pub fn accept_drops(widget: &impl IsA<Widget>, parent: &impl IsA<Window>, on_saved: impl Fn(PathBuf) + 'static) {
+    let target = gtk::DropTarget::new(gio::File::static_type(), gdk::DragAction::COPY);
+    let parent = parent.as_ref().clone();
+    let on_saved = Rc::new(on_saved);
+    target.connect_drop(move |_, value, _, _| {
+        let Some(path) = value.get::<gio::File>().ok().and_then(|file| file.path()) else {
+            return false;
+        };
+        let on_saved = on_saved.clone();
+        edit(&parent, &path, move |logo_path| on_saved(logo_path));
+        true
+    });
+    widget.add_controller(target);
+}
+
+fn is_svg(source: &Path) -> bool {
+    matches!(
+        source.extension().and_then(|extension| extension.to_str()),
+        Some("svg") | Some("svgz")
+    )
+}
+
+fn load(source: &Path) -> Result<Pixbuf> {
+    // Vector logos are rasterised at the largest output size so they stay sharp
+    let max_size = OUTPUT_SIZES[OUTPUT_SIZES.len() - 1];
+    let pixbuf = if is_svg(source) {
+        Pixbuf::from_file_at_scale(source, max_size, max_size, true)
+    } else {
+        Pixbuf::from_file(source)
+    }
+    .with_context(|| format!("Cannot open {}", source.display()))?;
+    // Photos taken sideways carry their rotation in EXIF
+    Ok(pixbuf.apply_embedded_orientation().unwrap_or(pixbuf))
+}
+
+fn save(pixbuf: &Pixbuf, framing: Framing, size: i32, path: &Path) -> Result<()> {
+    let output = Pixbuf::new(Colorspace::Rgb, true, 8, size, size).context("Cannot allocate the logo")?;
+    output.fill(0);
+
+    // Only the part of the image that lands inside the square is drawn
+    let (x, y, scale) = framing.placement(pixbuf, size as f64);
+    let left = x.max(0.0).floor() as i32;
+    let top = y.max(0.0).floor() as i32;
+    let right = (x + pixbuf.width() as f64 * scale).min(size as f64).ceil() as i32;
+    let bottom = (y + pixbuf.height() as f64 * scale).min(size as f64).ceil() as i32;
+    if right > left && bottom > top {
+        pixbuf.composite(
+            &output,
+            left,
+            top,
+            right - left,
+            bottom - top,
+            x,
+            y,
+            scale,
+            scale,
+            InterpType::Hyper,
+            255,
+        );
+    }
+
+    output
+        .savev(path, "png", &[])
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
@@ -0,0 +1,93 @@
+use anyhow::{Context, Result};
+use gdk_pixbuf::Pixbuf;
+use gtk::prelude::*;
+use gtk::{gio, Box, FlowBox, Image, Label, Orientation};
+
+use crate::config;
+use crate::paths;
+use crate::system_info;
+
+// Logos compiled in from data/logos as (id, name), with ids matched against
+// os-release ID and ID_LIKE. They are simple monograms in each
+// distribution's colors rather than the trademarked artwork.
+pub const LOGOS: [(&str, &str); 8] = [
+    ("tux", "Tux"),
+    ("arch", "Arch"),
+    ("debian", "Debian"),
+    ("fedora", "Fedora"),
+    ("linuxmint", "Linux Mint"),
+    ("manjaro", "Manjaro"),
+    ("opensuse", "openSUSE"),
+    ("ubuntu", "Ubuntu"),
+];
+
+const TUX: usize = 0;
+const THUMBNAIL_SIZE: i32 = 64;
+
+/// The entry for the running distribution, or its closest parent; Tux when
+/// none matches
+pub fn detected() -> usize {
+    let ids = system_info::distro_ids();
+    ids.iter()
+        .find_map(|id| LOGOS.iter().position(|(logo_id, _)| logo_id == id))
+        .unwrap_or(TUX)
+}
+
+/// Unpack a gallery logo into the config directory and return the value
+/// for `distro_image_path`
+pub fn install(index: usize) -> Result<String> {
+    let (id, name) = LOGOS[index];
+    if id == "tux" {
+        return Ok(config::BUNDLED_LOGO.to_string());
+    }
+    let data = gio::resources_lookup_data(&resource_path(id), gio::ResourceLookupFlags::NONE)
+        .with_context(|| format!("{} logo is not bundled", name))?;
+    let path = paths::gallery_logo_path(id);
+    std::fs::write(&path, &data).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// A grid of the bundled logos with their names. `on_pick` gets the picked
+/// entry's index in `LOGOS`.
+pub fn picker(on_pick: impl Fn(usize) + 'static) -> FlowBox {
+    let flow_box = FlowBox::new();
+    flow_box.set_selection_mode(gtk::SelectionMode::Single);
+    flow_box.set_homogeneous(true);
+    flow_box.set_max_children_per_line(LOGOS.len() as u32);
+    flow_box.set_row_spacing(10);
+    flow_box.set_column_spacing(10);
+
+    for (id, name) in LOGOS {
+        let entry = Box::new(Orientation::Vertical, 5);
+        let thumbnail = Pixbuf::from_resource_at_scale(&resource_path(id), THUMBNAIL_SIZE, THUMBNAIL_SIZE, true)
+            .map(|pixbuf| Image::from_pixbuf(Some(&pixbuf)))
+            .unwrap_or_else(|_| Image::from_icon_name("image-missing"));
+        thumbnail.set_pixel_size(THUMBNAIL_SIZE);
+        entry.append(&thumbnail);
+        entry.append(&Label::new(Some(name)));
+        entry.set_tooltip_text(Some(&format!("Use the {} logo", name)));
+        flow_box.insert(&entry, -1);
+    }
+
+    flow_box.connect_child_activated(move |_, child| {
+        if let Ok(index) = usize::try_from(child.index()) {
+            on_pick(index);
+        }
+    });
+    flow_box
+}
+
+/// Select an entry without calling `on_pick`, e.g. to show the current logo
+pub fn select(flow_box: &FlowBox, index: usize) {
+    if let Some(child) = flow_box.child_at_index(index as i32) {
+        flow_box.select_child(&child);
+    }
+}
+
+fn resource_path(id: &str) -> String {
+    if id == "tux" {
+        paths::TUX_LOGO_RESOURCE.to_string()
+    } else {
+        format!("{}/{}.svg", paths::LOGOS_RESOURCE, id)
+    }
+}
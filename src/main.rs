@@ -1,17 +1,62 @@
 use anyhow::Result;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use gtk::prelude::*;
-use std::path::PathBuf;
+use std::cell::{Cell, RefCell};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
+mod actions;
+mod bench;
+mod backup;
+mod bundle;
 mod config;
 mod configurator;
+mod cpu_window;
+mod deps;
+mod flatpak;
+mod hooks;
+mod host;
+mod hostnamed;
+mod hotplug;
+mod install;
+mod journal;
+mod krunner;
+mod logo_editor;
+mod logo_gallery;
+mod journal_window;
 mod main_window;
+mod packagekit;
+mod paths;
+mod pdf;
+mod placement;
+mod power;
+mod privacy;
+mod profiles;
+mod probe;
+mod providers;
+mod region;
+mod remote;
+mod report;
+mod screenshot;
+mod search_provider;
+mod service;
+mod session;
+mod snap;
+mod state;
+mod status;
 mod system_info;
+mod theme;
+mod toast;
+mod tray;
+mod units;
+mod updates;
 mod utils;
+mod warranty;
 
-use config::Config;
+use config::{Config, Placement};
 use configurator::ConfiguratorWindow;
 use main_window::MainWindow;
+use providers::ProviderRegistry;
 
 const VERSION: &str = "0.9.0";
 const AUTHOR: &str = "Kamil 'Novik' Nowicki";
@@ -20,6 +65,12 @@ const ORIGINAL_AUTHOR: &str = "hungngocphat01";
 const ORIGINAL_REPO: &str = "https://github.com/hungngocphat01/AboutThisMc";
 const CURRENT_REPO: &str = "https://github.com/n0vik/about-this-linux.git";
 
+// Exit statuses of the command-line modes, for scripts; any other failure
+// exits with 1
+const EXIT_CONFIG_INVALID: i32 = 2;
+const EXIT_DETECTION_FAILED: i32 = 3;
+const EXIT_MISSING_DEPS: i32 = 4;
+
 #[derive(Parser)]
 #[command(author = "Kamil 'Novik' Nowicki <kamil.nowicki@h4b.uk>")]
 #[command(version = "0.9.0")]
@@ -33,43 +84,348 @@ struct Cli {
     #[arg(long = "config-path", value_name = "PATH")]
     config_path: Option<String>,
 
-    /// Load a custom overview configuration file
-    #[arg(long = "load-overview", value_name = "FILE")]
+    /// Use the named profile's config (profiles/NAME.json) instead of the default one
+    #[arg(long = "profile", value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Load a custom overview configuration file or https URL
+    #[arg(long = "load-overview", value_name = "FILE|URL")]
     load_overview: Option<String>,
+
+    /// Show only what the config holds, without running any detection
+    #[arg(long = "no-detect")]
+    no_detect: bool,
+
+    /// Run expensive detectors (SMART) even when on battery power
+    #[arg(long = "full-detection")]
+    full_detection: bool,
+
+    /// Use the compact layout regardless of the config
+    #[arg(long = "compact")]
+    compact: bool,
+
+    /// Hide the serial number and hostname, e.g. while taking screenshots
+    #[arg(long = "redact")]
+    redact: bool,
+
+    /// Keep running in the background with a tray icon, without opening a window
+    #[arg(long = "daemon")]
+    daemon: bool,
+
+    /// Keep running without a window and answer queries on the session bus
+    /// as com.novik.AboutThisLinux
+    #[arg(long = "service")]
+    service: bool,
+
+    /// Take over from an already running instance instead of activating it
+    #[arg(long = "replace")]
+    replace: bool,
+
+    /// Open on the given monitor connector (e.g. DP-1), or "pointer"
+    #[arg(long = "monitor", value_name = "NAME")]
+    monitor: Option<String>,
+
+    /// Open on the given tab (e.g. storage, display, support)
+    #[arg(long = "page", visible_alias = "tab", value_name = "NAME")]
+    page: Option<String>,
+
+    /// Capture the window, mapped but fully transparent, as a PNG and exit
+    #[arg(long = "screenshot", value_name = "FILE")]
+    screenshot: Option<PathBuf>,
+
+    /// Validate a config file (the default one if no PATH is given) and exit
+    #[arg(long = "check-config", value_name = "PATH")]
+    check_config: Option<Option<PathBuf>>,
+
+    /// Write the config and its logo to a .tar.gz bundle and exit
+    #[arg(long = "export-bundle", value_name = "FILE")]
+    export_bundle: Option<PathBuf>,
+
+    /// Replace the config with the one in a bundle and exit
+    #[arg(long = "import-bundle", value_name = "FILE")]
+    import_bundle: Option<PathBuf>,
+
+    /// Also import the hooks, custom providers and commands of an
+    /// --import-bundle config, which are left out otherwise
+    #[arg(long = "trust-bundle", requires = "import_bundle")]
+    trust_bundle: bool,
+
+    /// Print the overview as plain text and exit, without opening a window.
+    /// SECTIONS adds tabs, e.g. "storage,display", or "all"
+    #[arg(long = "print", value_name = "SECTIONS", value_delimiter = ',', num_args = 0..)]
+    print: Option<Vec<String>>,
+
+    /// Keep printing uptime, memory, disk usage and temperatures, showing what
+    /// changed every SECONDS (the config's auto_refresh_seconds by default)
+    #[arg(long = "watch", value_name = "SECONDS", num_args = 0..=1, conflicts_with = "json")]
+    watch: Option<Option<u32>>,
+
+    /// Print everything that is detected as JSON and exit
+    #[arg(long = "json")]
+    json: bool,
+
+    /// Print a compact JSON summary for a Plasma panel widget and exit
+    #[arg(long = "plasma-widget-data")]
+    plasma_widget_data: bool,
+
+    /// Limit --json (or extend --print) to these tabs, e.g. "overview,storage"
+    #[arg(long = "sections", value_name = "SECTIONS", value_delimiter = ',')]
+    sections: Option<Vec<String>>,
+
+    /// Write the full system report to a PDF and exit (needs a display)
+    #[arg(long = "export-pdf", value_name = "FILE")]
+    export_pdf: Option<PathBuf>,
+
+    /// List the optional tools used for detection, which are missing and
+    /// how to install them, and exit
+    #[arg(long = "check-deps")]
+    check_deps: bool,
+
+    /// Print only errors and results, without status messages, e.g. in scripts
+    #[arg(long = "quiet", short = 'q')]
+    quiet: bool,
+
+    /// Print a commented default config with detected values filled in and exit
+    #[arg(long = "dump-default-config")]
+    dump_default_config: bool,
+
+    /// Run as a D-Bus activated service
+    #[arg(long = "gapplication-service", hide = true)]
+    gapplication_service: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Time every detector and print how long each took, with the tools it ran
+    Bench {
+        /// Run each detector this many times and show min/avg/max
+        #[arg(long = "runs", value_name = "N", default_value_t = 1)]
+        runs: u32,
+    },
+    /// Add a desktop entry and icons for the current user, pointing at this binary
+    Install {
+        /// Also start in the background with a tray icon at login
+        #[arg(long = "autostart")]
+        autostart: bool,
+    },
+    /// Remove what `install` added; the config is kept
+    Uninstall,
 }
 
 fn main() -> Result<()> {
     let args = Cli::parse();
 
+    paths::migrate_legacy();
+    if let Some(name) = &args.profile {
+        profiles::check_name(name)?;
+        paths::use_profile(name);
+    }
+    if let Err(e) = gtk::gio::resources_register_include!("resources.gresource") {
+        eprintln!("Failed to load the bundled resources: {}", e);
+    }
+
+    if let Some(path) = args.check_config {
+        let path = path
+            .or_else(|| args.config_path.as_ref().map(PathBuf::from))
+            .unwrap_or_else(paths::default_config_path);
+        std::process::exit(check_config(&path, args.quiet));
+    }
+
+    if args.check_deps {
+        let installed = if args.quiet { deps::all_installed() } else { deps::print_report() };
+        std::process::exit(if installed { 0 } else { EXIT_MISSING_DEPS });
+    }
+
+    if args.full_detection {
+        system_info::request_full_detection();
+    }
+    if args.no_detect {
+        system_info::request_no_detect();
+    }
+    if args.redact {
+        privacy::request_redact();
+    }
+
+    match args.command {
+        Some(Command::Bench { runs }) => {
+            let settings = load_saved_settings();
+            system_info::configure(&settings);
+            bench::run(&settings, runs);
+            return Ok(());
+        }
+        Some(Command::Install { autostart }) => return install::install(autostart, args.quiet),
+        Some(Command::Uninstall) => return install::uninstall(args.quiet),
+        None => {}
+    }
+
+    if let Some(seconds) = args.watch {
+        watch_live_values(seconds);
+    }
+
+    if args.plasma_widget_data {
+        let config = detected_config(args.load_overview.as_deref())
+            .unwrap_or_else(|e| exit_with(EXIT_CONFIG_INVALID, e));
+        println!("{}", report::plasma_widget_json(&config));
+        return Ok(());
+    }
+
+    if args.print.is_some() || args.json {
+        let mut sections = args.print.unwrap_or_default();
+        sections.extend(args.sections.unwrap_or_default());
+        print_detected(&sections, args.load_overview.as_deref(), args.json)?;
+        exit_if_detection_failed();
+        return Ok(());
+    }
+
+    if let Some(path) = args.export_pdf {
+        gtk::init()?;
+        let config = detected_config(args.load_overview.as_deref())
+            .unwrap_or_else(|e| exit_with(EXIT_CONFIG_INVALID, e));
+        let registry = ProviderRegistry::from_config(&config);
+        pdf::export(&report::printable_report(&config, &registry), &path)?;
+        if !args.quiet {
+            println!("Exported report to {}", path.display());
+        }
+        exit_if_detection_failed();
+        return Ok(());
+    }
+
+    if args.dump_default_config {
+        let config = create_auto_detected_config(Config::default());
+        println!("{}", config.to_documented_json()?);
+        return Ok(());
+    }
+
+    if args.export_bundle.is_some() || args.import_bundle.is_some() {
+        let path = args
+            .config_path
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(paths::default_config_path);
+        if let Some(dest) = args.export_bundle {
+            bundle::export_bundle(config::read_resolved_json(&path)?, &dest)?;
+            if !args.quiet {
+                println!("Exported {} to {}", path.display(), dest.display());
+            }
+        }
+        if let Some(bundle_path) = args.import_bundle {
+            let keep = Config::load(&path)
+                .map(|config| config.backup_count)
+                .unwrap_or(Config::default().backup_count);
+            if !args.trust_bundle {
+                let commands = bundle::bundle_commands(&bundle_path)?;
+                if !commands.is_empty() {
+                    eprintln!("Leaving out these commands from the bundle (pass --trust-bundle to import them):");
+                    for command in &commands {
+                        eprintln!("  {}", command);
+                    }
+                }
+            }
+            bundle::import_bundle(&bundle_path, &path, keep, args.trust_bundle)?;
+            if !args.quiet {
+                println!("Imported {} into {}", bundle_path.display(), path.display());
+            }
+        }
+        return Ok(());
+    }
+
     let configure = args.configure;
     let config_path = args.config_path;
-    let load_overview = args.load_overview;
+    // Remote overviews are fetched before GTK starts so a slow server
+    // doesn't leave a frozen window
+    let load_overview = match args.load_overview {
+        Some(location) if remote::is_url(&location) => match remote::fetch_overview(&location) {
+            Ok(path) => Some(path.to_string_lossy().to_string()),
+            Err(e) => {
+                eprintln!("Error loading config: {:#}", e);
+                std::process::exit(1);
+            }
+        },
+        location => location,
+    };
+    let monitor = args.monitor;
+    let page = args.page;
+    let quiet = args.quiet;
+
+    if args.compact {
+        main_window::request_compact();
+    }
     
     // Initialize GTK after parsing arguments
+    // A running instance hands over when started again with --replace
+    let mut flags = gtk::gio::ApplicationFlags::ALLOW_REPLACEMENT;
+    if args.gapplication_service {
+        flags |= gtk::gio::ApplicationFlags::IS_SERVICE;
+    }
+    if args.replace {
+        flags |= gtk::gio::ApplicationFlags::REPLACE;
+    }
+    // A screenshot run has its own window, even next to a running instance
+    let screenshot = args.screenshot;
+    if screenshot.is_some() {
+        flags |= gtk::gio::ApplicationFlags::NON_UNIQUE;
+    }
     let app = gtk::Application::builder()
         .application_id("com.novik.about-this-linux")
+        .flags(flags)
         .build();
     
-    // Set application icon
-    if let Ok(_) = gdk_pixbuf::Pixbuf::from_file("tux-logo.png") {
-        // Icon set successfully
-    } else if let Ok(_) = gdk_pixbuf::Pixbuf::from_file("./tux-logo.png") {
-        // Icon set successfully
-    }
 
-    app.connect_activate(move |app| {
-        // Ensure the app follows the system theme
-        if let Some(settings) = gtk::Settings::default() {
-            // Check if user prefers dark theme
-            if is_dark_theme_preferred() {
-                settings.set_gtk_application_prefer_dark_theme(true);
-            } else {
-                settings.set_gtk_application_prefer_dark_theme(false);
-            }
+    // In the background the window is built once, then only hidden and
+    // shown again
+    let settings = load_saved_settings();
+    let background = screenshot.is_none() && (args.daemon || settings.background);
+    let update_check_hours = if screenshot.is_none() { settings.update_check_hours } else { 0 };
+    // Failures inside the GTK callbacks quit the application and leave their
+    // exit status here
+    let exit_status = Rc::new(Cell::new(0));
+    let status = exit_status.clone();
+    let start_hidden = Cell::new(args.daemon);
+    // The service only builds a window once a client asks for one
+    let service = args.service;
+    let service_idle = Cell::new(service);
+    let current_window: Rc<RefCell<Option<MainWindow>>> = Rc::new(RefCell::new(None));
+    let hold = RefCell::new(None);
+
+    // Tab asked for by app.show-tab, picked up by the next activate
+    let requested_page: Rc<RefCell<Option<String>>> = Rc::new(RefCell::new(None));
+    actions::register_window_actions(&app, current_window.clone(), requested_page.clone());
+
+    // Styling and actions are set up at startup so windows opened through
+    // desktop actions, without an activate, get them too
+    app.connect_startup(move |app| {
+        #[cfg(feature = "libadwaita")]
+        if let Err(e) = adw::init() {
+            eprintln!("Failed to initialize libadwaita: {}", e);
+        }
+        theme::install_default();
+        theme::follow_color_scheme();
+
+        actions::register(app);
+        search_provider::register(app);
+        krunner::register(app);
+
+        if background || service {
+            *hold.borrow_mut() = Some(app.hold());
+        }
+        if background {
+            tray::register(app);
         }
-        
+        if service {
+            service::register(app);
+        }
+        // Only a resident instance lives long enough to check again later
+        if update_check_hours > 0 {
+            updates::start_checker(app, update_check_hours, background);
+        }
+    });
+
+    app.connect_activate(move |app| {
         if configure {
-            let default_config_path = get_default_config_path();
+            let default_config_path = paths::default_config_path();
             let config_path = config_path
                 .as_ref()
                 .map(|p| PathBuf::from(p))
@@ -77,78 +433,300 @@ fn main() -> Result<()> {
             
             let configurator = ConfiguratorWindow::new(app, config_path);
             configurator.present();
-        } else if let Some(ref overview_path) = load_overview {
-            let config_path = PathBuf::from(overview_path);
-            match Config::load(&config_path) {
-                Ok(config) => {
-                    let main_window = MainWindow::new(app, config);
-                    main_window.present();
-                }
+            return;
+        }
+
+        let requested = requested_page.borrow_mut().take();
+        if service_idle.replace(false) && requested.is_none() {
+            return;
+        }
+
+        // Launching the binary again activates this instance; bring the
+        // window forward instead of stacking up another one
+        if let Some(main_window) = current_window.borrow().as_ref().filter(|window| window.is_open()) {
+            if let Some(page) = requested {
+                main_window.show_page(&page);
+            }
+            main_window.present();
+            return;
+        }
+
+        let page = requested.or_else(|| page.clone());
+        let main_window = if let Some(ref overview_path) = load_overview {
+            match build_overview_window(app, Path::new(overview_path), monitor.as_deref(), page.as_deref()) {
+                Ok(main_window) => main_window,
                 Err(e) => {
-                    eprintln!("Error loading config: {}", e);
-                    std::process::exit(1);
+                    eprintln!("Error loading config: {:#}", e);
+                    status.set(EXIT_CONFIG_INVALID);
+                    app.quit();
+                    return;
                 }
             }
         } else {
-            // Auto-detect system information and create config
-            let config = create_auto_detected_config();
-            let main_window = MainWindow::new(app, config);
+            build_main_window(app, monitor.as_deref(), page.as_deref())
+        };
+        if let Some(path) = screenshot.clone() {
+            let app = app.clone();
+            let status = status.clone();
+            main_window.capture_on_screen(path.clone(), move |result| {
+                match result {
+                    Ok(()) if !quiet => println!("Saved screenshot to {}", path.display()),
+                    Ok(()) => {}
+                    Err(e) => {
+                        eprintln!("Failed to save screenshot: {:#}", e);
+                        status.set(1);
+                    }
+                }
+                app.quit();
+            });
+            *current_window.borrow_mut() = Some(main_window);
+            return;
+        }
+        if background {
+            main_window.keep_resident();
+        }
+        if !start_hidden.replace(false) {
             main_window.present();
         }
+        *current_window.borrow_mut() = Some(main_window);
     });
 
     app.run_with_args(&["about-this-linux"]);
+    if exit_status.get() != 0 {
+        std::process::exit(exit_status.get());
+    }
     Ok(())
 }
 
-fn is_dark_theme_preferred() -> bool {
-    // Check various sources for dark theme preference
-    
-    // 1. Check GTK theme name
-    if let Some(settings) = gtk::Settings::default() {
-        if let Some(theme_name) = settings.gtk_theme_name() {
-            let theme_name = theme_name.as_str().to_lowercase();
-            if theme_name.contains("dark") || theme_name.contains("adwaita-dark") {
-                return true;
-            }
+fn build_overview_window(
+    app: &gtk::Application,
+    config_path: &Path,
+    monitor: Option<&str>,
+    page: Option<&str>,
+) -> Result<MainWindow> {
+    let config = with_monitor_override(Config::load(config_path)?, monitor);
+    print_config_warnings(&config);
+    let main_window = MainWindow::new(app, config);
+    if let Some(page) = page {
+        main_window.show_page(page);
+    }
+    let watched_path = config_path.to_path_buf();
+    main_window.watch_config(config_path, move |current| {
+        let config = Config::load(&watched_path)?;
+        // Placement only matters at startup; keep what was used
+        Ok(Config {
+            placement: current.placement,
+            placement_monitor: current.placement_monitor.clone(),
+            ..config
+        })
+    });
+    Ok(main_window)
+}
+
+fn build_main_window(app: &gtk::Application, monitor: Option<&str>, page: Option<&str>) -> MainWindow {
+    // Auto-detect system information, keeping settings from a saved config
+    let config = create_auto_detected_config(load_saved_settings());
+    let config = with_monitor_override(config, monitor);
+    print_config_warnings(&config);
+    let main_window = MainWindow::new(app, config);
+    if let Some(page) = page {
+        main_window.show_page(page);
+    }
+    main_window.set_redetect(|current| create_auto_detected_config(current.clone()));
+    let config_path = paths::default_config_path();
+    let watched_path = config_path.clone();
+    main_window.watch_config(&config_path, move |current| {
+        reload_saved_settings(current, &watched_path)
+    });
+    main_window
+}
+
+// Hand-edited settings on top of what this session already detected, so a
+// reload doesn't have to run detection again
+fn reload_saved_settings(current: &Config, config_path: &Path) -> Result<Config> {
+    let settings = Config::load(config_path)?;
+    let saved = settings.clone();
+    // A logo set in the file wins, as at launch; otherwise keep the detected one
+    let detected_logo = current.distro_image_path != config::BUNDLED_LOGO;
+    let (logo_path, logo_size) = if settings.distro_image_path == config::BUNDLED_LOGO && detected_logo {
+        (current.distro_image_path.clone(), current.distro_image_size)
+    } else {
+        (settings.distro_image_path.clone(), settings.distro_image_size)
+    };
+    let mut config = Config {
+        distro_image_path: logo_path,
+        distro_image_size: logo_size,
+        hostname: current.hostname.clone(),
+        cpu: current.cpu.clone(),
+        memory: current.memory.clone(),
+        startup_disk: current.startup_disk.clone(),
+        graphics: current.graphics.clone(),
+        serial_num: current.serial_num.clone(),
+        detection_errors: current.detection_errors.clone(),
+        placement: current.placement,
+        placement_monitor: current.placement_monitor.clone(),
+        ..settings
+    };
+    config.restore_locked(&saved);
+    config.apply_env_overrides();
+    config.validation_warnings = config.validate();
+    Ok(config)
+}
+
+fn with_monitor_override(config: Config, monitor: Option<&str>) -> Config {
+    match monitor {
+        Some("pointer") => Config {
+            placement: Placement::Pointer,
+            ..config
+        },
+        Some(name) => Config {
+            placement: Placement::Monitor,
+            placement_monitor: Some(name.to_string()),
+            ..config
+        },
+        None => config,
+    }
+}
+
+fn load_saved_settings() -> Config {
+    let config_path = paths::default_config_path();
+    if config_path.exists() {
+        match Config::load(&config_path) {
+            Ok(config) => return config,
+            Err(e) => eprintln!("Ignoring saved config: {}", e),
         }
     }
-    
-    // 2. Check gsettings
-    if let Ok(output) = std::process::Command::new("gsettings")
-        .args(&["get", "org.gnome.desktop.interface", "gtk-theme"])
-        .output() {
-        let theme = String::from_utf8_lossy(&output.stdout).trim().to_lowercase();
-        if theme.contains("dark") {
-            return true;
+    Config::default()
+}
+
+// --print and --json, for scripts and SSH sessions: the same detection as
+// the window, but GTK is never initialized
+fn print_detected(sections: &[String], overview_path: Option<&str>, json: bool) -> Result<()> {
+    let config = detected_config(overview_path).unwrap_or_else(|e| exit_with(EXIT_CONFIG_INVALID, e));
+    let registry = ProviderRegistry::from_config(&config);
+
+    let known = registry.categories();
+    for section in sections {
+        if section != "all" && !known.contains(section) {
+            eprintln!("Unknown section \"{}\", available: {}", section, known.join(", "));
         }
     }
-    
-    // 3. Check color scheme preference
-    if let Ok(output) = std::process::Command::new("gsettings")
-        .args(&["get", "org.gnome.desktop.interface", "color-scheme"])
-        .output() {
-        let scheme = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        if scheme.contains("dark") || scheme.contains("prefer-dark") {
-            return true;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report::json_report(&config, &registry, sections))?);
+    } else {
+        print!("{}", report::sections_report(&config, &registry, sections));
+    }
+    Ok(())
+}
+
+// --watch: the volatile values once, then only those that changed, like
+// `watch df` without clearing the screen. Runs until interrupted.
+fn watch_live_values(seconds: Option<u32>) -> ! {
+    let settings = load_saved_settings();
+    system_info::configure(&settings);
+    let seconds = seconds.unwrap_or(settings.auto_refresh_seconds).max(1);
+
+    // Only disks that reported a temperature are asked again, as in the window
+    let devices: Vec<String> = system_info::StorageInfo::detect()
+        .map(|storage| {
+            storage
+                .devices
+                .into_iter()
+                .filter(|device| device.temperature.is_ok())
+                .map(|device| device.name)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut previous: Vec<(String, String)> = Vec::new();
+    loop {
+        let rows = report::live_rows(&devices);
+        let changed: Vec<(String, String)> = rows
+            .iter()
+            .filter_map(|(key, value)| match previous.iter().find(|(seen, _)| seen == key) {
+                Some((_, old)) if old == value => None,
+                Some((_, old)) => Some((key.clone(), format!("{} -> {}", old, value))),
+                None => Some((key.clone(), value.clone())),
+            })
+            .collect();
+
+        if !changed.is_empty() {
+            println!("{}\n{}", units::format_now(), report::format_rows(&changed));
         }
+        previous = rows;
+        // Slows down on battery like the window's auto-refresh
+        std::thread::sleep(std::time::Duration::from_secs(system_info::refresh_interval(seconds) as u64));
     }
-    
-    // 4. Check KDE settings
-    if let Ok(output) = std::process::Command::new("kreadconfig5")
-        .args(&["--group", "General", "--key", "ColorScheme"])
-        .output() {
-        let scheme = String::from_utf8_lossy(&output.stdout).trim().to_lowercase();
-        if scheme.contains("dark") {
-            return true;
+}
+
+// What the window would show: a --load-overview config, or detection on top
+// of the saved settings
+fn detected_config(overview_path: Option<&str>) -> Result<Config> {
+    let config = match overview_path {
+        Some(location) if remote::is_url(location) => Config::load(&remote::fetch_overview(location)?)?,
+        Some(path) => Config::load(Path::new(path))?,
+        None => create_auto_detected_config(load_saved_settings()),
+    };
+    print_config_warnings(&config);
+    Ok(config)
+}
+
+// Config::validate only collects the warnings; they are printed once, where
+// the config is first used
+fn print_config_warnings(config: &Config) {
+    for warning in &config.validation_warnings {
+        eprintln!("Config warning: {}", warning);
+    }
+}
+
+fn exit_with(status: i32, error: anyhow::Error) -> ! {
+    eprintln!("Error: {:#}", error);
+    std::process::exit(status);
+}
+
+// A report was written, but with sections that show only their error
+fn exit_if_detection_failed() {
+    let failed = report::failed_sections();
+    if failed > 0 {
+        eprintln!("Detection failed for {} section{}", failed, if failed == 1 { "" } else { "s" });
+        std::process::exit(EXIT_DETECTION_FAILED);
+    }
+}
+
+fn check_config(path: &Path, quiet: bool) -> i32 {
+    let check = Config::check(path);
+    for error in &check.errors {
+        eprintln!("Config error: {}", error);
+    }
+
+    for warning in &check.warnings {
+        eprintln!("Config warning: {}", warning);
+    }
+
+    if check.errors.is_empty() {
+        if quiet {
+            return 0;
         }
+        println!(
+            "{}: OK ({} warning{})",
+            path.display(),
+            check.warnings.len(),
+            if check.warnings.len() == 1 { "" } else { "s" }
+        );
+        0
+    } else {
+        eprintln!("{}: {} error(s)", path.display(), check.errors.len());
+        EXIT_CONFIG_INVALID
     }
-    
-    // Default to false (light theme)
-    false
 }
 
-fn create_auto_detected_config() -> Config {
+fn create_auto_detected_config(settings: Config) -> Config {
+    system_info::configure(&settings);
+    if system_info::detection_disabled() {
+        return settings;
+    }
+
     // Detect system information
     let system_info = system_info::SystemInfo::detect().unwrap_or_else(|_| {
         system_info::SystemInfo {
@@ -158,13 +736,24 @@ fn create_auto_detected_config() -> Config {
             startup_disk: "Unknown Disk".to_string(),
             graphics: "Unknown Graphics".to_string(),
             serial_number: "Unknown".to_string(),
+            errors: std::collections::HashMap::new(),
         }
     });
     
-    // Detect distribution-specific logo and size
-    let (logo_path, logo_size) = detect_system_logo();
-    
-    Config {
+    // A logo the config sets (picked in the configurator, cropped, dropped
+    // onto the overview) is kept; only the bundled Tux fallback gives way
+    // to the detected one
+    let (logo_path, logo_size) = if settings.distro_image_path == config::BUNDLED_LOGO {
+        match detect_system_logo() {
+            (path, _) if path == config::BUNDLED_LOGO => (path, settings.distro_image_size),
+            detected => detected,
+        }
+    } else {
+        (settings.distro_image_path.clone(), settings.distro_image_size)
+    };
+
+    let saved = settings.clone();
+    let mut config = Config {
         distro_image_path: logo_path,
         distro_image_size: logo_size,
         hostname: system_info.hostname,
@@ -173,17 +762,80 @@ fn create_auto_detected_config() -> Config {
         startup_disk: system_info.startup_disk,
         graphics: system_info.graphics,
         serial_num: system_info.serial_number,
-        overview_margins: [60, 60, 60, 60],
-        section_space: 20,
-        logo_space: 60,
-        system_info_command: "".to_string(),
-        software_update_command: "".to_string(),
-        font_family: None,
+        detection_errors: system_info
+            .errors
+            .iter()
+            .map(|(field, error)| (field.clone(), error.to_string()))
+            .collect(),
+        ..settings
+    };
+    config.restore_locked(&saved);
+
+    // Detection replaced the saved values, so layer the environment on top again
+    config.apply_env_overrides();
+
+    // Detection may have replaced the logo, so check the final values
+    config.validation_warnings = config.validate();
+    config
+}
+
+const LOGO_SIZE: i32 = 256;
+
+// Every icon-theme logo for this distribution, best match first. Themes
+// often ship the logo as SVG or at several sizes, so each is asked for at a
+// size big enough for the display's scale. Only possible once GTK is up,
+// i.e. not for --dump-default-config.
+fn themed_logo_paths() -> Vec<String> {
+    if !gtk::is_initialized_main_thread() {
+        return Vec::new();
     }
+    let Some(display) = gtk::gdk::Display::default() else {
+        return Vec::new();
+    };
+    let theme = gtk::IconTheme::for_display(&display);
+    let scale = utils::max_scale_factor();
+
+    system_info::logo_icon_names()
+        .iter()
+        .filter(|name| theme.has_icon(name))
+        .filter_map(|name| {
+            let icon = theme.lookup_icon(
+                name,
+                &[],
+                LOGO_SIZE,
+                scale,
+                gtk::TextDirection::None,
+                gtk::IconLookupFlags::empty(),
+            );
+            icon.file()?.path().map(|path| path.to_string_lossy().to_string())
+        })
+        .collect()
 }
 
 fn detect_system_logo() -> (String, [i32; 2]) {
-    // Try to detect distribution
+    // Fallback to the Tux logo built into the binary
+    logo_candidates()
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| (config::BUNDLED_LOGO.to_string(), [256, 256]))
+}
+
+/// Every logo installed for this system with the size to show it at, in
+/// the order `detect_system_logo` prefers them, for the configurator's
+/// "Find Logos on This System"
+fn logo_candidates() -> Vec<(String, [i32; 2])> {
+    let mut candidates: Vec<(String, [i32; 2])> = Vec::new();
+    let mut add = |path: String, size: [i32; 2]| {
+        if !candidates.iter().any(|(known, _)| *known == path) {
+            candidates.push((path, size));
+        }
+    };
+
+    for path in themed_logo_paths() {
+        add(path, [LOGO_SIZE, LOGO_SIZE]);
+    }
+
+    // Then well-known paths, starting with the distribution
     let distro_info = system_info::DynamicSystemInfo::detect().unwrap_or_else(|_| {
         system_info::DynamicSystemInfo {
             distro_name: "Unknown Linux".to_string(),
@@ -219,14 +871,15 @@ fn detect_system_logo() -> (String, [i32; 2]) {
         ("redhat", "/usr/share/pixmaps/redhat-logo.png", [256, 256]),
     ];
     
-    // Try to find distribution-specific logo
+    // Distribution-specific logos
     for (distro_name, logo_path, size) in logo_configs {
-        if distro_lower.contains(distro_name) && std::path::Path::new(logo_path).exists() {
-            return (logo_path.to_string(), size);
+        let logo_path = host::path(logo_path);
+        if distro_lower.contains(distro_name) && logo_path.exists() {
+            add(logo_path.to_string_lossy().to_string(), size);
         }
     }
     
-    // Try some generic locations
+    // Some generic locations, then desktop environment specific logos
     let generic_paths = vec![
         "/usr/share/pixmaps/distributor-logo.png",
         "/usr/share/icons/hicolor/scalable/apps/distributor-logo.svg",
@@ -234,48 +887,18 @@ fn detect_system_logo() -> (String, [i32; 2]) {
         "/usr/share/icons/hicolor/48x48/apps/distributor-logo.png",
         "/usr/share/icons/hicolor/64x64/apps/distributor-logo.png",
         "/usr/share/icons/hicolor/128x128/apps/distributor-logo.png",
-    ];
-    
-    for path in generic_paths {
-        if std::path::Path::new(path).exists() {
-            return (path.to_string(), [256, 256]);
-        }
-    }
-    
-    // Check for desktop environment specific logos
-    let de_logos = vec![
         "/usr/share/pixmaps/gnome-logo.png",
         "/usr/share/pixmaps/kde-logo.png",
         "/usr/share/pixmaps/xfce-logo.png",
     ];
     
-    for path in de_logos {
-        if std::path::Path::new(path).exists() {
-            return (path.to_string(), [256, 256]);
-        }
-    }
-    
-    // Fallback to tux logo
-    let tux_paths = vec![
-        "tux-logo.png",
-        "./tux-logo.png",
-        "/usr/share/pixmaps/tux.png",
-    ];
-    
-    for path in tux_paths {
-        if std::path::Path::new(path).exists() {
-            return (path.to_string(), [256, 256]);
+    for path in generic_paths {
+        let path = host::path(path);
+        if path.exists() {
+            add(path.to_string_lossy().to_string(), [256, 256]);
         }
     }
-    
-    // Ultimate fallback
-    ("tux-logo.png".to_string(), [256, 256])
-}
 
-fn get_default_config_path() -> PathBuf {
-    let mut config_dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/tmp"));
-    config_dir.push(".local/share/about-this-linux");
-    std::fs::create_dir_all(&config_dir).unwrap_or_default();
-    config_dir.push("overview-conf.json");
-    config_dir
+    candidates
 }
+
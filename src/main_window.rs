@@ -1,25 +1,131 @@
 use gtk::prelude::*;
-use gtk::{Application, ApplicationWindow, Box, Button, HeaderBar, Image, Label, Stack, StackSwitcher, Orientation};
+use gtk::{gio, glib, Application, ApplicationWindow, Box, Button, HeaderBar, Image, Label, Orientation};
+#[cfg(feature = "libadwaita")]
+use adw::{prelude::AdwApplicationWindowExt, ViewStack as Stack, ViewSwitcher as StackSwitcher};
+#[cfg(not(feature = "libadwaita"))]
+use gtk::{Stack, StackSwitcher};
 use gdk_pixbuf::Pixbuf;
-
-use crate::config::Config;
-use crate::system_info::{DynamicSystemInfo, DisplayInfo, StorageInfo};
+use std::cell::{Cell, RefCell};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::{self, Config, Placement, ThemePreset, DEFAULT_TABS};
+use crate::cpu_window::CpuWindow;
+use crate::hooks::{run_hook, HookEvent};
+use crate::host;
+use crate::hostnamed;
+use crate::hotplug;
+use crate::journal;
+use crate::logo_editor;
+use crate::paths;
+use crate::pdf;
+use crate::placement;
+use crate::power;
+use crate::privacy;
+use crate::probe;
+use crate::providers::{ProviderRegistry, CATEGORY_DISPLAY, CATEGORY_OVERVIEW, CATEGORY_POWER, CATEGORY_STORAGE, CATEGORY_USB};
+use crate::status::{detection_error_icon, set_usage, status_widget, usage_bar, Status};
+use crate::system_info::{self, DynamicSystemInfo, DisplayInfo, StorageInfo};
+use crate::report;
+use crate::screenshot;
+use crate::region;
+use crate::session;
+use crate::state::WindowState;
+use crate::theme;
+use crate::toast::Toast;
+use crate::units;
+use crate::updates::{self, UpdateSource};
+use crate::utils::{self, escape_markup};
+use crate::warranty;
 use crate::{AUTHOR, AUTHOR_EMAIL, VERSION, ORIGINAL_AUTHOR, ORIGINAL_REPO, CURRENT_REPO};
 
+const MIN_WIDTH: i32 = 600;
+const MIN_HEIGHT: i32 = 400;
+// Below this width a resizable window stacks the logo above the overview
+const STACKED_LAYOUT_WIDTH: i32 = 560;
+const PAGE_MARGIN: i32 = 40;
+const EASTER_EGG_CLICKS: i32 = 5;
+// Frames drawn before --screenshot captures the window
+const SCREENSHOT_FRAMES: u32 = 3;
+const LOGO_FLIP_DURATION: Duration = Duration::from_millis(400);
+const COMPACT_PAGE_MARGIN: i32 = 16;
+// Compact mode scales the configured overview sizes rather than replacing them
+const COMPACT_SCALE: f64 = 0.5;
+// Packages named in the Software Update dialog before "…and N more"
+const MAX_LISTED_UPDATES: usize = 15;
+
+// Set from --compact, on top of the config option
+static COMPACT_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+pub fn request_compact() {
+    COMPACT_REQUESTED.store(true, Ordering::Relaxed);
+}
+
+// Labels and bars showing values that change while the window is open, kept
+// so the auto-refresh can update them in place. Rebuilding a page replaces
+// its entries.
+#[derive(Default)]
+struct LiveWidgets {
+    uptime: Option<Label>,
+    memory_usage: Option<Label>,
+    battery: Option<Label>,
+    // Keyed by mountpoint
+    volumes: Vec<(String, gtk::LevelBar, Label)>,
+    // Keyed by device path
+    temperatures: Vec<(String, Label)>,
+    // Pending updates count on the Software Update button
+    update_badge: Option<Label>,
+}
+
+// A row the header-bar search can filter, registered as pages are built
+struct SearchRow {
+    page: String,
+    widget: gtk::Widget,
+    // Lowercased label and value
+    text: String,
+}
+
+// Re-runs hardware detection for the overview, keeping the other settings
+type Redetect = Arc<dyn Fn(&Config) -> Config + Send + Sync>;
+
+#[derive(Clone)]
 pub struct MainWindow {
     window: ApplicationWindow,
-    config: Config,
+    stack: Stack,
+    config: Rc<RefCell<Config>>,
+    registry: Rc<ProviderRegistry>,
+    config_monitor: Rc<RefCell<Option<gio::FileMonitor>>>,
+    toast: Toast,
+    redetect: Rc<RefCell<Option<Redetect>>>,
+    live: Rc<RefCell<LiveWidgets>>,
+    search_entry: gtk::SearchEntry,
+    search_rows: Rc<RefCell<Vec<SearchRow>>>,
+    // Logo and info box of the current overview page
+    overview_box: Rc<RefCell<Option<Box>>>,
+    auto_refresh_pending: Rc<Cell<bool>>,
+    menu: gio::Menu,
+    stack_switcher: StackSwitcher,
+    // Config file the window was opened from, where the tab order is saved
+    config_path: Rc<RefCell<Option<PathBuf>>>,
 }
 
 impl MainWindow {
     pub fn new(app: &Application, config: Config) -> Self {
-        let window = ApplicationWindow::builder()
+        #[cfg(feature = "libadwaita")]
+        let window = adw::ApplicationWindow::builder();
+        #[cfg(not(feature = "libadwaita"))]
+        let window = ApplicationWindow::builder();
+        let window = window
             .application(app)
             .title("About this Linux")
-            .default_width(600)
-            .default_height(400)
-            .resizable(false)
-            .build();
+            .default_width(MIN_WIDTH)
+            .default_height(MIN_HEIGHT)
+            .resizable(config.resizable)
+            .build()
+            .upcast::<ApplicationWindow>();
         
         // Set window icon using GTK4 method
         // Note: GTK4 handles window icons differently - typically through desktop files
@@ -28,105 +134,674 @@ impl MainWindow {
 
         // Let GTK follow system theme - don't set any theme preferences
 
-        let main_window = MainWindow { window, config };
+        system_info::configure(&config);
+        theme::apply_user_css(config.css_path.as_deref());
+        theme::apply_preset(&window, config.theme);
+        window.add_css_class(theme::FONT_CLASS);
+        theme::apply_font_family(&window, theme::FONT_CLASS, config.font_family.as_deref());
+        let registry = Rc::new(ProviderRegistry::from_config(&config));
+        let stack = Stack::new();
+
+        let main_window = MainWindow {
+            window,
+            stack,
+            config: Rc::new(RefCell::new(config)),
+            registry,
+            config_monitor: Rc::new(RefCell::new(None)),
+            toast: Toast::new(),
+            redetect: Rc::new(RefCell::new(None)),
+            live: Rc::new(RefCell::new(LiveWidgets::default())),
+            search_entry: gtk::SearchEntry::new(),
+            search_rows: Rc::new(RefCell::new(Vec::new())),
+            overview_box: Rc::new(RefCell::new(None)),
+            auto_refresh_pending: Rc::new(Cell::new(false)),
+            menu: gio::Menu::new(),
+            stack_switcher: StackSwitcher::new(),
+            config_path: Rc::new(RefCell::new(None)),
+        };
         main_window.build_ui();
+        main_window.remember_state();
+        main_window.watch_hotplug();
+        main_window.watch_power();
+        let live = main_window.live.clone();
+        updates::connect_pending(move |count| {
+            if let Some(badge) = &live.borrow().update_badge {
+                set_update_badge(badge, count);
+            }
+        });
+        main_window.schedule_auto_refresh();
         main_window
     }
 
+    /// A window that is never shown, only used to render the overview for
+    /// the configurator's preview from the config being edited
+    pub fn preview(config: Rc<RefCell<Config>>) -> Self {
+        MainWindow {
+            window: ApplicationWindow::builder()
+                .default_width(MIN_WIDTH)
+                .default_height(MIN_HEIGHT)
+                .build(),
+            stack: Stack::new(),
+            config,
+            registry: Rc::new(ProviderRegistry::new()),
+            config_monitor: Rc::new(RefCell::new(None)),
+            toast: Toast::new(),
+            redetect: Rc::new(RefCell::new(None)),
+            live: Rc::new(RefCell::new(LiveWidgets::default())),
+            search_entry: gtk::SearchEntry::new(),
+            search_rows: Rc::new(RefCell::new(Vec::new())),
+            overview_box: Rc::new(RefCell::new(None)),
+            auto_refresh_pending: Rc::new(Cell::new(false)),
+            menu: gio::Menu::new(),
+            stack_switcher: StackSwitcher::new(),
+            config_path: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    /// The overview page as it looks with the current config, for embedding
+    /// elsewhere; it takes no clicks or keyboard focus
+    pub fn overview_preview(&self) -> gtk::Widget {
+        self.search_rows.borrow_mut().clear();
+        let page = self.overview_page();
+        page.set_can_target(false);
+        page.set_can_focus(false);
+        page
+    }
+
     fn build_ui(&self) {
         // Create header bar
         let header_bar = HeaderBar::new();
         header_bar.set_show_title_buttons(true);
+        // A libadwaita window has no titlebar slot, the header bar is put
+        // above the content instead
+        #[cfg(not(feature = "libadwaita"))]
         self.window.set_titlebar(Some(&header_bar));
 
         // Create stack and stack switcher
-        let stack = Stack::new();
-        stack.set_transition_type(gtk::StackTransitionType::SlideLeftRight);
-        stack.set_transition_duration(500);
+        let stack = &self.stack;
+        #[cfg(not(feature = "libadwaita"))]
+        {
+            stack.set_transition_type(gtk::StackTransitionType::SlideLeftRight);
+            stack.set_transition_duration(500);
+        }
 
-        let stack_switcher = StackSwitcher::new();
-        stack_switcher.set_stack(Some(&stack));
-        header_bar.set_title_widget(Some(&stack_switcher));
+        let stack_switcher = &self.stack_switcher;
+        stack_switcher.set_stack(Some(stack));
+        #[cfg(feature = "libadwaita")]
+        stack_switcher.set_policy(adw::ViewSwitcherPolicy::Wide);
+        stack_switcher.update_property(&[gtk::accessible::Property::Label("Sections")]);
+        header_bar.set_title_widget(Some(stack_switcher));
+
+        let refresh = gio::SimpleAction::new("refresh", None);
+        let main_window = self.clone();
+        refresh.connect_activate(move |_, _| main_window.refresh_visible_page());
+        self.window.add_action(&refresh);
+        if let Some(app) = self.window.application() {
+            app.set_accels_for_action("win.refresh", &["F5", "<Control>r"]);
+        }
 
-        // Create overview tab
-        self.create_overview_tab(&stack);
-        
-        // Create other tabs
-        self.create_display_tab(&stack);
-        self.create_storage_tab(&stack);
-        self.create_support_tab(&stack);
-        self.create_service_tab(&stack);
+        self.search_entry.set_placeholder_text(Some("Search"));
+        self.search_entry.set_width_chars(14);
+        self.search_entry.update_property(&[gtk::accessible::Property::Label("Search all tabs")]);
+        let main_window = self.clone();
+        self.search_entry.connect_search_changed(move |_| main_window.apply_search(true));
+        header_bar.pack_end(&self.search_entry);
+
+        let refresh_button = Button::from_icon_name("view-refresh-symbolic");
+        refresh_button.set_tooltip_text(Some("Refresh this tab (F5)"));
+        refresh_button.update_property(&[gtk::accessible::Property::Label("Refresh")]);
+        refresh_button.set_action_name(Some("win.refresh"));
+        header_bar.pack_start(&refresh_button);
+
+        let copy_report = gio::SimpleAction::new("copy-report", None);
+        let config = self.config.clone();
+        let toast = self.toast.clone();
+        let window = self.window.clone();
+        copy_report.connect_activate(move |_, _| {
+            let window = window.clone();
+            let toast = toast.clone();
+            build_report(config.borrow().clone(), report::full_report, move |text| {
+                window.clipboard().set_text(&text);
+                toast.show("Report copied to clipboard");
+            });
+        });
+        self.window.add_action(&copy_report);
+
+        let save_image = gio::SimpleAction::new("save-image", None);
+        let main_window = self.clone();
+        save_image.connect_activate(move |_, _| main_window.show_save_image_dialog(false));
+        self.window.add_action(&save_image);
+
+        let save_page_image = gio::SimpleAction::new("save-page-image", None);
+        let main_window = self.clone();
+        save_page_image.connect_activate(move |_, _| main_window.show_save_image_dialog(true));
+        self.window.add_action(&save_page_image);
+
+        let export_pdf = gio::SimpleAction::new("export-pdf", None);
+        let main_window = self.clone();
+        export_pdf.connect_activate(move |_, _| main_window.show_export_pdf_dialog());
+        self.window.add_action(&export_pdf);
+
+        let share_probe = gio::SimpleAction::new("share-probe", None);
+        let window = self.window.clone();
+        share_probe.connect_activate(move |_, _| probe::share(window.upcast_ref()));
+        self.window.add_action(&share_probe);
+
+        let menu = &self.menu;
+        menu.append(Some("Copy full report"), Some("win.copy-report"));
+        menu.append(Some("Export report as PDF…"), Some("win.export-pdf"));
+        menu.append(Some("Save as image…"), Some("win.save-image"));
+        menu.append(Some("Save tab as image…"), Some("win.save-page-image"));
+        if probe::available() {
+            menu.append(Some("Share hardware probe…"), Some("win.share-probe"));
+        }
+        menu.append(Some("Detection log"), Some("app.detection-log"));
+        let menu_button = gtk::MenuButton::new();
+        menu_button.set_icon_name("open-menu-symbolic");
+        menu_button.update_property(&[gtk::accessible::Property::Label("Main menu")]);
+        menu_button.set_menu_model(Some(menu));
+        header_bar.pack_end(&menu_button);
+
+        // Indicate that expensive detectors are skipped while on battery
+        if system_info::reduced_refresh_mode() {
+            let battery_icon = Image::from_icon_name("battery-caution-symbolic");
+            battery_icon.set_tooltip_text(Some(
+                "Reduced-refresh mode: running on battery, SMART data is skipped.\n\
+                Start with --full-detection to query it anyway.",
+            ));
+            battery_icon.update_property(&[gtk::accessible::Property::Label("Reduced-refresh mode")]);
+            header_bar.pack_end(&battery_icon);
+        }
+
+        // Pages in the configured order, then any provider pages not listed
+        let mut tabs = self.config.borrow().tabs.clone();
+        for category in self.registry.categories() {
+            if !DEFAULT_TABS.contains(&category.as_str()) && !tabs.contains(&category) {
+                tabs.push(category);
+            }
+        }
+
+        for tab in &tabs {
+            // Without detection only what the config holds can be shown
+            let detected = ![CATEGORY_OVERVIEW, "support", "service"].contains(&tab.as_str());
+            if detected && system_info::detection_disabled() {
+                continue;
+            }
+            match tab.as_str() {
+                CATEGORY_OVERVIEW => self.create_overview_tab(stack),
+                CATEGORY_DISPLAY => self.create_display_tab(stack),
+                CATEGORY_STORAGE => self.create_storage_tab(stack),
+                "support" => self.create_support_tab(stack),
+                "service" => self.create_service_tab(stack),
+                // Without a battery there is only the adapter to show
+                CATEGORY_POWER if !power::has_battery() => {}
+                category => self.create_provider_tab(stack, category),
+            }
+        }
+
+        self.make_tabs_draggable();
+
+        let content = Box::new(Orientation::Vertical, 0);
+        if !self.config.borrow().validation_warnings.is_empty() {
+            content.append(&self.create_config_warning_banner());
+        }
+        stack.set_vexpand(true);
+        content.append(stack);
+
+        let overlay = gtk::Overlay::new();
+        overlay.set_child(Some(&content));
+        overlay.add_overlay(self.toast.widget());
+
+        // Like the macOS panel, the window can be dragged by any part of the
+        // content that doesn't handle clicks itself
+        let handle = gtk::WindowHandle::new();
+        handle.set_child(Some(&overlay));
+
+        #[cfg(feature = "libadwaita")]
+        {
+            let toolbar = Box::new(Orientation::Vertical, 0);
+            toolbar.append(&header_bar);
+            toolbar.append(&handle);
+            if let Some(window) = self.window.downcast_ref::<adw::ApplicationWindow>() {
+                window.set_content(Some(&toolbar));
+            }
+        }
+        #[cfg(not(feature = "libadwaita"))]
+        self.window.set_child(Some(&handle));
+        self.fit_to_content(&content);
+        self.install_shortcuts();
+
+        if self.config.borrow().resizable {
+            let main_window = self.clone();
+            self.window
+                .connect_default_width_notify(move |_| main_window.update_overview_layout());
+            self.update_overview_layout();
+        }
+    }
+
+    // Tab buttons can be dropped onto one another to reorder the pages. The
+    // switcher makes new buttons whenever pages are re-added, so this runs
+    // again after every move.
+    fn make_tabs_draggable(&self) {
+        let buttons = std::iter::successors(self.stack_switcher.first_child(), |child| child.next_sibling());
+        for (button, name) in buttons.zip(self.tab_order()) {
+            let drag = gtk::DragSource::new();
+            drag.set_actions(gtk::gdk::DragAction::MOVE);
+            let dragged = name.clone();
+            drag.connect_prepare(move |_, _, _| Some(gtk::gdk::ContentProvider::for_value(&dragged.to_value())));
+            button.add_controller(drag);
+
+            let drop = gtk::DropTarget::new(glib::Type::STRING, gtk::gdk::DragAction::MOVE);
+            let main_window = self.clone();
+            drop.connect_drop(move |_, value, _, _| {
+                let dragged = match value.get::<String>() {
+                    Ok(dragged) if dragged != name => dragged,
+                    _ => return false,
+                };
+                // The buttons are replaced by the move, so not from inside
+                // their own drop handler
+                let main_window = main_window.clone();
+                let target = name.clone();
+                glib::idle_add_local_once(move || main_window.move_tab(&dragged, &target));
+                true
+            });
+            button.add_controller(drop);
+        }
+    }
+
+    fn tab_order(&self) -> Vec<String> {
+        std::iter::successors(self.stack.first_child(), |child| child.next_sibling())
+            .filter_map(|child| self.stack.page(&child).name())
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    // Put tab `name` where `target` is now, then save the new order
+    fn move_tab(&self, name: &str, target: &str) {
+        let mut order = self.tab_order();
+        let (from, to) = match (
+            order.iter().position(|tab| tab == name),
+            order.iter().position(|tab| tab == target),
+        ) {
+            (Some(from), Some(to)) => (from, to),
+            _ => return,
+        };
+        let tab = order.remove(from);
+        order.insert(to, tab);
+
+        let visible = self.stack.visible_child_name();
+        let pages: Vec<(gtk::Widget, String, String)> = order
+            .iter()
+            .filter_map(|name| {
+                let child = self.stack.child_by_name(name)?;
+                let title = self.stack.page(&child).title().map(|title| title.to_string()).unwrap_or_default();
+                Some((child, name.clone(), title))
+            })
+            .collect();
+        for (child, _, _) in &pages {
+            self.stack.remove(child);
+        }
+        for (child, name, title) in &pages {
+            self.stack.add_titled(child, Some(name), title);
+        }
+        if let Some(visible) = visible {
+            self.stack.set_visible_child_name(&visible);
+        }
+        self.make_tabs_draggable();
+
+        self.config.borrow_mut().tabs = order.clone();
+        let config_path = match self.config_path.borrow().clone() {
+            Some(path) if path.exists() => path,
+            _ => return,
+        };
+        let backup_count = self.config.borrow().backup_count;
+        if let Err(e) = config::save_field(&config_path, "tabs", order.into(), backup_count) {
+            eprintln!("Failed to save the tab order: {:#}", e);
+        }
+    }
+
+    fn install_shortcuts(&self) {
+        // Capture phase so Ctrl+C reaches us before a focused label treats
+        // it as "copy the (empty) selection"
+        let controller = gtk::ShortcutController::new();
+        controller.set_propagation_phase(gtk::PropagationPhase::Capture);
+
+        let add = |trigger: &str, action: gtk::CallbackAction| {
+            controller.add_shortcut(gtk::Shortcut::new(
+                gtk::ShortcutTrigger::parse_string(trigger),
+                Some(action),
+            ));
+        };
+
+        // Bubble phase, so Escape first closes a menu or leaves the search
+        // entry and only closes the window when nothing else wants it
+        let closing = gtk::ShortcutController::new();
+        let window = self.window.clone();
+        closing.add_shortcut(gtk::Shortcut::new(
+            gtk::ShortcutTrigger::parse_string("<Control>w|Escape"),
+            Some(gtk::CallbackAction::new(move |_, _| {
+                window.close();
+                true
+            })),
+        ));
+        self.window.add_controller(closing);
+
+        // Ctrl+1 to Ctrl+9 for the tabs in config.tabs order, plus any
+        // custom provider tabs added after them
+        for index in 0..self.tab_order().len().min(9) {
+            let stack = self.stack.clone();
+            add(
+                &format!("<Control>{}", index + 1),
+                gtk::CallbackAction::new(move |_, _| {
+                    let page = std::iter::successors(stack.first_child(), |child| child.next_sibling())
+                        .nth(index);
+                    if let Some(page) = page {
+                        stack.set_visible_child(&page);
+                    }
+                    true
+                }),
+            );
+        }
+
+        let search_entry = self.search_entry.clone();
+        add(
+            "<Control>f",
+            gtk::CallbackAction::new(move |_, _| {
+                search_entry.grab_focus();
+                true
+            }),
+        );
+
+        let window = self.window.clone();
+        let toast = self.toast.clone();
+        add(
+            "<Control>c",
+            gtk::CallbackAction::new(move |_, _| {
+                // Whole value of a focused copyable label, unless part of it
+                // is selected, in which case the label copies the selection
+                match GtkWindowExt::focus(&window).and_downcast::<Label>() {
+                    Some(label) if label.has_css_class("atl-copyable") && label.selection_bounds().is_none() => {
+                        copy_value(&label, &toast);
+                        true
+                    }
+                    _ => false,
+                }
+            }),
+        );
+
+        self.window.add_controller(controller);
+    }
+
+    fn update_overview_layout(&self) {
+        // The macOS preset always has the logo on top, like the real panel
+        let stacked = self.config.borrow().theme == ThemePreset::Macos
+            || self.window.default_width() < STACKED_LAYOUT_WIDTH;
+        if let Some(main_box) = self.overview_box.borrow().as_ref() {
+            main_box.set_orientation(if stacked {
+                Orientation::Vertical
+            } else {
+                Orientation::Horizontal
+            });
+        }
+    }
+
+    // Size the window from the built pages so large logos and long custom
+    // rows aren't clipped, without going below the classic 600x400 or
+    // beyond the screen
+    fn fit_to_content(&self, content: &Box) {
+        let (_, natural_width, _, _) = content.measure(Orientation::Horizontal, -1);
+        let (_, natural_height, _, _) = content.measure(Orientation::Vertical, natural_width);
+
+        let mut width = natural_width.max(MIN_WIDTH);
+        let mut height = natural_height.max(MIN_HEIGHT);
+
+        let monitors = WidgetExt::display(&self.window).monitors();
+        let largest = (0..monitors.n_items())
+            .filter_map(|i| monitors.item(i).and_downcast::<gtk::gdk::Monitor>())
+            .map(|monitor| monitor.geometry())
+            .max_by_key(|geometry| geometry.width() * geometry.height());
+        if let Some(geometry) = largest {
+            width = width.min(geometry.width() * 9 / 10);
+            height = height.min(geometry.height() * 9 / 10);
+        }
+
+        self.window.set_default_size(width, height);
+    }
 
-        self.window.set_child(Some(&stack));
+    // Bold name and copyable value, centered as one overview row
+    fn overview_row(&self, field_name: &str, field_value: &str) -> (Box, Label) {
+        let value_label = Label::new(Some(field_value));
+        self.make_copyable(&value_label);
+        (self.overview_row_with(field_name, &value_label), value_label)
+    }
+
+    fn overview_row_with(&self, field_name: &str, value_label: &Label) -> Box {
+        let field_box = Box::new(Orientation::Horizontal, 20);
+        field_box.set_halign(gtk::Align::Center);
+
+        let name_label = Label::new(None);
+        name_label.set_markup(&format!("<b>{}</b>", escape_markup(field_name)));
+        name_label.add_css_class("atl-field-name");
+        name_label.set_halign(gtk::Align::Start);
+        field_box.append(&name_label);
+
+        value_label.add_css_class("atl-field-value");
+        value_label.set_halign(gtk::Align::Start);
+        // Wrap long CPU and GPU strings rather than clipping them when the
+        // window can be narrowed
+        if self.config.borrow().resizable {
+            value_label.set_wrap(true);
+            value_label.set_wrap_mode(gtk::pango::WrapMode::WordChar);
+            value_label.set_xalign(0.0);
+        }
+        field_box.append(value_label);
+        describe_field(&name_label, value_label);
+
+        field_box
+    }
+
+    // Privacy mode shows a placeholder until the label is clicked; only then
+    // is the value filled in and copyable
+    fn make_revealable(&self, label: &Label, reveal: impl Fn(&Label) + 'static) {
+        label.set_text(privacy::MASK);
+        label.add_css_class("atl-masked");
+        label.set_tooltip_text(Some("Hidden for privacy, click to reveal"));
+
+        let click = gtk::GestureClick::new();
+        click.set_button(gtk::gdk::BUTTON_PRIMARY);
+        let main_window = self.clone();
+        click.connect_released(move |gesture, _, _, _| {
+            if let Ok(label) = gesture.widget().downcast::<Label>() {
+                label.remove_controller(gesture);
+                label.remove_css_class("atl-masked");
+                label.set_tooltip_text(None);
+                reveal(&label);
+                main_window.make_copyable(&label);
+            }
+        });
+        label.add_controller(click);
+    }
+
+    // Values can be selected, and copied whole with a plain click or from the
+    // context menu, for pasting into bug reports
+    fn make_copyable(&self, label: &Label) {
+        label.set_selectable(true);
+        label.add_css_class("atl-copyable");
+        if label.tooltip_text().is_none() {
+            label.set_tooltip_text(Some("Click to copy"));
+        }
+
+        let toast = self.toast.clone();
+        let copy = move |label: &Label| copy_value(label, &toast);
+
+        // Capture phase so the label's own selection handling still runs
+        let click = gtk::GestureClick::new();
+        click.set_button(gtk::gdk::BUTTON_PRIMARY);
+        click.set_propagation_phase(gtk::PropagationPhase::Capture);
+        let click_copy = copy.clone();
+        click.connect_released(move |gesture, n_press, _, _| {
+            if let Ok(label) = gesture.widget().downcast::<Label>() {
+                // Drags and double clicks are left to select part of the text
+                if n_press == 1 && label.selection_bounds().is_none() {
+                    click_copy(&label);
+                }
+            }
+        });
+        label.add_controller(click);
+
+        let copy_action = gio::SimpleAction::new("copy", None);
+        let weak_label = label.downgrade();
+        copy_action.connect_activate(move |_, _| {
+            if let Some(label) = weak_label.upgrade() {
+                copy(&label);
+            }
+        });
+        let actions = gio::SimpleActionGroup::new();
+        actions.add_action(&copy_action);
+        label.insert_action_group("value", Some(&actions));
+
+        let menu = gio::Menu::new();
+        menu.append(Some("Copy Value"), Some("value.copy"));
+        label.set_extra_menu(Some(&menu));
+    }
+
+    // Non-modal notice listing what validate() had to fix up or ignore
+    fn create_config_warning_banner(&self) -> gtk::InfoBar {
+        let banner = gtk::InfoBar::new();
+        banner.set_message_type(gtk::MessageType::Warning);
+        banner.set_show_close_button(true);
+
+        let mut text = String::from("Some configuration values were adjusted:");
+        for warning in &self.config.borrow().validation_warnings {
+            text.push_str(&format!("\n• {}", warning));
+        }
+
+        let label = Label::new(Some(&text));
+        label.set_halign(gtk::Align::Start);
+        label.set_wrap(true);
+        banner.add_child(&label);
+
+        banner.connect_response(|banner, response| {
+            if response == gtk::ResponseType::Close {
+                banner.set_revealed(false);
+            }
+        });
+
+        banner
     }
 
     fn create_overview_tab(&self, stack: &Stack) {
+        stack.add_titled(&page_slot(&self.overview_page()), Some(CATEGORY_OVERVIEW), "Overview");
+    }
+
+    fn is_compact(&self) -> bool {
+        self.config.borrow().compact || COMPACT_REQUESTED.load(Ordering::Relaxed)
+    }
+
+    fn page_margin(&self) -> i32 {
+        if self.is_compact() {
+            COMPACT_PAGE_MARGIN
+        } else {
+            PAGE_MARGIN
+        }
+    }
+
+    // In compact mode the overview scrolls instead of growing the window
+    fn overview_page(&self) -> gtk::Widget {
+        let page = self.build_overview_page();
+        if !self.is_compact() {
+            return page.upcast();
+        }
+
+        let scrolled = gtk::ScrolledWindow::new();
+        scrolled.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+        scrolled.set_propagate_natural_width(true);
+        scrolled.set_child(Some(&page));
+        scrolled.upcast()
+    }
+
+    fn build_overview_page(&self) -> Box {
+        let config = self.config.borrow();
+        let compact = self.is_compact();
+        let scaled = |value: i32| {
+            if compact {
+                (value as f64 * COMPACT_SCALE) as i32
+            } else {
+                value
+            }
+        };
+        let logo_size = config.distro_image_size.map(scaled);
+        let margins = config.overview_margins.map(scaled);
+
         // Main horizontal box with centering
         let center_box = Box::new(Orientation::Vertical, 0);
+        center_box.add_css_class("atl-overview");
         center_box.set_halign(gtk::Align::Center);
         center_box.set_valign(gtk::Align::Center);
         center_box.set_hexpand(true);
         center_box.set_vexpand(true);
         
-        let main_box = Box::new(Orientation::Horizontal, self.config.logo_space);
-        main_box.set_margin_start(self.config.overview_margins[0]);
-        main_box.set_margin_end(self.config.overview_margins[1]);
-        main_box.set_margin_top(self.config.overview_margins[2]);
-        main_box.set_margin_bottom(self.config.overview_margins[3]);
+        let main_box = Box::new(Orientation::Horizontal, scaled(config.logo_space));
+        main_box.set_margin_start(margins[0]);
+        main_box.set_margin_end(margins[1]);
+        main_box.set_margin_top(margins[2]);
+        main_box.set_margin_bottom(margins[3]);
         main_box.set_halign(gtk::Align::Center);
         main_box.set_valign(gtk::Align::Center);
+        *self.overview_box.borrow_mut() = Some(main_box.clone());
+        if config.resizable || config.theme == ThemePreset::Macos {
+            self.update_overview_layout();
+        }
 
         // Create distro image with fallback logic
         let mut image_loaded = false;
+        // Loaded at device pixels and shown at logical size, so logos stay
+        // sharp on HiDPI screens
+        let scale = utils::max_scale_factor();
+        let load_size = logo_size.map(|size| size * scale);
         
         // Try to load the configured image first
-        if let Ok(pixbuf) = Pixbuf::from_file_at_scale(
-            &self.config.distro_image_path,
-            self.config.distro_image_size[0],
-            self.config.distro_image_size[1],
-            true,
-        ) {
-            let image = Image::from_pixbuf(Some(&pixbuf));
+        let logo = svg_logo_image(&config.distro_image_path, logo_size).or_else(|| {
+            Pixbuf::from_file_at_scale(&config.distro_image_path, load_size[0], load_size[1], true)
+                .ok()
+                .map(|pixbuf| logo_image(&pixbuf, scale))
+        });
+        if let Some(image) = logo {
+            image.add_css_class("atl-logo");
+            image.update_property(&[gtk::accessible::Property::Label("Distribution logo")]);
             image.set_valign(gtk::Align::Start);
+            if let Some(tux) = load_tux_logo(load_size) {
+                add_logo_easter_egg(&image, tux);
+            }
+            self.accept_logo_drops(&image);
             main_box.append(&image);
             image_loaded = true;
         }
         
-        // If that fails, try tux-logo.png as fallback
+        // If that fails, use the bundled Tux logo
         if !image_loaded {
-            let current_dir_path = format!("{}/tux-logo.png", std::env::current_dir().unwrap_or_default().to_string_lossy());
-            let tux_paths = vec![
-                "tux-logo.png",
-                "./tux-logo.png",
-                &current_dir_path,
-            ];
-            
-            for tux_path in tux_paths {
-                if let Ok(pixbuf) = Pixbuf::from_file_at_scale(
-                    tux_path,
-                    self.config.distro_image_size[0],
-                    self.config.distro_image_size[1],
-                    true,
-                ) {
-                    let image = Image::from_pixbuf(Some(&pixbuf));
-                    image.set_valign(gtk::Align::Start);
-                    main_box.append(&image);
-                    image_loaded = true;
-                    break;
-                }
+            if let Some(pixbuf) = load_tux_logo(load_size) {
+                let image = logo_image(&pixbuf, scale);
+                image.add_css_class("atl-logo");
+                image.update_property(&[gtk::accessible::Property::Label("Tux logo")]);
+                image.set_valign(gtk::Align::Start);
+                self.accept_logo_drops(&image);
+                main_box.append(&image);
+                image_loaded = true;
             }
         }
         
         // If still no image, show a placeholder
         if !image_loaded {
             let placeholder = Label::new(Some("🐧\nTux\nLogo"));
+            placeholder.add_css_class("atl-logo");
             placeholder.set_valign(gtk::Align::Start);
             placeholder.set_halign(gtk::Align::Center);
             main_box.append(&placeholder);
         }
 
         // Create info column
-        let info_vbox = Box::new(Orientation::Vertical, self.config.section_space);
+        let info_vbox = Box::new(Orientation::Vertical, scaled(config.section_space));
         info_vbox.set_valign(gtk::Align::Center);
         info_vbox.set_halign(gtk::Align::Center);
 
@@ -135,25 +810,44 @@ impl MainWindow {
         distro_info_box.set_halign(gtk::Align::Center);
 
         // Get dynamic distro information
-        let dynamic_info = DynamicSystemInfo::detect().unwrap_or_else(|_| DynamicSystemInfo {
-            distro_name: "Unknown Linux".to_string(),
-            distro_version: "Unknown".to_string(),
-            distro_codename: None,
-            kernel: "Unknown".to_string(),
-        });
+        let dynamic_info = DynamicSystemInfo::for_config(&config);
 
         let distro_name = Label::new(None);
         distro_name.set_markup(&dynamic_info.get_distro_markup());
+        distro_name.add_css_class("atl-distro-name");
         distro_name.set_halign(gtk::Align::Center);
         distro_info_box.append(&distro_name);
 
         let distro_ver = Label::new(Some(&dynamic_info.distro_version));
+        distro_ver.add_css_class("atl-distro-version");
         distro_ver.set_halign(gtk::Align::Center);
+        distro_ver.set_justify(gtk::Justification::Center);
+        // As on macOS, clicking the version toggles the build behind it
+        if !system_info::detection_disabled() {
+            distro_ver.set_tooltip_text(Some("Click for build details"));
+            let pretty_version = dynamic_info.distro_version.clone();
+            let showing_build = Cell::new(false);
+            let click = gtk::GestureClick::new();
+            click.connect_released(move |gesture, _, _, _| {
+                if let Ok(label) = gesture.widget().downcast::<Label>() {
+                    if showing_build.replace(!showing_build.get()) {
+                        label.set_text(&pretty_version);
+                    } else {
+                        label.set_text(&system_info::build_details());
+                    }
+                }
+            });
+            distro_ver.add_controller(click);
+        }
+        distro_ver.set_visible(!dynamic_info.distro_version.is_empty());
         distro_info_box.append(&distro_ver);
 
         // Add kernel version
         let kernel_label = Label::new(Some(&format!("Kernel {}", dynamic_info.kernel)));
+        kernel_label.add_css_class("atl-kernel");
         kernel_label.set_halign(gtk::Align::Center);
+        kernel_label.set_visible(!dynamic_info.kernel.is_empty());
+        self.make_copyable(&kernel_label);
         distro_info_box.append(&kernel_label);
 
         info_vbox.append(&distro_info_box);
@@ -164,76 +858,167 @@ impl MainWindow {
 
         // Hostname
         let hostname_label = Label::new(None);
-        hostname_label.set_markup(&format!("<b>{}</b>", &self.config.hostname));
+        hostname_label.add_css_class("atl-hostname");
         hostname_label.set_halign(gtk::Align::Center);
-        system_info_box.append(&hostname_label);
+        let hostname_markup = format!("<b>{}</b>", escape_markup(&config.hostname));
+        if privacy::hides_hostname(&config) && !config.hostname.is_empty() {
+            self.make_revealable(&hostname_label, move |label| label.set_markup(&hostname_markup));
+        } else {
+            hostname_label.set_markup(&hostname_markup);
+            if let Some(error) = config.detection_errors.get("hostname") {
+                hostname_label.set_tooltip_text(Some(error));
+            }
+            self.make_copyable(&hostname_label);
+        }
+        // A laptop, desktop or VM icon in front, after the chassis hostnamed reports
+        if system_info::detection_disabled() {
+            system_info_box.append(&hostname_label);
+        } else {
+            let hostname_box = Box::new(Orientation::Horizontal, 6);
+            hostname_box.set_halign(gtk::Align::Center);
+            let chassis = Image::from_gicon(&hostnamed::chassis_icon());
+            chassis.add_css_class("atl-chassis-icon");
+            hostname_box.append(&chassis);
+            hostname_box.append(&hostname_label);
+            system_info_box.append(&hostname_box);
+        }
 
         // System info fields
         let info_fields = vec![
-            ("Processor", &self.config.cpu),
-            ("Memory", &self.config.memory),
-            ("Startup Disk", &self.config.startup_disk),
-            ("Graphics", &self.config.graphics),
-            ("Serial Number", &self.config.serial_num),
+            ("Processor", "cpu", &config.cpu),
+            ("Memory", "memory", &config.memory),
+            ("Startup Disk", "startup_disk", &config.startup_disk),
+            ("Graphics", "graphics", &config.graphics),
+            ("Serial Number", "serial_num", &config.serial_num),
         ];
 
-        for (field_name, field_value) in info_fields {
-            let field_box = Box::new(Orientation::Horizontal, 20);
-            field_box.set_halign(gtk::Align::Center);
-
-            let name_label = Label::new(None);
-            name_label.set_markup(&format!("<b>{}</b>", field_name));
-            name_label.set_halign(gtk::Align::Start);
-            field_box.append(&name_label);
-
-            let value_label = Label::new(Some(field_value));
-            value_label.set_halign(gtk::Align::Start);
-            field_box.append(&value_label);
+        // User-defined rows never have detection errors, so give them an empty key
+        let custom_fields = config
+            .custom_fields
+            .iter()
+            .map(|(name, value)| (name.as_str(), "", value));
+
+        for (field_name, field_key, field_value) in info_fields.into_iter().chain(custom_fields) {
+            let hidden = field_key == "serial_num" && privacy::hides_serial(&config) && !field_value.is_empty();
+            let field_box = if hidden {
+                let value_label = Label::new(None);
+                let value = field_value.clone();
+                self.make_revealable(&value_label, move |label| label.set_text(&value));
+                let field_box = self.overview_row_with(field_name, &value_label);
+                // Searching for the serial would give away what it starts with
+                self.add_search_row(CATEGORY_OVERVIEW, &field_box, &[field_name]);
+                field_box
+            } else {
+                let (field_box, value_label) = self.overview_row(field_name, field_value);
+                self.add_search_row(CATEGORY_OVERVIEW, &field_box, &[field_name, field_value]);
+                if field_key == "cpu" && !system_info::detection_disabled() {
+                    self.open_cpu_details_on_click(&field_box, &value_label);
+                }
+                field_box
+            };
+            if let Some(error) = config.detection_errors.get(field_key) {
+                field_box.append(&detection_error_icon(error));
+            }
+            system_info_box.append(&field_box);
+        }
 
+        // Kept up to date by the auto-refresh
+        let mut live = self.live.borrow_mut();
+        live.uptime = system_info::uptime().filter(|_| config.show_uptime).map(|uptime| {
+            let (field_box, value_label) = self.overview_row("Uptime", &units::format_duration(uptime));
             system_info_box.append(&field_box);
+            value_label
+        });
+
+        let session_fields = [
+            ("Desktop Environment", config.show_desktop_environment, system_info::desktop_environment as fn() -> Option<String>),
+            ("Window Manager", config.show_window_manager, system_info::window_manager),
+            ("Session", config.show_session, || session::detect().ok().map(|session| session.summary())),
+            ("Language & Region", config.show_region, || region::detect().ok().map(|region| region.summary())),
+        ];
+        for (field_name, shown, detect) in session_fields {
+            if !shown {
+                continue;
+            }
+            if let Some(value) = detect() {
+                let (field_box, _) = self.overview_row(field_name, &value);
+                self.add_search_row(CATEGORY_OVERVIEW, &field_box, &[field_name, &value]);
+                system_info_box.append(&field_box);
+            }
         }
+        live.memory_usage = system_info::memory_usage().map(|(used, total)| {
+            let (field_box, value_label) = self.overview_row("Memory Used", &report::memory_usage_text(used, total));
+            system_info_box.append(&field_box);
+            value_label
+        });
+        live.battery = config.show_battery.then(power::battery_summary).flatten().map(|battery| {
+            let (field_box, value_label) = self.overview_row("Battery", &battery);
+            system_info_box.append(&field_box);
+            value_label
+        });
+        drop(live);
 
         info_vbox.append(&system_info_box);
 
         // Buttons section
         let buttons_box = Box::new(Orientation::Horizontal, 10);
+        buttons_box.add_css_class("atl-buttons");
         buttons_box.set_halign(gtk::Align::Center);
 
         let system_report_btn = Button::with_label("System Report...");
+        system_report_btn.add_css_class("atl-button");
         let software_update_btn = Button::with_label("Software Update...");
+        software_update_btn.add_css_class("atl-button");
 
         // Add button callbacks if commands are configured
-        if !self.config.system_info_command.is_empty() {
-            let cmd = self.config.system_info_command.clone();
+        if !config.system_info_command.is_empty() {
+            let cmd = config.system_info_command.clone();
             system_report_btn.connect_clicked(move |_| {
-                let _ = std::process::Command::new("sh")
+                let _ = host::command("sh")
                     .arg("-c")
                     .arg(&cmd)
                     .spawn();
             });
         }
 
-        if !self.config.software_update_command.is_empty() {
-            let cmd = self.config.software_update_command.clone();
+        if !config.software_update_command.is_empty() {
+            let cmd = config.software_update_command.clone();
             software_update_btn.connect_clicked(move |_| {
-                let _ = std::process::Command::new("sh")
+                let _ = host::command("sh")
                     .arg("-c")
                     .arg(&cmd)
                     .spawn();
             });
+        } else {
+            software_update_btn.connect_clicked(builtin_software_update);
         }
 
         buttons_box.append(&system_report_btn);
-        buttons_box.append(&software_update_btn);
+        let badge = Label::new(None);
+        badge.add_css_class("atl-badge");
+        badge.set_halign(gtk::Align::End);
+        badge.set_valign(gtk::Align::Start);
+        badge.set_can_target(false);
+        set_update_badge(&badge, updates::pending().unwrap_or(0));
+        let software_update = gtk::Overlay::new();
+        software_update.set_child(Some(&software_update_btn));
+        software_update.add_overlay(&badge);
+        buttons_box.append(&software_update);
+        self.live.borrow_mut().update_badge = Some(badge);
 
         info_vbox.append(&buttons_box);
         main_box.append(&info_vbox);
         
         center_box.append(&main_box);
-        stack.add_titled(&center_box, Some("overview"), "Overview");
+        center_box
     }
 
     fn create_display_tab(&self, stack: &Stack) {
+        let page = self.build_display_page(DisplayInfo::detect());
+        stack.add_titled(&page_slot(&page), Some(CATEGORY_DISPLAY), "Display");
+    }
+
+    fn build_display_page(&self, detected: anyhow::Result<DisplayInfo>) -> gtk::ScrolledWindow {
         let center_wrapper = Box::new(Orientation::Vertical, 0);
         center_wrapper.set_halign(gtk::Align::Center);
         center_wrapper.set_valign(gtk::Align::Center);
@@ -241,13 +1026,12 @@ impl MainWindow {
         center_wrapper.set_vexpand(true);
         
         let main_display_box = Box::new(Orientation::Vertical, 20);
-        main_display_box.set_margin_start(40);
-        main_display_box.set_margin_end(40);
-        main_display_box.set_margin_top(40);
-        main_display_box.set_margin_bottom(40);
+        main_display_box.set_margin_start(self.page_margin());
+        main_display_box.set_margin_end(self.page_margin());
+        main_display_box.set_margin_top(self.page_margin());
+        main_display_box.set_margin_bottom(self.page_margin());
 
-        // Get display information
-        match DisplayInfo::detect() {
+        match detected {
             Ok(display_info) => {
                 if display_info.displays.is_empty() {
                     let no_displays_label = Label::new(Some("No displays detected"));
@@ -304,8 +1088,11 @@ impl MainWindow {
 
                                 let value_label = Label::new(Some(prop_value));
                                 value_label.set_halign(gtk::Align::Start);
+                                self.make_copyable(&value_label);
                                 prop_box.append(&value_label);
+                                describe_field(&name_label, &value_label);
 
+                                self.add_search_row(CATEGORY_DISPLAY, &prop_box, &[&display_name, prop_name, prop_value]);
                                 display_box.append(&prop_box);
                             }
                         }
@@ -334,11 +1121,15 @@ impl MainWindow {
         let scrolled = gtk::ScrolledWindow::new();
         scrolled.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
         scrolled.set_child(Some(&center_wrapper));
-        
-        stack.add_titled(&scrolled, Some("display"), "Display");
+        scrolled
     }
 
     fn create_storage_tab(&self, stack: &Stack) {
+        let page = self.build_storage_page(StorageInfo::detect());
+        stack.add_titled(&page_slot(&page), Some(CATEGORY_STORAGE), "Storage");
+    }
+
+    fn build_storage_page(&self, detected: anyhow::Result<StorageInfo>) -> gtk::ScrolledWindow {
         let center_wrapper = Box::new(Orientation::Vertical, 0);
         center_wrapper.set_halign(gtk::Align::Center);
         center_wrapper.set_valign(gtk::Align::Center);
@@ -346,13 +1137,18 @@ impl MainWindow {
         center_wrapper.set_vexpand(true);
         
         let main_storage_box = Box::new(Orientation::Vertical, 20);
-        main_storage_box.set_margin_start(40);
-        main_storage_box.set_margin_end(40);
-        main_storage_box.set_margin_top(40);
-        main_storage_box.set_margin_bottom(40);
+        main_storage_box.set_margin_start(self.page_margin());
+        main_storage_box.set_margin_end(self.page_margin());
+        main_storage_box.set_margin_top(self.page_margin());
+        main_storage_box.set_margin_bottom(self.page_margin());
+
+        {
+            let mut live = self.live.borrow_mut();
+            live.volumes.clear();
+            live.temperatures.clear();
+        }
 
-        // Get storage information
-        match StorageInfo::detect() {
+        match detected {
             Ok(storage_info) => {
                 // Title
                 let title = Label::new(None);
@@ -361,6 +1157,15 @@ impl MainWindow {
                 title.set_margin_bottom(20);
                 main_storage_box.append(&title);
 
+                if system_info::reduced_refresh_mode() {
+                    let battery_label = Label::new(Some(
+                        "Running on battery: temperature and health checks are skipped",
+                    ));
+                    battery_label.set_halign(gtk::Align::Start);
+                    battery_label.add_css_class("dim-label");
+                    main_storage_box.append(&battery_label);
+                }
+
                 // Storage devices section
                 if !storage_info.devices.is_empty() {
                     let devices_title = Label::new(None);
@@ -402,14 +1207,17 @@ impl MainWindow {
 
                                 let value_label = Label::new(Some(prop_value));
                                 value_label.set_halign(gtk::Align::Start);
+                                self.make_copyable(&value_label);
                                 prop_box.append(&value_label);
+                                describe_field(&name_label, &value_label);
 
+                                self.add_search_row(CATEGORY_STORAGE, &prop_box, &[&device.name, prop_name, prop_value]);
                                 device_box.append(&prop_box);
                             }
                         }
 
-                        // Temperature and health if available
-                        if let Some(ref temp) = device.temperature {
+                        // Temperature and health, or why they are unavailable
+                        {
                             let temp_box = Box::new(Orientation::Horizontal, 10);
                             temp_box.set_halign(gtk::Align::Start);
                             temp_box.set_margin_start(20);
@@ -419,14 +1227,28 @@ impl MainWindow {
                             temp_name.set_size_request(80, -1);
                             temp_box.append(&temp_name);
 
-                            let temp_value = Label::new(Some(temp));
-                            temp_value.set_halign(gtk::Align::Start);
-                            temp_box.append(&temp_value);
+                            match device.temperature {
+                                Ok(ref temp) => {
+                                    let temp_value = Label::new(Some(temp));
+                                    temp_value.set_halign(gtk::Align::Start);
+                                    self.make_copyable(&temp_value);
+                                    self.live
+                                        .borrow_mut()
+                                        .temperatures
+                                        .push((device.name.clone(), temp_value.clone()));
+                                    temp_box.append(&temp_value);
+                                    describe_field(&temp_name, &temp_value);
+                                }
+                                Err(ref e) => {
+                                    temp_box.append(&Label::new(Some("Unavailable")));
+                                    temp_box.append(&detection_error_icon(&e.to_string()));
+                                }
+                            }
 
                             device_box.append(&temp_box);
                         }
 
-                        if let Some(ref health) = device.health {
+                        {
                             let health_box = Box::new(Orientation::Horizontal, 10);
                             health_box.set_halign(gtk::Align::Start);
                             health_box.set_margin_start(20);
@@ -436,9 +1258,20 @@ impl MainWindow {
                             health_name.set_size_request(80, -1);
                             health_box.append(&health_name);
 
-                            let health_value = Label::new(Some(health));
-                            health_value.set_halign(gtk::Align::Start);
-                            health_box.append(&health_value);
+                            match device.health {
+                                Ok(ref health) => {
+                                    let health_value = status_widget(Status::from_smart_health(health), health);
+                                    health_value.update_relation(&[gtk::accessible::Relation::LabelledBy(&[
+                                        health_name.upcast_ref(),
+                                    ])]);
+                                    health_value.set_halign(gtk::Align::Start);
+                                    health_box.append(&health_value);
+                                }
+                                Err(ref e) => {
+                                    health_box.append(&Label::new(Some("Unavailable")));
+                                    health_box.append(&detection_error_icon(&e.to_string()));
+                                }
+                            }
 
                             device_box.append(&health_box);
                         }
@@ -447,6 +1280,53 @@ impl MainWindow {
                     }
                 }
 
+                // Mounted volumes with usage bars
+                if !storage_info.filesystems.is_empty() {
+                    let volumes_title = Label::new(None);
+                    volumes_title.set_markup("<b>Volumes</b>");
+                    volumes_title.set_halign(gtk::Align::Start);
+                    volumes_title.set_margin_bottom(10);
+                    main_storage_box.append(&volumes_title);
+
+                    for filesystem in &storage_info.filesystems {
+                        let volume_box = Box::new(Orientation::Horizontal, 10);
+                        volume_box.set_halign(gtk::Align::Start);
+                        volume_box.set_margin_start(20);
+
+                        let mount_label = Label::new(Some(&filesystem.mountpoint));
+                        mount_label.set_halign(gtk::Align::Start);
+                        mount_label.set_size_request(120, -1);
+                        mount_label.set_tooltip_text(Some(&format!(
+                            "{} ({})",
+                            filesystem.device, filesystem.filesystem_type
+                        )));
+                        volume_box.append(&mount_label);
+
+                        let bar = usage_bar(filesystem.usage_percent);
+                        bar.update_relation(&[gtk::accessible::Relation::LabelledBy(&[mount_label.upcast_ref()])]);
+                        volume_box.append(&bar);
+
+                        let usage_label = Label::new(Some(&report::volume_usage_text(filesystem)));
+                        usage_label.set_halign(gtk::Align::Start);
+                        self.make_copyable(&usage_label);
+                        volume_box.append(&usage_label);
+                        describe_field(&mount_label, &usage_label);
+
+                        self.live.borrow_mut().volumes.push((
+                            filesystem.mountpoint.clone(),
+                            bar,
+                            usage_label,
+                        ));
+
+                        self.add_search_row(
+                            CATEGORY_STORAGE,
+                            &volume_box,
+                            &[&filesystem.mountpoint, &filesystem.device, &filesystem.filesystem_type],
+                        );
+                        main_storage_box.append(&volume_box);
+                    }
+                }
+
                 if storage_info.devices.is_empty() {
                     let no_storage_label = Label::new(Some("No storage devices detected"));
                     no_storage_label.set_halign(gtk::Align::Center);
@@ -465,8 +1345,7 @@ impl MainWindow {
         let scrolled = gtk::ScrolledWindow::new();
         scrolled.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
         scrolled.set_child(Some(&center_wrapper));
-
-        stack.add_titled(&scrolled, Some("storage"), "Storage");
+        scrolled
     }
 
     fn create_support_tab(&self, stack: &Stack) {
@@ -504,27 +1383,28 @@ impl MainWindow {
             ORIGINAL_REPO, ORIGINAL_AUTHOR, CURRENT_REPO
         ));
         attribution_label.set_halign(gtk::Align::Center);
+        self.handle_links(&attribution_label);
         support_box.append(&attribution_label);
 
         stack.add_titled(&support_box, Some("support"), "Support");
     }
 
+    fn handle_links(&self, label: &Label) {
+        let toast = self.toast.clone();
+        utils::handle_links(label, move || toast.show("Link copied to clipboard"));
+    }
+
     fn create_service_tab(&self, stack: &Stack) {
         let main_service_box = Box::new(Orientation::Vertical, 25);
-        main_service_box.set_margin_start(40);
-        main_service_box.set_margin_end(40);
-        main_service_box.set_margin_top(40);
-        main_service_box.set_margin_bottom(40);
+        main_service_box.set_margin_start(self.page_margin());
+        main_service_box.set_margin_end(self.page_margin());
+        main_service_box.set_margin_top(self.page_margin());
+        main_service_box.set_margin_bottom(self.page_margin());
         main_service_box.set_halign(gtk::Align::Center);
         main_service_box.set_valign(gtk::Align::Center);
 
         // Get dynamic distro information for service links
-        let dynamic_info = DynamicSystemInfo::detect().unwrap_or_else(|_| DynamicSystemInfo {
-            distro_name: "Unknown Linux".to_string(),
-            distro_version: "Unknown".to_string(),
-            distro_codename: None,
-            kernel: "Unknown".to_string(),
-        });
+        let dynamic_info = DynamicSystemInfo::for_config(&self.config.borrow());
 
         // Title
         let title = Label::new(None);
@@ -533,8 +1413,38 @@ impl MainWindow {
         title.set_margin_bottom(20);
         main_service_box.append(&title);
 
+        // Only opened on a click, since it sends the serial number to the vendor
+        if let Some(warranty) = warranty::link() {
+            let section_label = Label::new(None);
+            section_label.set_markup("<b>Warranty</b>");
+            section_label.set_halign(gtk::Align::Start);
+            section_label.set_margin_bottom(10);
+            main_service_box.append(&section_label);
+
+            let warranty_box = Box::new(Orientation::Vertical, 8);
+            warranty_box.set_margin_start(20);
+            warranty_box.set_margin_bottom(15);
+
+            let warranty_btn = Button::with_label("Check Warranty...");
+            warranty_btn.set_halign(gtk::Align::Start);
+            warranty_btn.set_tooltip_text(Some(&format!(
+                "Opens {}'s warranty page with this computer's serial number",
+                warranty.vendor
+            )));
+            warranty_btn.connect_clicked(move |button| utils::open_uri(button, &warranty.url));
+            warranty_box.append(&warranty_btn);
+
+            let note_label = Label::new(Some(&format!("Your serial number is sent to {} when you click.", warranty.vendor)));
+            note_label.add_css_class("dim-label");
+            note_label.set_halign(gtk::Align::Start);
+            warranty_box.append(&note_label);
+
+            main_service_box.append(&warranty_box);
+        }
+
         // Get distribution-specific links
-        let service_links = get_distro_service_links(&dynamic_info.distro_name);
+        let sys_vendor = system_info::system_vendor();
+        let service_links = get_distro_service_links(&dynamic_info.distro_name, sys_vendor.as_deref());
 
         // Create sections for different types of links
         let sections = vec![
@@ -542,6 +1452,7 @@ impl MainWindow {
             ("Community &amp; Forums", &service_links.community),
             ("Bug Reports &amp; Issues", &service_links.bug_reports),
             ("Download &amp; Updates", &service_links.downloads),
+            ("Hardware Support", &service_links.hardware),
         ];
 
         for (section_title, links) in sections {
@@ -562,6 +1473,7 @@ impl MainWindow {
                     let link_label = Label::new(None);
                     link_label.set_markup(&format!("• <a href='{}'>{}</a>", url, link_name));
                     link_label.set_halign(gtk::Align::Start);
+                    self.handle_links(&link_label);
                     links_box.append(&link_label);
                 }
 
@@ -592,6 +1504,7 @@ impl MainWindow {
                 let link_label = Label::new(None);
                 link_label.set_markup(&format!("• <a href='{}'>{}</a>", url, name));
                 link_label.set_halign(gtk::Align::Start);
+                self.handle_links(&link_label);
                 general_box.append(&link_label);
             }
 
@@ -605,8 +1518,869 @@ impl MainWindow {
         stack.add_titled(&scrolled, Some("service"), "Service");
     }
 
+    fn create_provider_tab(&self, stack: &Stack, category: &str) {
+        // A page holding a single provider is titled after it
+        let providers = self.registry.by_category(category);
+        let title = match providers.as_slice() {
+            [provider] => provider.name().to_string(),
+            _ => capitalize(category),
+        };
+
+        stack.add_titled(&page_slot(&self.build_provider_page(category)), Some(category), &title);
+    }
+
+    fn build_provider_page(&self, category: &str) -> gtk::ScrolledWindow {
+        let main_box = Box::new(Orientation::Vertical, 20);
+        main_box.set_margin_start(self.page_margin());
+        main_box.set_margin_end(self.page_margin());
+        main_box.set_margin_top(self.page_margin());
+        main_box.set_margin_bottom(self.page_margin());
+        main_box.set_halign(gtk::Align::Center);
+
+        for provider in self.registry.by_category(category) {
+            let section_box = Box::new(Orientation::Vertical, 8);
+            section_box.set_halign(gtk::Align::Start);
+
+            let title = Label::new(None);
+            title.set_markup(&format!("<b>{}</b>", escape_markup(provider.name())));
+            title.set_halign(gtk::Align::Start);
+            title.set_margin_bottom(10);
+            section_box.append(&title);
+
+            // Scripts run on a worker thread and fill their section in when
+            // they are done, so a slow one can't hold up the window
+            let step = format!("provider {}", provider.name());
+            match provider.detect_job() {
+                Some(job) => {
+                    let loading = Label::new(Some("Loading…"));
+                    loading.add_css_class("dim-label");
+                    loading.set_halign(gtk::Align::Start);
+                    loading.set_margin_start(20);
+                    section_box.append(&loading);
+
+                    let main_window = self.clone();
+                    let category = category.to_string();
+                    let source = provider.category().to_string();
+                    let name = provider.name().to_string();
+                    let section_box = section_box.clone();
+                    glib::spawn_future_local(async move {
+                        let result = gio::spawn_blocking(move || journal::timed(&step, &source, job))
+                            .await
+                            .unwrap_or_else(|_| Err(anyhow::anyhow!("detection panicked")));
+                        // The page was rebuilt while the script ran
+                        if section_box.root().is_none() {
+                            return;
+                        }
+                        section_box.remove(&loading);
+                        main_window.fill_provider_section(&section_box, &category, &name, result);
+                    });
+                }
+                None => {
+                    let result = journal::timed(&step, provider.category(), || provider.detect());
+                    self.fill_provider_section(&section_box, category, provider.name(), result);
+                }
+            }
+
+            main_box.append(&section_box);
+        }
+
+        let scrolled = gtk::ScrolledWindow::new();
+        scrolled.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+        scrolled.set_child(Some(&main_box));
+        scrolled
+    }
+
+    // Rows of a provider page section, or why there are none
+    fn fill_provider_section(
+        &self,
+        section_box: &Box,
+        category: &str,
+        name: &str,
+        result: anyhow::Result<Vec<(String, String)>>,
+    ) {
+        match result {
+            Ok(rows) if !rows.is_empty() => {
+                for (key, value) in rows {
+                    let row_box = Box::new(Orientation::Horizontal, 10);
+                    row_box.set_halign(gtk::Align::Start);
+                    row_box.set_margin_start(20);
+
+                    let name_label = Label::new(Some(&format!("{}:", key)));
+                    name_label.set_halign(gtk::Align::Start);
+                    name_label.set_size_request(120, -1);
+                    row_box.append(&name_label);
+
+                    let value_label = Label::new(Some(&value));
+                    value_label.set_halign(gtk::Align::Start);
+                    row_box.append(&value_label);
+                    describe_field(&name_label, &value_label);
+
+                    self.add_search_row(category, &row_box, &[name, &key, &value]);
+                    section_box.append(&row_box);
+                }
+            }
+            Ok(_) => {
+                let empty_label = Label::new(Some("No information available"));
+                empty_label.set_halign(gtk::Align::Start);
+                empty_label.set_margin_start(20);
+                section_box.append(&empty_label);
+            }
+            Err(e) => {
+                let error_label = Label::new(Some(&format!("Error detecting {}: {}", name, e)));
+                error_label.set_halign(gtk::Align::Start);
+                error_label.set_margin_start(20);
+                section_box.append(&error_label);
+            }
+        }
+    }
+
+    // Clicking the Processor row, other than its value which copies, opens
+    // the cache, instruction set and vulnerability details
+    fn open_cpu_details_on_click(&self, field_box: &Box, value_label: &Label) {
+        if let Some(name_label) = field_box.first_child() {
+            name_label.set_tooltip_text(Some("Click for processor details"));
+        }
+        let click = gtk::GestureClick::new();
+        click.set_button(gtk::gdk::BUTTON_PRIMARY);
+        let main_window = self.clone();
+        let value_label = value_label.clone();
+        click.connect_released(move |gesture, _, x, y| {
+            let value: &gtk::Widget = value_label.upcast_ref();
+            let on_value = gesture
+                .widget()
+                .pick(x, y, gtk::PickFlags::DEFAULT)
+                .is_some_and(|picked| picked == *value || picked.is_ancestor(value));
+            if !on_value {
+                CpuWindow::new(&main_window.window, &value_label.text()).present();
+            }
+        });
+        field_box.add_controller(click);
+    }
+
+    // A logo dropped on the overview goes through the logo editor and is
+    // saved to the config file the window was opened from, backed up only
+    // if nothing else has been saved from the window this session
+    fn accept_logo_drops(&self, image: &Image) {
+        let main_window = self.clone();
+        logo_editor::accept_drops(image, &self.window, move |logo_path| {
+            let logo_path = logo_path.to_string_lossy().to_string();
+            main_window.config.borrow_mut().distro_image_path = logo_path.clone();
+            let config_path = main_window.config_path.borrow().clone();
+            if let Some(config_path) = config_path.filter(|path| path.exists()) {
+                let backup_count = main_window.config.borrow().backup_count;
+                if let Err(e) = config::save_field(&config_path, "distro_image_path", logo_path.into(), backup_count) {
+                    eprintln!("Failed to save the logo: {:#}", e);
+                }
+            }
+            main_window.refresh_page(CATEGORY_OVERVIEW);
+        });
+    }
+
+    /// Re-run detection for a page and swap its contents in place
+    fn refresh_page(&self, name: &str) {
+        if self.stack.child_by_name(name).is_none() {
+            return;
+        }
+
+        // The rebuilt page registers its own search rows
+        self.search_rows.borrow_mut().retain(|row| row.page != name);
+
+        let page: gtk::Widget = match name {
+            CATEGORY_OVERVIEW => self.overview_page(),
+            CATEGORY_DISPLAY => self.build_display_page(DisplayInfo::detect()).upcast(),
+            CATEGORY_STORAGE => self.build_storage_page(StorageInfo::detect()).upcast(),
+            // Static pages, nothing to detect
+            "support" | "service" => return,
+            category => self.build_provider_page(category).upcast(),
+        };
+        self.replace_page(name, &page);
+    }
+
+    // Rebuild the storage or display page from detection run on a worker
+    // thread, for refreshes that happen without the user asking
+    fn refresh_page_in_background(&self, name: &'static str) {
+        let main_window = self.clone();
+        glib::spawn_future_local(async move {
+            let page: gtk::Widget = match name {
+                CATEGORY_DISPLAY => match gio::spawn_blocking(DisplayInfo::detect).await {
+                    Ok(detected) => {
+                        main_window.search_rows.borrow_mut().retain(|row| row.page != name);
+                        main_window.build_display_page(detected).upcast()
+                    }
+                    Err(_) => return,
+                },
+                CATEGORY_STORAGE => match gio::spawn_blocking(StorageInfo::detect).await {
+                    Ok(detected) => {
+                        main_window.search_rows.borrow_mut().retain(|row| row.page != name);
+                        main_window.build_storage_page(detected).upcast()
+                    }
+                    Err(_) => return,
+                },
+                _ => return main_window.refresh_page(name),
+            };
+            main_window.replace_page(name, &page);
+        });
+    }
+
+    fn replace_page(&self, name: &str, page: &gtk::Widget) {
+        let slot = match self.stack.child_by_name(name).and_downcast::<Box>() {
+            Some(slot) => slot,
+            None => return,
+        };
+
+        while let Some(child) = slot.first_child() {
+            slot.remove(&child);
+        }
+        slot.append(page);
+        self.apply_search(false);
+    }
+
+    // Each tick schedules the next so the interval follows the config and
+    // slows down when the machine switches to battery
+    fn schedule_auto_refresh(&self) {
+        let seconds = self.config.borrow().auto_refresh_seconds;
+        if seconds == 0 {
+            return;
+        }
+
+        // One timer at a time, even when a resident window is shown again
+        if self.auto_refresh_pending.replace(true) {
+            return;
+        }
+
+        let main_window = self.clone();
+        glib::timeout_add_seconds_local_once(system_info::refresh_interval(seconds), move || {
+            main_window.auto_refresh_pending.set(false);
+            // Stop once the window has been closed
+            if !main_window.window.is_visible() {
+                return;
+            }
+            main_window.update_live_values();
+            main_window.schedule_auto_refresh();
+        });
+    }
+
+    // Only the volatile values; everything else stays as detected
+    fn update_live_values(&self) {
+        let live = self.live.borrow();
+
+        if let (Some(label), Some(uptime)) = (&live.uptime, system_info::uptime()) {
+            label.set_text(&units::format_duration(uptime));
+        }
+        if let (Some(label), Some((used, total))) = (&live.memory_usage, system_info::memory_usage()) {
+            label.set_text(&report::memory_usage_text(used, total));
+        }
+        if let (Some(label), Some(battery)) = (&live.battery, power::battery_summary()) {
+            label.set_text(&battery);
+        }
+
+        if !live.volumes.is_empty() {
+            if let Ok(filesystems) = system_info::detect_filesystem_usage() {
+                for filesystem in &filesystems {
+                    let widgets = live
+                        .volumes
+                        .iter()
+                        .find(|(mountpoint, _, _)| *mountpoint == filesystem.mountpoint);
+                    if let Some((_, bar, label)) = widgets {
+                        set_usage(bar, filesystem.usage_percent);
+                        label.set_text(&report::volume_usage_text(filesystem));
+                    }
+                }
+            }
+        }
+
+        // Skipped on battery by device_temperature itself; the last value stays
+        for (device, label) in &live.temperatures {
+            if let Ok(temperature) = system_info::device_temperature(device) {
+                label.set_text(&temperature);
+            }
+        }
+    }
+
+    fn add_search_row(&self, page: &str, widget: &impl IsA<gtk::Widget>, parts: &[&str]) {
+        self.search_rows.borrow_mut().push(SearchRow {
+            page: page.to_string(),
+            widget: widget.clone().upcast(),
+            text: parts.join(" ").to_lowercase(),
+        });
+    }
+
+    // Hide rows that don't match the search and highlight those that do.
+    // With `jump`, switch to the first page with a match if the visible one
+    // has none.
+    fn apply_search(&self, jump: bool) {
+        let query = self.search_entry.text().trim().to_lowercase();
+        let rows = self.search_rows.borrow();
+
+        let mut first_match: Option<&str> = None;
+        let mut visible_page_matches = false;
+        let visible_page = self.stack.visible_child_name().map(|name| name.to_string());
+        for row in rows.iter() {
+            let matches = !query.is_empty() && row.text.contains(&query);
+            row.widget.set_visible(query.is_empty() || matches);
+            if matches {
+                row.widget.add_css_class("atl-search-match");
+                first_match.get_or_insert(&row.page);
+                visible_page_matches |= visible_page.as_deref() == Some(row.page.as_str());
+            } else {
+                row.widget.remove_css_class("atl-search-match");
+            }
+        }
+
+        if jump && !visible_page_matches {
+            if let Some(page) = first_match {
+                self.stack.set_visible_child_name(page);
+            }
+        }
+    }
+
+    /// Let the refresh action re-run hardware detection for the overview.
+    /// Without it only the distribution details are detected again.
+    pub fn set_redetect<F>(&self, redetect: F)
+    where
+        F: Fn(&Config) -> Config + Send + Sync + 'static,
+    {
+        *self.redetect.borrow_mut() = Some(Arc::new(redetect));
+    }
+
+    // Save the whole window, or only the visible tab without the header bar
+    fn show_save_image_dialog(&self, page_only: bool) {
+        let page_name = self
+            .stack
+            .visible_child_name()
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| CATEGORY_OVERVIEW.to_string());
+        let target: gtk::Widget = match self.stack.visible_child() {
+            Some(page) if page_only => page,
+            _ => self.window.clone().upcast(),
+        };
+
+        let file_chooser = gtk::FileChooserNative::new(
+            Some("Save as Image"),
+            Some(&self.window),
+            gtk::FileChooserAction::Save,
+            Some("Save"),
+            Some("Cancel"),
+        );
+        let name = if page_only {
+            format!("about-this-linux-{}.png", page_name)
+        } else {
+            "about-this-linux.png".to_string()
+        };
+        file_chooser.set_current_name(&name);
+
+        let config = self.config.clone();
+        let toast = self.toast.clone();
+        let window = self.window.clone();
+        file_chooser.connect_response(move |dialog, response| {
+            if response != gtk::ResponseType::Accept {
+                return;
+            }
+            let path = match dialog.file().and_then(|file| file.path()) {
+                Some(path) => path,
+                None => return,
+            };
+
+            // Wait for the dialog to be gone so it cannot end up in the image
+            let config = config.clone();
+            let toast = toast.clone();
+            let window = window.clone();
+            let target = target.clone();
+            glib::idle_add_local_once(move || match screenshot::save_png(&target, &path) {
+                Ok(()) => {
+                    toast.show("Image saved");
+                    run_hook(&config.borrow(), HookEvent::Export, Some(&path));
+                }
+                Err(e) => {
+                    eprintln!("Failed to save image: {:#}", e);
+                    utils::show_error_dialog(
+                        Some(window.upcast_ref()),
+                        "Save Failed",
+                        &format!("Failed to save image: {:#}", e),
+                    );
+                }
+            });
+        });
+
+        utils::show_file_chooser(&file_chooser);
+    }
+
+    fn show_export_pdf_dialog(&self) {
+        let file_chooser = gtk::FileChooserNative::new(
+            Some("Export Report as PDF"),
+            Some(&self.window),
+            gtk::FileChooserAction::Save,
+            Some("Export"),
+            Some("Cancel"),
+        );
+        file_chooser.set_current_name("about-this-linux-report.pdf");
+
+        let config = self.config.clone();
+        let toast = self.toast.clone();
+        let window = self.window.clone();
+        file_chooser.connect_response(move |dialog, response| {
+            if response != gtk::ResponseType::Accept {
+                return;
+            }
+            let path = match dialog.file().and_then(|file| file.path()) {
+                Some(path) => path,
+                None => return,
+            };
+
+            let settings = config.borrow().clone();
+            let config = config.clone();
+            let toast = toast.clone();
+            let window = window.clone();
+            build_report(settings, report::printable_report, move |text| {
+                match pdf::export(&text, &path) {
+                    Ok(()) => {
+                        toast.show("Report exported");
+                        run_hook(&config.borrow(), HookEvent::Export, Some(&path));
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to export report: {:#}", e);
+                        utils::show_error_dialog(
+                            Some(window.upcast_ref()),
+                            "Export Failed",
+                            &format!("Failed to export report: {:#}", e),
+                        );
+                    }
+                }
+            });
+        });
+
+        utils::show_file_chooser(&file_chooser);
+    }
+
+    /// Run detection again for the visible tab, as F5 does
+    pub fn refresh(&self) {
+        self.refresh_visible_page();
+    }
+
+    fn refresh_visible_page(&self) {
+        let name = match self.stack.visible_child_name() {
+            Some(name) => name.to_string(),
+            None => return,
+        };
+
+        let redetect = self.redetect.borrow().clone();
+        match redetect {
+            Some(redetect) if name == CATEGORY_OVERVIEW => {
+                let current = self.config.borrow().clone();
+                let main_window = self.clone();
+                glib::spawn_future_local(async move {
+                    let redetected = gio::spawn_blocking(move || redetect(&current)).await;
+                    match redetected {
+                        Ok(config) => *main_window.config.borrow_mut() = config,
+                        Err(_) => eprintln!("Detecting the hardware again failed"),
+                    }
+                    main_window.finish_refresh(&name);
+                });
+            }
+            _ => self.finish_refresh(&name),
+        }
+    }
+
+    fn finish_refresh(&self, name: &str) {
+        self.refresh_page(name);
+        run_hook(&self.config.borrow(), HookEvent::Refresh, None);
+    }
+
+    /// Reload the config with `reload` whenever the file at `path` changes
+    /// and rebuild the overview from it. Tabs reordered by dragging are
+    /// saved to this file.
+    pub fn watch_config<F>(&self, path: &Path, reload: F)
+    where
+        F: Fn(&Config) -> anyhow::Result<Config> + 'static,
+    {
+        *self.config_path.borrow_mut() = Some(path.to_path_buf());
+        let monitor = match gio::File::for_path(path)
+            .monitor_file(gio::FileMonitorFlags::NONE, None::<&gio::Cancellable>)
+        {
+            Ok(monitor) => monitor,
+            Err(e) => {
+                eprintln!("Cannot watch {} for changes: {}", path.display(), e);
+                return;
+            }
+        };
+
+        let main_window = self.clone();
+        let reload = Rc::new(reload);
+        let pending = Rc::new(Cell::new(false));
+        monitor.connect_changed(move |_, _, _, event| {
+            let relevant = matches!(
+                event,
+                gio::FileMonitorEvent::ChangesDoneHint | gio::FileMonitorEvent::Created
+            );
+            // Editors write in several steps; reload once they are done
+            if !relevant || pending.replace(true) {
+                return;
+            }
+
+            let main_window = main_window.clone();
+            let reload = reload.clone();
+            let pending = pending.clone();
+            glib::timeout_add_local_once(Duration::from_millis(200), move || {
+                pending.set(false);
+                let reloaded = reload(&main_window.config.borrow());
+                match reloaded {
+                    Ok(config) => {
+                        theme::apply_user_css(config.css_path.as_deref());
+                        theme::apply_preset(&main_window.window, config.theme);
+                        theme::apply_font_family(&main_window.window, theme::FONT_CLASS, config.font_family.as_deref());
+                        *main_window.config.borrow_mut() = config;
+                        main_window.refresh_page(CATEGORY_OVERVIEW);
+                    }
+                    Err(e) => eprintln!("Keeping previous config: {}", e),
+                }
+            });
+        });
+
+        if let Some(previous) = self.config_monitor.replace(Some(monitor)) {
+            previous.cancel();
+        }
+    }
+
+    // Battery and adapter changes arrive as events, so the Power tab and the
+    // Battery row follow them without waiting for the auto-refresh
+    fn watch_power(&self) {
+        if system_info::detection_disabled() {
+            return;
+        }
+        let main_window = self.clone();
+        let result = power::watch(move || {
+            if !main_window.is_open() {
+                return;
+            }
+            if let (Some(label), Some(battery)) = (&main_window.live.borrow().battery, power::battery_summary()) {
+                label.set_text(&battery);
+            }
+            main_window.refresh_page(CATEGORY_POWER);
+        });
+
+        if let Err(e) = result {
+            eprintln!("Live power updates disabled: {}", e);
+        }
+    }
+
+    fn watch_hotplug(&self) {
+        let main_window = self.clone();
+        let result = hotplug::watch(move |subsystems| {
+            let mut pages = Vec::new();
+            if subsystems.contains(hotplug::SUBSYSTEM_BLOCK) {
+                pages.push(CATEGORY_STORAGE);
+            }
+            if subsystems.contains(hotplug::SUBSYSTEM_DRM) {
+                pages.push(CATEGORY_DISPLAY);
+            }
+            if subsystems.contains(hotplug::SUBSYSTEM_USB) {
+                pages.push(CATEGORY_USB);
+            }
+
+            for page in pages {
+                main_window.refresh_page_in_background(page);
+            }
+        });
+
+        if let Err(e) = result {
+            eprintln!("Hotplug updates disabled: {}", e);
+        }
+    }
+
+    // Reopen on the tab, size and monitor of the last window. A --page or
+    // placement given explicitly is applied later and still wins.
+    fn remember_state(&self) {
+        let config = self.config.borrow();
+        if !config.remember_window {
+            return;
+        }
+
+        let state = WindowState::load();
+        if let Some(page) = state.page.as_deref() {
+            if self.stack.child_by_name(page).is_some() {
+                self.stack.set_visible_child_name(page);
+            }
+        }
+        if let (Some([width, height]), true) = (state.size, config.resizable) {
+            self.window.set_default_size(width, height);
+        }
+        if let (Some(monitor), Placement::Default) = (state.monitor, config.placement) {
+            placement::restore(&self.window, monitor);
+        }
+
+        // Also runs when a background window is only hidden
+        let main_window = self.clone();
+        self.window.connect_unmap(move |_| main_window.save_state());
+    }
+
+    fn save_state(&self) {
+        if !self.config.borrow().remember_window {
+            return;
+        }
+        let window = &self.window;
+        let (width, height) = window.default_size();
+        let state = WindowState {
+            page: self.stack.visible_child_name().map(|name| name.to_string()),
+            monitor: placement::current_monitor(window),
+            size: self.config.borrow().resizable.then_some([width, height]),
+        };
+        state.save();
+    }
+
+    pub fn show_page(&self, name: &str) {
+        if self.stack.child_by_name(name).is_some() {
+            self.stack.set_visible_child_name(name);
+        } else {
+            eprintln!("No such page: {}", name);
+        }
+    }
+
+    /// Whether the window is still around, i.e. not closed for good
+    pub fn is_open(&self) -> bool {
+        self.window.application().is_some()
+    }
+
+    /// Hide instead of closing, so presenting the window again is instant.
+    /// Live values catch up whenever it is shown.
+    pub fn keep_resident(&self) {
+        self.window.set_hide_on_close(true);
+        // Closing no longer exits, so offer a way to
+        self.menu.append(Some("Quit"), Some("app.quit"));
+        let main_window = self.clone();
+        self.window.connect_show(move |_| {
+            main_window.update_live_values();
+            main_window.schedule_auto_refresh();
+        });
+    }
+
+    /// Map the window fully transparent for a few frames, save its content to
+    /// `path` as a PNG and pass on the result. GTK only draws mapped widgets,
+    /// so this is an on-screen capture: it needs a display, and the window
+    /// exists there, if invisibly, until the caller closes it.
+    pub fn capture_on_screen(&self, path: PathBuf, done: impl FnOnce(anyhow::Result<()>) + 'static) {
+        let content = match self.window.child() {
+            Some(content) => content,
+            None => return done(Err(anyhow::anyhow!("The window has no content"))),
+        };
+        // A scripted capture shouldn't change where the next launch opens
+        self.config.borrow_mut().remember_window = false;
+        self.window.set_opacity(0.0);
+
+        let done = RefCell::new(Some(done));
+        let frames = Cell::new(0);
+        content.add_tick_callback(move |content, _| {
+            // The first frames go to layout and loading the logo
+            if frames.replace(frames.get() + 1) < SCREENSHOT_FRAMES {
+                return glib::ControlFlow::Continue;
+            }
+            if let Some(done) = done.borrow_mut().take() {
+                let content = content.clone();
+                let path = path.clone();
+                glib::idle_add_local_once(move || done(screenshot::save_png(&content, &path)));
+            }
+            glib::ControlFlow::Break
+        });
+        self.window.present();
+    }
+
     pub fn present(&self) {
+        placement::apply(&self.window, &self.config.borrow());
         self.window.present();
+        run_hook(&self.config.borrow(), HookEvent::Open, None);
+    }
+}
+
+/// Build a report in a worker thread, since it runs every provider, and
+/// hand the text to `done` back on the main thread
+fn build_report(config: Config, build: fn(&Config, &ProviderRegistry) -> String, done: impl FnOnce(String) + 'static) {
+    glib::spawn_future_local(async move {
+        // The window's registry isn't Send, so the worker makes its own
+        let result = gio::spawn_blocking(move || build(&config, &ProviderRegistry::from_config(&config))).await;
+        match result {
+            Ok(text) => done(text),
+            Err(_) => eprintln!("Building the report failed"),
+        }
+    });
+}
+
+fn set_update_badge(badge: &Label, count: usize) {
+    badge.set_text(&count.to_string());
+    badge.set_visible(count > 0);
+    let description = format!("{} updates available", count);
+    badge.update_property(&[gtk::accessible::Property::Label(&description)]);
+}
+
+// Without a configured command, open the distro's updater or at least say
+// how many updates are waiting
+fn builtin_software_update(button: &Button) {
+    if updates::launch_updater_gui() {
+        return;
+    }
+    let parent = button.root().and_downcast::<gtk::Window>();
+
+    button.set_sensitive(false);
+    button.set_label("Checking...");
+    let button = button.clone();
+    glib::spawn_future_local(async move {
+        // Finding PackageKit may have to start its daemon, so that is done
+        // off the main thread as well
+        let result = gio::spawn_blocking(|| {
+            UpdateSource::detect().map(|source| (source, source.pending_updates()))
+        })
+        .await;
+        button.set_sensitive(true);
+        button.set_label("Software Update...");
+        match result {
+            Ok(None) => utils::show_error_dialog(
+                parent.as_ref(),
+                "Software Update",
+                "Neither PackageKit nor a supported package manager (pacman, apt, dnf or zypper) was found.\n\n\
+                Set software_update_command in the config to choose what this button runs.",
+            ),
+            Ok(Some((_, Ok(updates)))) if updates.is_empty() => {
+                utils::show_info_dialog(parent.as_ref(), "Software Update", "Your system is up to date.")
+            }
+            Ok(Some((source, Ok(updates)))) => {
+                let count = updates.len();
+                let mut listed = updates.iter().take(MAX_LISTED_UPDATES).cloned().collect::<Vec<_>>().join("\n");
+                if count > MAX_LISTED_UPDATES {
+                    listed.push_str(&format!("\n…and {} more", count - MAX_LISTED_UPDATES));
+                }
+                utils::show_info_dialog(
+                    parent.as_ref(),
+                    "Software Update",
+                    &format!(
+                        "{} update{} available:\n\n{}\n\nInstall {} in a terminal with:\n{}",
+                        count,
+                        if count == 1 { " is" } else { "s are" },
+                        listed,
+                        if count == 1 { "it" } else { "them" },
+                        source.upgrade_command()
+                    ),
+                )
+            }
+            Ok(Some((_, Err(e)))) => {
+                eprintln!("Failed to check for updates: {}", e);
+                utils::show_error_dialog(parent.as_ref(), "Software Update", &format!("Failed to check for updates: {}", e));
+            }
+            Err(_) => eprintln!("Update check panicked"),
+        }
+    });
+}
+
+// `pixbuf` was loaded at `scale` times the wanted size
+fn logo_image(pixbuf: &Pixbuf, scale: i32) -> Image {
+    let image = Image::from_paintable(Some(&gtk::gdk::Texture::for_pixbuf(pixbuf)));
+    image.set_pixel_size(pixbuf.width().max(pixbuf.height()) / scale);
+    image
+}
+
+// SVGs are drawn by GTK's icon loader rather than pre-rendered, so they are
+// rasterized at exactly the device size, redrawn when the window moves to a
+// screen with another scale factor, and `*-symbolic.svg` files are recolored
+// with the text color of the current theme
+fn svg_logo_image(path: &str, size: [i32; 2]) -> Option<Image> {
+    let (format, width, height) = Pixbuf::file_info(path)?;
+    if format.name().as_deref() != Some("svg") || width <= 0 || height <= 0 {
+        return None;
+    }
+
+    // The icon is fitted into a square, so size it by the longer side of
+    // the SVG scaled into the configured box
+    let fit = (size[0] as f64 / width as f64).min(size[1] as f64 / height as f64);
+    let image = Image::from_gicon(&gio::FileIcon::new(&gio::File::for_path(path)));
+    image.set_pixel_size((width.max(height) as f64 * fit).round() as i32);
+    Some(image)
+}
+
+fn load_tux_logo(size: [i32; 2]) -> Option<Pixbuf> {
+    Pixbuf::from_resource_at_scale(paths::TUX_LOGO_RESOURCE, size[0], size[1], true).ok()
+}
+
+// Clicking the logo five times in a row swaps it for Tux, and back again
+fn add_logo_easter_egg(image: &Image, tux: Pixbuf) {
+    let logo_icon = image.gicon();
+    let logo_paintable = image.paintable();
+    let tux = gtk::gdk::Texture::for_pixbuf(&tux);
+    let showing_tux = Rc::new(Cell::new(false));
+    let click = gtk::GestureClick::new();
+    click.connect_pressed(move |gesture, n_press, _, _| {
+        if n_press % EASTER_EGG_CLICKS != 0 {
+            return;
+        }
+        let image = match gesture.widget().downcast::<Image>() {
+            Ok(image) => image,
+            Err(_) => return,
+        };
+
+        let to_tux = !showing_tux.get();
+        showing_tux.set(to_tux);
+        let (logo_icon, logo_paintable, tux) = (logo_icon.clone(), logo_paintable.clone(), tux.clone());
+        let swapped = Cell::new(false);
+        // Fade out, swap halfway through, fade back in
+        animate(&image, LOGO_FLIP_DURATION, move |image, progress| {
+            if progress >= 0.5 && !swapped.replace(true) {
+                match (&logo_icon, to_tux) {
+                    (Some(icon), false) => image.set_from_gicon(icon),
+                    (None, false) => image.set_from_paintable(logo_paintable.as_ref()),
+                    (_, true) => image.set_from_paintable(Some(&tux)),
+                }
+            }
+            image.set_opacity((progress - 0.5).abs() * 2.0);
+        });
+    });
+    image.add_controller(click);
+}
+
+/// Call `step` on every frame for `duration`, with the progress from 0.0 to 1.0
+fn animate<W: IsA<gtk::Widget>>(widget: &W, duration: Duration, step: impl Fn(&W, f64) + 'static) {
+    let started = Cell::new(None);
+    widget.add_tick_callback(move |widget, clock| {
+        let now = clock.frame_time();
+        let start = started.get().unwrap_or(now);
+        started.set(Some(start));
+        let progress = ((now - start) as f64 / duration.as_micros() as f64).min(1.0);
+        step(widget, progress);
+        if progress < 1.0 {
+            glib::ControlFlow::Continue
+        } else {
+            glib::ControlFlow::Break
+        }
+    });
+}
+
+// Screen readers announce a row as one "Processor: AMD Ryzen…" rather than
+// two unrelated labels, kept in step for values that update live
+fn describe_field(name_label: &Label, value_label: &Label) {
+    value_label.update_relation(&[gtk::accessible::Relation::LabelledBy(&[name_label.upcast_ref()])]);
+
+    let name = name_label.text().trim_end_matches(':').to_string();
+    let describe = move |label: &Label| {
+        label.update_property(&[gtk::accessible::Property::Label(&format!("{}: {}", name, label.text()))]);
+    };
+    describe(value_label);
+    value_label.connect_label_notify(describe);
+}
+
+fn copy_value(label: &Label, toast: &Toast) {
+    label.clipboard().set_text(&label.text());
+    toast.show("Copied to clipboard");
+}
+
+// Pages are wrapped in a box so they can be rebuilt without changing the
+// order of the stack switcher
+fn page_slot(page: &impl IsA<gtk::Widget>) -> Box {
+    page.set_vexpand(true);
+    let slot = Box::new(Orientation::Vertical, 0);
+    slot.append(page);
+    slot
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
     }
 }
 
@@ -616,12 +2390,14 @@ struct DistroServiceLinks {
     community: Vec<(String, String)>,
     bug_reports: Vec<(String, String)>,
     downloads: Vec<(String, String)>,
+    // Support pages of the machine's manufacturer
+    hardware: Vec<(String, String)>,
 }
 
-fn get_distro_service_links(distro_name: &str) -> DistroServiceLinks {
+fn get_distro_service_links(distro_name: &str, sys_vendor: Option<&str>) -> DistroServiceLinks {
     let distro_lower = distro_name.to_lowercase();
     
-    if distro_lower.contains("arch") {
+    let mut links = if distro_lower.contains("arch") {
         DistroServiceLinks {
             documentation: vec![
                 ("Arch Wiki".to_string(), "https://wiki.archlinux.org/".to_string()),
@@ -642,6 +2418,7 @@ fn get_distro_service_links(distro_name: &str) -> DistroServiceLinks {
                 ("Package Search".to_string(), "https://archlinux.org/packages/".to_string()),
                 ("AUR".to_string(), "https://aur.archlinux.org/".to_string()),
             ],
+            hardware: vec![],
         }
     } else if distro_lower.contains("ubuntu") {
         DistroServiceLinks {
@@ -665,6 +2442,7 @@ fn get_distro_service_links(distro_name: &str) -> DistroServiceLinks {
                 ("Package Search".to_string(), "https://packages.ubuntu.com/".to_string()),
                 ("Snap Store".to_string(), "https://snapcraft.io/".to_string()),
             ],
+            hardware: vec![],
         }
     } else if distro_lower.contains("fedora") {
         DistroServiceLinks {
@@ -688,6 +2466,7 @@ fn get_distro_service_links(distro_name: &str) -> DistroServiceLinks {
                 ("Package Search".to_string(), "https://packages.fedoraproject.org/".to_string()),
                 ("COPR Repos".to_string(), "https://copr.fedorainfracloud.org/".to_string()),
             ],
+            hardware: vec![],
         }
     } else if distro_lower.contains("debian") {
         DistroServiceLinks {
@@ -711,6 +2490,7 @@ fn get_distro_service_links(distro_name: &str) -> DistroServiceLinks {
                 ("Package Search".to_string(), "https://packages.debian.org/".to_string()),
                 ("CD/DVD Images".to_string(), "https://www.debian.org/CD/".to_string()),
             ],
+            hardware: vec![],
         }
     } else if distro_lower.contains("opensuse") || distro_lower.contains("suse") {
         DistroServiceLinks {
@@ -734,6 +2514,7 @@ fn get_distro_service_links(distro_name: &str) -> DistroServiceLinks {
                 ("Software Search".to_string(), "https://software.opensuse.org/".to_string()),
                 ("Build Service".to_string(), "https://build.opensuse.org/".to_string()),
             ],
+            hardware: vec![],
         }
     } else if distro_lower.contains("manjaro") {
         DistroServiceLinks {
@@ -756,6 +2537,7 @@ fn get_distro_service_links(distro_name: &str) -> DistroServiceLinks {
                 ("Package Search".to_string(), "https://packages.manjaro.org/".to_string()),
                 ("AUR".to_string(), "https://aur.archlinux.org/".to_string()),
             ],
+            hardware: vec![],
         }
     } else {
         // Default empty links for unknown distributions
@@ -764,6 +2546,56 @@ fn get_distro_service_links(distro_name: &str) -> DistroServiceLinks {
             community: vec![],
             bug_reports: vec![],
             downloads: vec![],
+            hardware: vec![],
         }
-    }
+    };
+    links.hardware = hardware_service_links(sys_vendor.unwrap_or_default());
+    links
+}
+
+// Manufacturer support, driver and firmware pages, by DMI sys_vendor
+fn hardware_service_links(sys_vendor: &str) -> Vec<(String, String)> {
+    let vendor_lower = sys_vendor.to_lowercase();
+    let links: &[(&str, &str)] = if vendor_lower.starts_with("lenovo") {
+        &[
+            ("Lenovo Support", "https://pcsupport.lenovo.com/"),
+            ("Linux on Lenovo", "https://support.lenovo.com/solutions/pd031426"),
+            ("Firmware Updates (LVFS)", "https://fwupd.org/lvfs/devices/"),
+        ]
+    } else if vendor_lower.starts_with("dell") {
+        &[
+            ("Dell Support", "https://www.dell.com/support/home/"),
+            ("Drivers &amp; Downloads", "https://www.dell.com/support/home/drivers"),
+            ("Dell Linux Engineering", "https://linux.dell.com/"),
+            ("Firmware Updates (LVFS)", "https://fwupd.org/lvfs/devices/"),
+        ]
+    } else if vendor_lower == "hp" || vendor_lower.starts_with("hewlett-packard") {
+        &[
+            ("HP Support", "https://support.hp.com/"),
+            ("Drivers &amp; Software", "https://support.hp.com/drivers"),
+            ("HP Linux Imaging and Printing", "https://developers.hp.com/hp-linux-imaging-and-printing"),
+        ]
+    } else if vendor_lower.starts_with("asus") {
+        &[
+            ("ASUS Support", "https://www.asus.com/support/"),
+            ("Download Center", "https://www.asus.com/support/download-center/"),
+            ("asus-linux.org", "https://asus-linux.org/"),
+        ]
+    } else if vendor_lower.starts_with("framework") {
+        &[
+            ("Framework Support", "https://frame.work/support"),
+            ("Linux on Framework", "https://frame.work/linux"),
+            ("Knowledge Base", "https://knowledgebase.frame.work/"),
+            ("Framework Community", "https://community.frame.work/"),
+        ]
+    } else if vendor_lower.starts_with("system76") {
+        &[
+            ("System76 Support", "https://support.system76.com/"),
+            ("System76 Driver", "https://support.system76.com/articles/system76-driver/"),
+            ("Firmware Updates", "https://support.system76.com/articles/system-firmware/"),
+        ]
+    } else {
+        &[]
+    };
+    links.iter().map(|(name, url)| (name.to_string(), url.to_string())).collect()
 }
@@ -0,0 +1,160 @@
+use anyhow::{bail, Context, Result};
+use gtk::prelude::*;
+use gtk::{gio, glib};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+// PackageKit answers for whichever package manager the distribution uses,
+// from the metadata it keeps refreshed itself, without root
+const PACKAGEKIT_NAME: &str = "org.freedesktop.PackageKit";
+const PACKAGEKIT_PATH: &str = "/org/freedesktop/PackageKit";
+const TRANSACTION_INTERFACE: &str = "org.freedesktop.PackageKit.Transaction";
+const CALL_TIMEOUT_MS: i32 = 5000;
+// Slow mirrors can keep a transaction busy for a while
+const TRANSACTION_TIMEOUT: Duration = Duration::from_secs(120);
+// Bitfield of PkFilterEnum values; 1 is "none", i.e. no filtering
+const FILTER_NONE: u64 = 1 << 1;
+
+fn system_bus() -> Result<gio::DBusConnection> {
+    gio::bus_get_sync(gio::BusType::System, gio::Cancellable::NONE).context("No system bus")
+}
+
+fn call(
+    connection: &gio::DBusConnection,
+    path: &str,
+    interface: &str,
+    method: &str,
+    args: glib::Variant,
+) -> Result<glib::Variant> {
+    connection
+        .call_sync(
+            Some(PACKAGEKIT_NAME),
+            path,
+            interface,
+            method,
+            Some(&args),
+            None,
+            gio::DBusCallFlags::NONE,
+            CALL_TIMEOUT_MS,
+            gio::Cancellable::NONE,
+        )
+        .with_context(|| format!("PackageKit {} failed", method))
+}
+
+/// Whether the PackageKit daemon is installed; asking starts it if needed
+pub fn available() -> bool {
+    system_bus()
+        .and_then(|connection| {
+            call(
+                &connection,
+                PACKAGEKIT_PATH,
+                "org.freedesktop.DBus.Properties",
+                "Get",
+                (PACKAGEKIT_NAME, "VersionMajor").to_variant(),
+            )
+        })
+        .is_ok()
+}
+
+/// Packages with an update available, as "name version". Blocks until
+/// PackageKit is done, so run it off the main thread.
+pub fn pending_updates() -> Result<Vec<String>> {
+    // Transaction signals are delivered to this thread's own context
+    let context = glib::MainContext::new();
+    context
+        .with_thread_default(|| pending_updates_on(&context))
+        .context("Failed to set up a main context")?
+}
+
+fn pending_updates_on(context: &glib::MainContext) -> Result<Vec<String>> {
+    let connection = system_bus()?;
+    let reply = call(
+        &connection,
+        PACKAGEKIT_PATH,
+        PACKAGEKIT_NAME,
+        "CreateTransaction",
+        ().to_variant(),
+    )?;
+    let transaction = reply
+        .child_value(0)
+        .str()
+        .map(str::to_string)
+        .context("PackageKit returned no transaction")?;
+
+    let packages = Rc::new(RefCell::new(Vec::new()));
+    let error = Rc::new(RefCell::new(None));
+    let finished = Rc::new(Cell::new(false));
+    let subscription = {
+        let (packages, error, finished) = (packages.clone(), error.clone(), finished.clone());
+        connection.signal_subscribe(
+            Some(PACKAGEKIT_NAME),
+            Some(TRANSACTION_INTERFACE),
+            None,
+            Some(&transaction),
+            None,
+            gio::DBusSignalFlags::NONE,
+            move |_, _, _, _, signal, parameters| match signal {
+                "Package" => {
+                    if let Some((_, id, _)) = parameters.get::<(u32, String, String)>() {
+                        packages.borrow_mut().push(package_label(&id));
+                    }
+                }
+                "ErrorCode" => {
+                    if let Some((_, details)) = parameters.get::<(u32, String)>() {
+                        *error.borrow_mut() = Some(details);
+                    }
+                }
+                "Finished" | "Destroy" => finished.set(true),
+                _ => {}
+            },
+        )
+    };
+
+    let timed_out = Arc::new(AtomicBool::new(false));
+    let timeout = {
+        let timed_out = timed_out.clone();
+        let source = glib::timeout_source_new(TRANSACTION_TIMEOUT, None, glib::Priority::DEFAULT, move || {
+            timed_out.store(true, Ordering::Relaxed);
+            glib::ControlFlow::Break
+        });
+        source.attach(Some(context));
+        source
+    };
+
+    let result = call(
+        &connection,
+        &transaction,
+        TRANSACTION_INTERFACE,
+        "GetUpdates",
+        (FILTER_NONE,).to_variant(),
+    );
+    while result.is_ok() && !finished.get() && !timed_out.load(Ordering::Relaxed) {
+        context.iteration(true);
+    }
+    timeout.destroy();
+    connection.signal_unsubscribe(subscription);
+
+    result?;
+    if let Some(details) = error.take() {
+        bail!("PackageKit: {}", details);
+    }
+    if !finished.get() {
+        bail!(
+            "PackageKit did not answer within {} seconds",
+            TRANSACTION_TIMEOUT.as_secs()
+        );
+    }
+    Ok(packages.take())
+}
+
+// Package IDs are "name;version;arch;repository"
+fn package_label(id: &str) -> String {
+    let mut parts = id.split(';');
+    match (parts.next(), parts.next()) {
+        (Some(name), Some(version)) if !version.is_empty() => format!("{} {}", name, version),
+        _ => id.to_string(),
+    }
+}
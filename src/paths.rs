@@ -0,0 +1,222 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+const APP_DIR: &str = "about-this-linux";
+const CONFIG_FILE: &str = "overview-conf.json";
+// The logo keeps the extension of its format, e.g. distro-logo.svg
+const LOGO_NAME: &str = "distro-logo";
+const BACKUPS_DIR: &str = "backups";
+const LOGOS_DIR: &str = "logos";
+const PROFILES_DIR: &str = "profiles";
+const STATE_FILE: &str = "window-state.json";
+
+// Compiled in from data/resources.gresource.xml
+pub const ICONS_RESOURCE: &str = "/com/novik/about-this-linux/icons";
+pub const TUX_LOGO_RESOURCE: &str = "/com/novik/about-this-linux/icons/tux-logo.png";
+pub const LOGOS_RESOURCE: &str = "/com/novik/about-this-linux/logos";
+
+/// `$XDG_CONFIG_HOME/about-this-linux`, created on first use
+pub fn config_dir() -> PathBuf {
+    let dir = dirs::config_dir()
+        .unwrap_or_else(|| home_dir().join(".config"))
+        .join(APP_DIR);
+    std::fs::create_dir_all(&dir).unwrap_or_default();
+    dir
+}
+
+/// `$XDG_CACHE_HOME/about-this-linux`, created on first use
+pub fn cache_dir() -> PathBuf {
+    let dir = dirs::cache_dir()
+        .unwrap_or_else(|| home_dir().join(".cache"))
+        .join(APP_DIR);
+    std::fs::create_dir_all(&dir).unwrap_or_default();
+    dir
+}
+
+/// `$XDG_RUNTIME_DIR/about-this-linux`, or the cache directory where there
+/// is no runtime directory; created on first use
+pub fn runtime_dir() -> PathBuf {
+    let dir = match dirs::runtime_dir() {
+        Some(dir) => dir.join(APP_DIR),
+        None => return cache_dir(),
+    };
+    std::fs::create_dir_all(&dir).unwrap_or_default();
+    dir
+}
+
+/// `$XDG_STATE_HOME/about-this-linux`, created on first use
+pub fn state_dir() -> PathBuf {
+    let dir = dirs::state_dir()
+        .unwrap_or_else(|| home_dir().join(".local/state"))
+        .join(APP_DIR);
+    std::fs::create_dir_all(&dir).unwrap_or_default();
+    dir
+}
+
+/// `$XDG_DATA_HOME`, where per-user desktop entries and icons go
+pub fn data_home() -> PathBuf {
+    dirs::data_dir().unwrap_or_else(|| home_dir().join(".local/share"))
+}
+
+/// `$XDG_CONFIG_HOME/autostart`, read by the session at login
+pub fn autostart_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| home_dir().join(".config"))
+        .join("autostart")
+}
+
+// Set from --profile
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Make `default_config_path` the named profile for the rest of the run
+pub fn use_profile(name: &str) {
+    let _ = PROFILE.set(name.to_string());
+}
+
+/// The profile picked with --profile, if any
+pub fn current_profile() -> Option<&'static str> {
+    PROFILE.get().map(String::as_str)
+}
+
+pub fn default_config_path() -> PathBuf {
+    match current_profile() {
+        Some(name) => profile_path(name),
+        None => main_config_path(),
+    }
+}
+
+/// The config used without --profile
+pub fn main_config_path() -> PathBuf {
+    config_dir().join(CONFIG_FILE)
+}
+
+/// `profiles/` in the config directory, created on first use
+pub fn profiles_dir() -> PathBuf {
+    let dir = config_dir().join(PROFILES_DIR);
+    std::fs::create_dir_all(&dir).unwrap_or_default();
+    dir
+}
+
+/// A named profile's config, e.g. `profiles/work.json`
+pub fn profile_path(name: &str) -> PathBuf {
+    profiles_dir().join(format!("{}.json", name))
+}
+
+/// Last tab, size and monitor of the main window
+pub fn state_path() -> PathBuf {
+    state_dir().join(STATE_FILE)
+}
+
+/// Where an imported logo is kept, with the extension of its format
+pub fn logo_cache_path(extension: &str) -> PathBuf {
+    cache_dir().join(format!("{}.{}", LOGO_NAME, extension))
+}
+
+/// Where a logo picked from the gallery is unpacked, e.g. `logos/arch.svg`
+pub fn gallery_logo_path(id: &str) -> PathBuf {
+    let dir = config_dir().join(LOGOS_DIR);
+    std::fs::create_dir_all(&dir).unwrap_or_default();
+    dir.join(format!("{}.svg", id))
+}
+
+/// Where the logo editor writes the cropped logo, next to the config so
+/// clearing the cache doesn't lose it
+pub fn logo_path(extension: &str) -> PathBuf {
+    config_dir().join(format!("{}.{}", LOGO_NAME, extension))
+}
+
+/// Expand a leading `~` or `$HOME` and make relative paths relative to
+/// `base_dir`, so configs can be shared between users and machines
+pub fn expand(value: &str, base_dir: &Path) -> String {
+    if value.is_empty() || value.contains("://") {
+        return value.to_string();
+    }
+
+    let home_relative = ["~/", "$HOME/", "${HOME}/"]
+        .iter()
+        .find_map(|prefix| value.strip_prefix(prefix));
+    let path = match home_relative {
+        Some(rest) => home_dir().join(rest),
+        None if matches!(value, "~" | "$HOME" | "${HOME}") => home_dir(),
+        None => base_dir.join(value),
+    };
+    path.to_string_lossy().to_string()
+}
+
+fn home_dir() -> PathBuf {
+    dirs::home_dir().unwrap_or_else(|| PathBuf::from("/tmp"))
+}
+
+// Versions before XDG support kept everything here
+fn legacy_dir() -> PathBuf {
+    home_dir().join(".local/share").join(APP_DIR)
+}
+
+/// Move files from the pre-XDG location into the config and cache
+/// directories. Files that already exist at the new location win.
+pub fn migrate_legacy() {
+    let legacy = legacy_dir();
+    if !legacy.is_dir() {
+        return;
+    }
+
+    let config_path = default_config_path();
+    let migrated_config = move_if_absent(&legacy.join(CONFIG_FILE), &config_path);
+    move_if_absent(&legacy.join(BACKUPS_DIR), &config_dir().join(BACKUPS_DIR));
+
+    let legacy_logo = legacy.join(format!("{}.png", LOGO_NAME));
+    let logo_path = logo_cache_path("png");
+    if move_if_absent(&legacy_logo, &logo_path) && migrated_config {
+        if let Err(e) = repoint_logo(&config_path, &legacy_logo, &logo_path) {
+            eprintln!("Failed to update logo path in migrated config: {}", e);
+        }
+    }
+
+    // Only succeeds once everything has been moved out
+    let _ = std::fs::remove_dir(&legacy);
+}
+
+fn move_if_absent(from: &Path, to: &Path) -> bool {
+    if !from.exists() || to.exists() {
+        return false;
+    }
+
+    // rename fails across filesystems; fall back to copy and delete for files
+    let result = std::fs::rename(from, to).or_else(|e| {
+        if from.is_file() {
+            std::fs::copy(from, to).and_then(|_| std::fs::remove_file(from))
+        } else {
+            Err(e)
+        }
+    });
+
+    match result {
+        Ok(()) => {
+            eprintln!("Migrated {} to {}", from.display(), to.display());
+            true
+        }
+        Err(e) => {
+            eprintln!("Failed to migrate {}: {}", from.display(), e);
+            false
+        }
+    }
+}
+
+// Edit the JSON directly so unknown keys survive and no backup is made
+fn repoint_logo(config_path: &Path, old_logo: &Path, new_logo: &Path) -> anyhow::Result<()> {
+    let content = std::fs::read_to_string(config_path)?;
+    let mut json: serde_json::Value = serde_json::from_str(&content)?;
+
+    let points_at_old = json
+        .get("distro_image_path")
+        .and_then(|value| value.as_str())
+        .map(|path| Path::new(path) == old_logo)
+        .unwrap_or(false);
+
+    if points_at_old {
+        json["distro_image_path"] = serde_json::Value::String(new_logo.to_string_lossy().to_string());
+        std::fs::write(config_path, serde_json::to_string_pretty(&json)?)?;
+    }
+
+    Ok(())
+}
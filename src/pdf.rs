@@ -0,0 +1,59 @@
+use anyhow::{Context, Result};
+use gtk::prelude::*;
+use gtk::{gdk, pango};
+use std::cell::Cell;
+use std::path::Path;
+use std::rc::Rc;
+
+// Monospace keeps the padded "Name:  value" columns of the report aligned
+const FONT: &str = "Monospace 9";
+
+/// Write `text` to `path` as a PDF, split over as many pages as it needs.
+/// Goes through GTK's print backend, so no window has to be shown.
+pub fn export(text: &str, path: &Path) -> Result<()> {
+    let lines: Rc<Vec<String>> = Rc::new(text.lines().map(str::to_string).collect());
+    let lines_per_page = Rc::new(Cell::new(1));
+
+    let operation = gtk::PrintOperation::new();
+    operation.set_export_filename(path);
+    operation.set_unit(gtk::Unit::Points);
+
+    let total = lines.len();
+    let per_page = lines_per_page.clone();
+    operation.connect_begin_print(move |operation, context| {
+        let layout = page_layout(context);
+        layout.set_text("X");
+        let line_height = layout.pixel_size().1.max(1) as f64;
+        per_page.set(((context.height() / line_height) as usize).max(1));
+        operation.set_n_pages(total.div_ceil(per_page.get()).max(1) as i32);
+    });
+
+    operation.connect_draw_page(move |_, context, page| {
+        let per_page = lines_per_page.get();
+        let start = (page as usize * per_page).min(lines.len());
+        let end = (start + per_page).min(lines.len());
+
+        let layout = page_layout(context);
+        layout.set_text(&lines[start..end].join("\n"));
+        let snapshot = gtk::Snapshot::new();
+        snapshot.append_layout(&layout, &gdk::RGBA::BLACK);
+        if let Some(node) = snapshot.to_node() {
+            node.draw(&context.cairo_context());
+        }
+    });
+
+    operation
+        .run(gtk::PrintOperationAction::Export, None::<&gtk::Window>)
+        .with_context(|| format!("Failed to export PDF: {}", path.display()))?;
+    Ok(())
+}
+
+// Long values are cut off rather than wrapped, so every text line is one
+// line on the page
+fn page_layout(context: &gtk::PrintContext) -> pango::Layout {
+    let layout = context.create_pango_layout();
+    layout.set_font_description(Some(&pango::FontDescription::from_string(FONT)));
+    layout.set_width((context.width() * pango::SCALE as f64) as i32);
+    layout.set_ellipsize(pango::EllipsizeMode::End);
+    layout
+}
@@ -0,0 +1,246 @@
+use gtk::prelude::*;
+use gtk::{gdk, glib, ApplicationWindow};
+use std::cell::Cell;
+use std::time::Duration;
+
+use crate::config::{Config, Placement};
+use crate::host;
+
+// GTK4 can't position toplevels itself, so the move is delegated to the
+// window manager: sway/i3 and Hyprland through their IPC tools, anything
+// else on X11 through xdotool.
+const WINDOW_TITLE_PATTERN: &str = "^About this Linux$";
+
+enum Compositor {
+    Sway,
+    I3,
+    Hyprland,
+    X11,
+    Unsupported,
+}
+
+fn detect_compositor() -> Compositor {
+    let has_env = |name: &str| std::env::var_os(name).is_some();
+
+    if has_env("SWAYSOCK") {
+        Compositor::Sway
+    } else if has_env("HYPRLAND_INSTANCE_SIGNATURE") {
+        Compositor::Hyprland
+    } else if has_env("I3SOCK") {
+        Compositor::I3
+    } else if has_env("DISPLAY") && !has_env("WAYLAND_DISPLAY") {
+        Compositor::X11
+    } else {
+        Compositor::Unsupported
+    }
+}
+
+/// Move the window onto the configured monitor once it has been mapped
+pub fn apply(window: &ApplicationWindow, config: &Config) {
+    if config.placement == Placement::Default {
+        return;
+    }
+
+    let placement = config.placement;
+    let monitor = config.placement_monitor.clone();
+    window.connect_map(move |window| {
+        let window = window.clone();
+        let monitor = monitor.clone();
+        // Give the window manager a moment to manage the new window
+        glib::timeout_add_local_once(Duration::from_millis(100), move || {
+            let target = match placement {
+                Placement::Monitor => monitor,
+                Placement::Pointer => pointer_monitor(&window),
+                Placement::Default => None,
+            };
+
+            match target {
+                Some(connector) => move_to_monitor(&window, &connector),
+                None => eprintln!("Could not determine the monitor to open on"),
+            }
+        });
+    });
+}
+
+/// Move the window back onto the monitor it was last closed on, the first
+/// time it is shown. Does nothing if that monitor is gone.
+pub fn restore(window: &ApplicationWindow, connector: String) {
+    let restored = Cell::new(false);
+    window.connect_map(move |window| {
+        if restored.replace(true) {
+            return;
+        }
+        if !monitors(window).iter().any(|(name, _)| *name == connector) {
+            return;
+        }
+        let window = window.clone();
+        let connector = connector.clone();
+        glib::timeout_add_local_once(Duration::from_millis(100), move || {
+            move_to_monitor(&window, &connector);
+        });
+    });
+}
+
+/// Connector name of the monitor the window is on
+pub fn current_monitor(window: &ApplicationWindow) -> Option<String> {
+    if !window.is_realized() {
+        return None;
+    }
+    let surface = window.surface();
+    let monitor = WidgetExt::display(window).monitor_at_surface(&surface)?;
+    monitor.connector().map(|connector| connector.to_string())
+}
+
+fn pointer_monitor(window: &ApplicationWindow) -> Option<String> {
+    match detect_compositor() {
+        // Both track the focused output, which follows the pointer
+        Compositor::Sway | Compositor::I3 => {
+            let output = run_ipc(ipc_tool_for_i3_family(), &["-t", "get_outputs"])?;
+            focused_output_name(&output)
+        }
+        Compositor::Hyprland => {
+            let output = run_ipc("hyprctl", &["monitors", "-j"])?;
+            focused_output_name(&output)
+        }
+        Compositor::X11 => {
+            let output = run_ipc("xdotool", &["getmouselocation", "--shell"])?;
+            let mut x = None;
+            let mut y = None;
+            for line in output.lines() {
+                if let Some(value) = line.strip_prefix("X=") {
+                    x = value.trim().parse::<i32>().ok();
+                } else if let Some(value) = line.strip_prefix("Y=") {
+                    y = value.trim().parse::<i32>().ok();
+                }
+            }
+
+            let (x, y) = (x?, y?);
+            monitors(window).into_iter().find_map(|(connector, geometry)| {
+                let inside = x >= geometry.x()
+                    && x < geometry.x() + geometry.width()
+                    && y >= geometry.y()
+                    && y < geometry.y() + geometry.height();
+                if inside {
+                    Some(connector)
+                } else {
+                    None
+                }
+            })
+        }
+        Compositor::Unsupported => None,
+    }
+}
+
+fn move_to_monitor(window: &ApplicationWindow, connector: &str) {
+    // The name goes into sway and Hyprland command lines, where `,` and `;`
+    // would start another command
+    if !connector.chars().all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c)) {
+        eprintln!("Ignoring invalid monitor name {:?}", connector);
+        return;
+    }
+
+    let result = match detect_compositor() {
+        Compositor::Sway | Compositor::I3 => {
+            let command = format!(
+                "[title=\"{}\"] floating enable, move container to output {}, move position center",
+                WINDOW_TITLE_PATTERN, connector
+            );
+            run_ipc(ipc_tool_for_i3_family(), &[&command])
+        }
+        Compositor::Hyprland => {
+            let batch = format!(
+                "dispatch movewindow mon:{} ; dispatch centerwindow",
+                connector
+            );
+            run_ipc("hyprctl", &["--batch", &batch])
+        }
+        Compositor::X11 => {
+            let geometry = match monitors(window)
+                .into_iter()
+                .find(|(name, _)| name == connector)
+            {
+                Some((_, geometry)) => geometry,
+                None => {
+                    eprintln!("Monitor {} not found", connector);
+                    return;
+                }
+            };
+
+            let (default_width, default_height) = window.default_size();
+            let width = if window.width() > 0 { window.width() } else { default_width };
+            let height = if window.height() > 0 { window.height() } else { default_height };
+            let x = geometry.x() + (geometry.width() - width).max(0) / 2;
+            let y = geometry.y() + (geometry.height() - height).max(0) / 2;
+
+            run_ipc(
+                "xdotool",
+                &[
+                    "search",
+                    "--name",
+                    WINDOW_TITLE_PATTERN,
+                    "windowmove",
+                    "%@",
+                    &x.to_string(),
+                    &y.to_string(),
+                ],
+            )
+        }
+        Compositor::Unsupported => {
+            eprintln!("Window placement is not supported by this compositor");
+            return;
+        }
+    };
+
+    if result.is_none() {
+        eprintln!("Failed to move window to monitor {}", connector);
+    }
+}
+
+fn ipc_tool_for_i3_family() -> &'static str {
+    match detect_compositor() {
+        Compositor::I3 => "i3-msg",
+        _ => "swaymsg",
+    }
+}
+
+fn run_ipc(tool: &str, args: &[&str]) -> Option<String> {
+    match host::command(tool).args(args).output() {
+        Ok(output) if output.status.success() => {
+            Some(String::from_utf8_lossy(&output.stdout).to_string())
+        }
+        Ok(output) => {
+            eprintln!(
+                "{} failed: {}",
+                tool,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            None
+        }
+        Err(e) => {
+            eprintln!("Failed to run {}: {}", tool, e);
+            None
+        }
+    }
+}
+
+fn focused_output_name(json: &str) -> Option<String> {
+    let outputs: serde_json::Value = serde_json::from_str(json).ok()?;
+    outputs.as_array()?.iter().find_map(|output| {
+        if output.get("focused")?.as_bool()? {
+            output.get("name")?.as_str().map(|name| name.to_string())
+        } else {
+            None
+        }
+    })
+}
+
+fn monitors(window: &ApplicationWindow) -> Vec<(String, gdk::Rectangle)> {
+    let model = WidgetExt::display(window).monitors();
+    (0..model.n_items())
+        .filter_map(|i| model.item(i).and_downcast::<gdk::Monitor>())
+        .filter_map(|monitor| {
+            let connector = monitor.connector()?;
+            Some((connector.to_string(), monitor.geometry()))
+        })
+        .collect()
+}
@@ -0,0 +1,459 @@
+use anyhow::{bail, Context, Result};
+use gtk::prelude::*;
+use gtk::{gio, glib};
+use std::cell::Cell;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::journal::{self, Outcome};
+use crate::{host, system_info, units};
+
+// Batteries and AC adapters come from UPower on the system bus, which also
+// announces every change. Without it they are read from sysfs and udev
+// reports the changes.
+const UPOWER_NAME: &str = "org.freedesktop.UPower";
+const UPOWER_PATH: &str = "/org/freedesktop/UPower";
+const DEVICE_INTERFACE: &str = "org.freedesktop.UPower.Device";
+const PROPERTIES_INTERFACE: &str = "org.freedesktop.DBus.Properties";
+const CALL_TIMEOUT_MS: i32 = 2000;
+const POWER_SUPPLY_DIR: &str = "/sys/class/power_supply";
+const SUBSYSTEM_POWER_SUPPLY: &str = "power_supply";
+
+// UPower updates several properties at once, and a battery every few
+// seconds while charging; wait for a burst to end before reporting it
+const SETTLE_DELAY: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    LinePower,
+    Battery,
+    Ups,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChargeState {
+    Unknown,
+    Charging,
+    Discharging,
+    Empty,
+    Full,
+    NotCharging,
+}
+
+#[derive(Debug, Clone)]
+pub struct PowerDevice {
+    pub kind: Kind,
+    // Kernel name, e.g. BAT0 or AC
+    pub name: String,
+    pub vendor: String,
+    pub model: String,
+    pub technology: String,
+    // Whether an adapter is plugged in; always false for batteries
+    pub online: bool,
+    pub percentage: Option<f64>,
+    pub state: ChargeState,
+    pub energy_wh: Option<f64>,
+    pub energy_full_wh: Option<f64>,
+    pub energy_full_design_wh: Option<f64>,
+    pub rate_w: Option<f64>,
+    pub time_to_empty: Option<Duration>,
+    pub time_to_full: Option<Duration>,
+    pub cycles: Option<u32>,
+}
+
+impl PowerDevice {
+    /// "87%, charging, 1 h 12 min until full"
+    pub fn charge_text(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(percentage) = self.percentage {
+            parts.push(format!("{:.0}%", percentage));
+        }
+        match (self.state, self.time_to_full, self.time_to_empty) {
+            (ChargeState::Charging, Some(time), _) => {
+                parts.push(format!("charging, {} until full", units::format_duration(time)))
+            }
+            (ChargeState::Discharging, _, Some(time)) => {
+                parts.push(format!("{} remaining", units::format_duration(time)))
+            }
+            (ChargeState::Charging, _, _) => parts.push("charging".to_string()),
+            (ChargeState::Discharging, _, _) => parts.push("discharging".to_string()),
+            (ChargeState::Full, _, _) => parts.push("fully charged".to_string()),
+            (ChargeState::Empty, _, _) => parts.push("empty".to_string()),
+            (ChargeState::NotCharging, _, _) => parts.push("not charging".to_string()),
+            (ChargeState::Unknown, _, _) => {}
+        }
+        parts.join(", ")
+    }
+
+    /// Full charge capacity as a share of the design capacity, i.e. how much
+    /// the battery has worn
+    pub fn health_percent(&self) -> Option<f64> {
+        match (self.energy_full_wh, self.energy_full_design_wh) {
+            (Some(full), Some(design)) if design > 0.0 => Some((full / design * 100.0).min(100.0)),
+            _ => None,
+        }
+    }
+}
+
+/// Every battery, UPS and AC adapter powering the machine. Peripherals such
+/// as mice and headsets are left out.
+pub fn detect() -> Result<Vec<PowerDevice>> {
+    if system_info::detection_disabled() {
+        bail!("detection is disabled (--no-detect)");
+    }
+    match journal::timed("power devices", "upower", detect_upower) {
+        Ok(devices) => Ok(devices),
+        Err(e) => {
+            journal::record(
+                "power devices",
+                "sysfs",
+                Duration::ZERO,
+                Outcome::Fallback(format!("UPower unavailable ({:#}), reading {}", e, POWER_SUPPLY_DIR)),
+            );
+            journal::timed("power devices", "sysfs", detect_sysfs)
+        }
+    }
+}
+
+/// The charge of the first battery, for the overview
+pub fn battery_summary() -> Option<String> {
+    let devices = detect().ok()?;
+    let battery = devices.iter().find(|device| device.kind == Kind::Battery)?;
+    Some(battery.charge_text())
+}
+
+pub fn has_battery() -> bool {
+    detect()
+        .map(|devices| devices.iter().any(|device| device.kind != Kind::LinePower))
+        .unwrap_or(false)
+}
+
+/// True when running from a battery: no adapter is online and a battery is
+/// discharging
+pub fn on_battery() -> bool {
+    if let Ok(on_battery) = upower_on_battery() {
+        return on_battery;
+    }
+    let devices = match detect_sysfs() {
+        Ok(devices) => devices,
+        Err(_) => return false,
+    };
+    !devices
+        .iter()
+        .any(|device| device.kind == Kind::LinePower && device.online)
+        && devices
+            .iter()
+            .any(|device| device.kind == Kind::Battery && device.state == ChargeState::Discharging)
+}
+
+/// Call `on_change` whenever a battery or adapter changes, e.g. when the
+/// charge goes up or the adapter is plugged in
+pub fn watch<F: Fn() + 'static>(on_change: F) -> Result<()> {
+    let pending = Rc::new(Cell::new(false));
+    let on_change = Rc::new(on_change);
+    let changed = move || {
+        if pending.replace(true) {
+            return;
+        }
+        let pending = pending.clone();
+        let on_change = on_change.clone();
+        glib::timeout_add_local_once(SETTLE_DELAY, move || {
+            pending.set(false);
+            on_change();
+        });
+    };
+
+    // UPower is started on demand, so asking it for the devices starts it
+    match system_bus().filter(|_| detect_upower().is_ok()) {
+        Some(connection) => {
+            // Device properties, devices coming and going and OnBattery
+            connection.signal_subscribe(
+                Some(UPOWER_NAME),
+                None,
+                None,
+                None,
+                None,
+                gio::DBusSignalFlags::NONE,
+                move |_, _, _, _, _, _| changed(),
+            );
+            Ok(())
+        }
+        None => watch_udev(changed),
+    }
+}
+
+fn watch_udev(changed: impl Fn() + 'static) -> Result<()> {
+    let socket = udev::MonitorBuilder::new()
+        .and_then(|builder| builder.match_subsystem(SUBSYSTEM_POWER_SUPPLY))
+        .and_then(|builder| builder.listen())
+        .context("Failed to set up udev monitor")?;
+
+    let fd = socket.as_raw_fd();
+    glib::source::unix_fd_add_local(fd, glib::IOCondition::IN, move |_, _| {
+        if socket.iter().count() > 0 {
+            changed();
+        }
+        glib::ControlFlow::Continue
+    });
+    Ok(())
+}
+
+fn system_bus() -> Option<gio::DBusConnection> {
+    gio::bus_get_sync(gio::BusType::System, gio::Cancellable::NONE).ok()
+}
+
+fn call(
+    connection: &gio::DBusConnection,
+    path: &str,
+    interface: &str,
+    method: &str,
+    args: glib::Variant,
+) -> Result<glib::Variant> {
+    connection
+        .call_sync(
+            Some(UPOWER_NAME),
+            path,
+            interface,
+            method,
+            Some(&args),
+            None,
+            gio::DBusCallFlags::NONE,
+            CALL_TIMEOUT_MS,
+            gio::Cancellable::NONE,
+        )
+        .with_context(|| format!("{} failed", method))
+}
+
+fn upower_on_battery() -> Result<bool> {
+    let connection = system_bus().context("No system bus")?;
+    let reply = call(
+        &connection,
+        UPOWER_PATH,
+        PROPERTIES_INTERFACE,
+        "Get",
+        (UPOWER_NAME, "OnBattery").to_variant(),
+    )?;
+    reply
+        .child_value(0)
+        .as_variant()
+        .and_then(|value| value.get::<bool>())
+        .context("OnBattery is not a boolean")
+}
+
+fn detect_upower() -> Result<Vec<PowerDevice>> {
+    let connection = system_bus().context("No system bus")?;
+    let reply = call(
+        &connection,
+        UPOWER_PATH,
+        UPOWER_NAME,
+        "EnumerateDevices",
+        ().to_variant(),
+    )?;
+
+    let mut devices = Vec::new();
+    for path in reply.child_value(0).iter() {
+        let path = match path.str() {
+            Some(path) => path.to_string(),
+            None => continue,
+        };
+        let reply = call(
+            &connection,
+            &path,
+            PROPERTIES_INTERFACE,
+            "GetAll",
+            (DEVICE_INTERFACE,).to_variant(),
+        )?;
+        if let Some(device) = upower_device(&reply.child_value(0)) {
+            devices.push(device);
+        }
+    }
+    Ok(devices)
+}
+
+// Built from the a{sv} of org.freedesktop.UPower.Device properties
+fn upower_device(properties: &glib::Variant) -> Option<PowerDevice> {
+    let properties = glib::VariantDict::new(Some(properties));
+    let get = |name: &str| properties.lookup_value(name, None);
+    let text = |name: &str| {
+        get(name)
+            .and_then(|value| value.str().map(str::to_string))
+            .unwrap_or_default()
+    };
+    let number = |name: &str| {
+        get(name)
+            .and_then(|value| value.get::<f64>())
+            .filter(|value| *value > 0.0)
+    };
+    let seconds = |name: &str| {
+        get(name)
+            .and_then(|value| value.get::<i64>())
+            .filter(|seconds| *seconds > 0)
+            .map(|seconds| Duration::from_secs(seconds as u64))
+    };
+
+    if !get("PowerSupply")
+        .and_then(|value| value.get::<bool>())
+        .unwrap_or(false)
+    {
+        return None;
+    }
+    let kind = match get("Type").and_then(|value| value.get::<u32>())? {
+        1 => Kind::LinePower,
+        2 => Kind::Battery,
+        3 => Kind::Ups,
+        _ => return None,
+    };
+    if kind != Kind::LinePower && get("IsPresent").and_then(|value| value.get::<bool>()) == Some(false) {
+        return None;
+    }
+
+    let native_path = text("NativePath");
+    let state = match get("State").and_then(|value| value.get::<u32>()).unwrap_or(0) {
+        1 => ChargeState::Charging,
+        2 => ChargeState::Discharging,
+        3 => ChargeState::Empty,
+        4 => ChargeState::Full,
+        5 | 6 => ChargeState::NotCharging,
+        _ => ChargeState::Unknown,
+    };
+    let technology = match get("Technology").and_then(|value| value.get::<u32>()).unwrap_or(0) {
+        1 => "Lithium-ion",
+        2 => "Lithium polymer",
+        3 => "Lithium iron phosphate",
+        4 => "Lead acid",
+        5 => "Nickel cadmium",
+        6 => "Nickel metal hydride",
+        _ => "",
+    };
+
+    Some(PowerDevice {
+        kind,
+        name: Path::new(&native_path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or(native_path),
+        vendor: text("Vendor"),
+        model: text("Model"),
+        technology: technology.to_string(),
+        online: get("Online").and_then(|value| value.get::<bool>()).unwrap_or(false),
+        percentage: get("Percentage")
+            .and_then(|value| value.get::<f64>())
+            .filter(|_| kind != Kind::LinePower),
+        state,
+        energy_wh: number("Energy"),
+        energy_full_wh: number("EnergyFull"),
+        energy_full_design_wh: number("EnergyFullDesign"),
+        rate_w: number("EnergyRate"),
+        time_to_empty: seconds("TimeToEmpty"),
+        time_to_full: seconds("TimeToFull"),
+        cycles: get("ChargeCycles")
+            .and_then(|value| value.get::<i32>())
+            .filter(|cycles| *cycles > 0)
+            .map(|cycles| cycles as u32),
+    })
+}
+
+fn detect_sysfs() -> Result<Vec<PowerDevice>> {
+    let dir = host::path(POWER_SUPPLY_DIR);
+    let entries = std::fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+
+    let mut devices: Vec<PowerDevice> = entries
+        .flatten()
+        .filter_map(|entry| sysfs_device(&entry.path()))
+        .collect();
+    devices.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(devices)
+}
+
+fn sysfs_device(path: &Path) -> Option<PowerDevice> {
+    let read = |name: &str| {
+        std::fs::read_to_string(path.join(name))
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default()
+    };
+    // Micro-units in sysfs
+    let micro = |name: &str| {
+        read(name)
+            .parse::<f64>()
+            .ok()
+            .filter(|value| *value > 0.0)
+            .map(|value| value / 1e6)
+    };
+
+    let kind = match read("type").as_str() {
+        "Mains" | "USB" => Kind::LinePower,
+        "Battery" => Kind::Battery,
+        "UPS" => Kind::Ups,
+        _ => return None,
+    };
+    // Batteries of wireless peripherals
+    if read("scope") == "Device" || (kind != Kind::LinePower && read("present") == "0") {
+        return None;
+    }
+
+    let name = path.file_name()?.to_string_lossy().to_string();
+    let mut device = PowerDevice {
+        kind,
+        name,
+        vendor: read("manufacturer"),
+        model: read("model_name"),
+        technology: match read("technology").as_str() {
+            "Li-ion" => "Lithium-ion",
+            "Li-poly" => "Lithium polymer",
+            "LiFe" => "Lithium iron phosphate",
+            "NiCd" => "Nickel cadmium",
+            "NiMH" => "Nickel metal hydride",
+            _ => "",
+        }
+        .to_string(),
+        online: kind == Kind::LinePower && read("online") == "1",
+        percentage: None,
+        state: ChargeState::Unknown,
+        energy_wh: None,
+        energy_full_wh: None,
+        energy_full_design_wh: None,
+        rate_w: None,
+        time_to_empty: None,
+        time_to_full: None,
+        cycles: read("cycle_count").parse().ok().filter(|cycles| *cycles > 0),
+    };
+    if kind == Kind::LinePower {
+        return Some(device);
+    }
+
+    device.state = match read("status").as_str() {
+        "Charging" => ChargeState::Charging,
+        "Discharging" => ChargeState::Discharging,
+        "Full" => ChargeState::Full,
+        "Not charging" => ChargeState::NotCharging,
+        _ => ChargeState::Unknown,
+    };
+
+    // Some batteries report charge (µAh) and voltage instead of energy
+    let volts = micro("voltage_min_design").or_else(|| micro("voltage_now"));
+    let energy = |energy: &str, charge: &str| micro(energy).or_else(|| Some(micro(charge)? * volts?));
+    device.energy_wh = energy("energy_now", "charge_now");
+    device.energy_full_wh = energy("energy_full", "charge_full");
+    device.energy_full_design_wh = energy("energy_full_design", "charge_full_design");
+    device.rate_w = micro("power_now").or_else(|| Some(micro("current_now")? * micro("voltage_now")?));
+    device.percentage = read("capacity")
+        .parse()
+        .ok()
+        .or_else(|| Some((device.energy_wh? / device.energy_full_wh? * 100.0).min(100.0)));
+
+    // Estimated like UPower does, from the current rate
+    if let (Some(energy), Some(rate)) = (device.energy_wh, device.rate_w) {
+        let hours = |wh: f64| Duration::from_secs_f64(wh / rate * 3600.0);
+        match device.state {
+            ChargeState::Discharging => device.time_to_empty = Some(hours(energy)),
+            ChargeState::Charging => {
+                device.time_to_full = device.energy_full_wh.map(|full| hours((full - energy).max(0.0)))
+            }
+            _ => {}
+        }
+    }
+
+    Some(device)
+}
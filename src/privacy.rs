@@ -0,0 +1,29 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::config::Config;
+
+pub const MASK: &str = "••••••••";
+
+// Set from --redact, which hides both values for the run regardless of the config
+static REDACT_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+pub fn request_redact() {
+    REDACT_REQUESTED.store(true, Ordering::Relaxed);
+}
+
+pub fn hides_serial(config: &Config) -> bool {
+    config.privacy_mode || REDACT_REQUESTED.load(Ordering::Relaxed)
+}
+
+pub fn hides_hostname(config: &Config) -> bool {
+    (config.privacy_mode && config.privacy_hide_hostname) || REDACT_REQUESTED.load(Ordering::Relaxed)
+}
+
+/// `value`, or the mask when `hidden` and there is something to hide
+pub fn masked(value: &str, hidden: bool) -> String {
+    if hidden && !value.is_empty() {
+        MASK.to_string()
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,152 @@
+use anyhow::{bail, Context, Result};
+use gtk::prelude::*;
+use gtk::{gio, glib, MessageDialog, Window};
+
+use crate::{host, utils};
+
+// hw-probe collects the hardware list, logs and a few configs, anonymises
+// serial numbers and addresses, and uploads it to the community database
+const HW_PROBE: &str = "hw-probe";
+const DATABASE_URL: &str = "https://linux-hardware.org";
+const URL_PREFIX: &str = "Probe URL:";
+
+/// The "Share hardware probe…" action is only offered with hw-probe installed
+pub fn available() -> bool {
+    host::find_program(HW_PROBE).is_some()
+}
+
+/// Ask for consent, then collect and upload a probe and show where it went
+pub fn share(parent: &Window) {
+    let dialog = MessageDialog::new(
+        Some(parent),
+        gtk::DialogFlags::MODAL,
+        gtk::MessageType::Question,
+        gtk::ButtonsType::None,
+        "Share a hardware probe?",
+    );
+    dialog.set_secondary_use_markup(true);
+    dialog.set_secondary_text(Some(&format!(
+        "hw-probe will collect the list of devices, their drivers and boot logs and upload them \
+        publicly to <a href=\"{url}\">{url}</a>, where they help others find hardware that works on Linux.\n\n\
+        Serial numbers, MAC and IP addresses are anonymised before the upload. Collecting everything \
+        needs administrator rights, so you may be asked for your password.",
+        url = DATABASE_URL
+    )));
+    dialog.add_button("Cancel", gtk::ResponseType::Cancel);
+    dialog.add_button("Upload", gtk::ResponseType::Accept);
+    dialog.set_default_response(gtk::ResponseType::Cancel);
+
+    let parent = parent.clone();
+    dialog.connect_response(move |dialog, response| {
+        dialog.close();
+        if response == gtk::ResponseType::Accept {
+            upload(&parent);
+        }
+    });
+    dialog.present();
+}
+
+fn upload(parent: &Window) {
+    let progress = MessageDialog::new(
+        Some(parent),
+        gtk::DialogFlags::MODAL,
+        gtk::MessageType::Info,
+        gtk::ButtonsType::None,
+        "Collecting and uploading the hardware probe…",
+    );
+    progress.set_secondary_text(Some("This can take a minute."));
+    progress.set_deletable(false);
+    progress.present();
+
+    let parent = parent.clone();
+    glib::spawn_future_local(async move {
+        let result = gio::spawn_blocking(run_hw_probe).await;
+        progress.close();
+        match result {
+            Ok(Ok(url)) => show_probe_url(&parent, &url),
+            Ok(Err(e)) => {
+                eprintln!("Failed to share the hardware probe: {:#}", e);
+                utils::show_error_dialog(
+                    Some(&parent),
+                    "Share Hardware Probe",
+                    &format!("The hardware probe could not be uploaded.\n\n{:#}", e),
+                );
+            }
+            Err(_) => eprintln!("Hardware probe task panicked"),
+        }
+    });
+}
+
+// pkexec for the full probe; without it hw-probe still collects what a
+// regular user can read
+fn run_hw_probe() -> Result<String> {
+    let mut command = if host::find_program("pkexec").is_some() {
+        let mut command = host::command("pkexec");
+        command.arg(HW_PROBE);
+        command
+    } else {
+        host::command(HW_PROBE)
+    };
+    let output = command
+        .args(["-all", "-upload"])
+        .output()
+        .context("Failed to run hw-probe")?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if let Some(url) = stdout
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(URL_PREFIX))
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+    {
+        return Ok(url.to_string());
+    }
+
+    // pkexec exits with 126 when the password prompt is dismissed
+    if output.status.code() == Some(126) {
+        bail!("Authorisation was cancelled");
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let last_line = stderr
+        .lines()
+        .chain(stdout.lines())
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or("no output");
+    bail!(
+        "hw-probe did not report a probe URL ({}): {}",
+        output.status,
+        last_line.trim()
+    )
+}
+
+fn show_probe_url(parent: &Window, url: &str) {
+    let dialog = MessageDialog::new(
+        Some(parent),
+        gtk::DialogFlags::MODAL,
+        gtk::MessageType::Info,
+        gtk::ButtonsType::None,
+        "Hardware probe uploaded",
+    );
+    dialog.set_secondary_use_markup(true);
+    let escaped = utils::escape_markup(url);
+    dialog.set_secondary_text(Some(&format!(
+        "Thank you for sharing. Your probe is at\n<a href=\"{0}\">{0}</a>",
+        escaped
+    )));
+    dialog.add_button("Copy URL", gtk::ResponseType::Apply);
+    dialog.add_button("Open", gtk::ResponseType::Yes);
+    dialog.add_button("Close", gtk::ResponseType::Close);
+    dialog.set_default_response(gtk::ResponseType::Yes);
+
+    let url = url.to_string();
+    dialog.connect_response(move |dialog, response| {
+        match response {
+            gtk::ResponseType::Apply => dialog.clipboard().set_text(&url),
+            gtk::ResponseType::Yes => utils::open_uri(dialog, &url),
+            _ => {}
+        }
+        dialog.close();
+    });
+    dialog.present();
+}
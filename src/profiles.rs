@@ -0,0 +1,30 @@
+use anyhow::{bail, Result};
+
+use crate::paths;
+
+/// Names of the saved profiles, sorted
+pub fn list() -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(paths::profiles_dir())
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+                .filter_map(|path| path.file_stem().map(|stem| stem.to_string_lossy().to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// Profile names become file names, so they are kept to a single plain one
+pub fn check_name(name: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        bail!("The profile needs a name");
+    }
+    if name.starts_with('.') || name.contains(['/', '\\']) {
+        bail!("\"{}\" can't be used as a profile name", name);
+    }
+    Ok(())
+}
@@ -0,0 +1,487 @@
+use anyhow::{bail, Context, Result};
+use std::io::Read;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use crate::config::{Config, ScriptFormat, ScriptProviderConfig};
+use crate::flatpak;
+use crate::host;
+use crate::power::{self, Kind};
+use crate::region;
+use crate::session;
+use crate::snap;
+use crate::system_info::{detection_disabled, process_count, DisplayInfo, DynamicSystemInfo, StorageInfo, SystemInfo};
+
+// Categories that MainWindow renders with dedicated pages. Providers in any
+// other category get a generic key/value page of their own.
+pub const CATEGORY_OVERVIEW: &str = "overview";
+pub const CATEGORY_DISPLAY: &str = "display";
+pub const CATEGORY_STORAGE: &str = "storage";
+pub const CATEGORY_POWER: &str = "power";
+// Not built in; script providers can use it to be refreshed on USB hotplug
+pub const CATEGORY_USB: &str = "usb";
+
+// Pages that only show what MainWindow lays out itself, so a script provider
+// put there would never be seen
+const RESERVED_CATEGORIES: [&str; 3] = [CATEGORY_OVERVIEW, CATEGORY_DISPLAY, CATEGORY_STORAGE];
+
+// A script that takes longer than this is killed
+const SCRIPT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Detection that can be moved to a worker thread
+pub type DetectJob = Box<dyn FnOnce() -> Result<Vec<(String, String)>> + Send>;
+
+/// A source of system information that can be rendered as a titled section.
+pub trait InfoProvider {
+    /// Section title shown above the provider's rows
+    fn name(&self) -> &str;
+
+    /// Stack page the provider belongs to (e.g. "overview", "storage")
+    fn category(&self) -> &str;
+
+    /// Run detection and return the rows as (key, value) pairs
+    fn detect(&self) -> Result<Vec<(String, String)>>;
+
+    /// The same detection as a job for a worker thread, for providers that
+    /// may keep the main thread waiting, such as scripts
+    fn detect_job(&self) -> Option<DetectJob> {
+        None
+    }
+}
+
+pub struct ProviderRegistry {
+    providers: Vec<Box<dyn InfoProvider>>,
+}
+
+impl ProviderRegistry {
+    pub fn new() -> Self {
+        ProviderRegistry {
+            providers: Vec::new(),
+        }
+    }
+
+    pub fn with_builtin() -> Self {
+        let mut registry = ProviderRegistry::new();
+        registry.register(Box::new(HardwareProvider));
+        registry.register(Box::new(DistroProvider));
+        registry.register(Box::new(SessionProvider));
+        registry.register(Box::new(RegionProvider));
+        registry.register(Box::new(DisplayProvider));
+        registry.register(Box::new(StorageProvider));
+        registry.register(Box::new(PowerProvider));
+        registry
+    }
+
+    /// Built-in providers plus any script providers declared in the config,
+    /// or none at all when detection is disabled
+    pub fn from_config(config: &Config) -> Self {
+        if detection_disabled() {
+            return ProviderRegistry::new();
+        }
+        let mut registry = ProviderRegistry::with_builtin();
+        for script in &config.custom_providers {
+            let provider = ScriptProvider::new(script.clone());
+            if RESERVED_CATEGORIES.contains(&provider.category()) {
+                eprintln!(
+                    "Ignoring custom provider \"{}\": the {} page only shows built-in information",
+                    provider.name(),
+                    provider.category()
+                );
+                continue;
+            }
+            registry.register(Box::new(provider));
+        }
+        registry
+    }
+
+    pub fn register(&mut self, provider: Box<dyn InfoProvider>) {
+        self.providers.push(provider);
+    }
+
+    pub fn by_category(&self, category: &str) -> Vec<&dyn InfoProvider> {
+        self.providers
+            .iter()
+            .filter(|provider| provider.category() == category)
+            .map(|provider| provider.as_ref())
+            .collect()
+    }
+
+    pub fn all(&self) -> Vec<&dyn InfoProvider> {
+        self.providers.iter().map(|provider| provider.as_ref()).collect()
+    }
+
+    /// Categories in registration order, without duplicates
+    pub fn categories(&self) -> Vec<String> {
+        let mut categories: Vec<String> = Vec::new();
+        for provider in &self.providers {
+            if !categories.iter().any(|c| c == provider.category()) {
+                categories.push(provider.category().to_string());
+            }
+        }
+        categories
+    }
+}
+
+struct HardwareProvider;
+
+impl InfoProvider for HardwareProvider {
+    fn name(&self) -> &str {
+        "Hardware"
+    }
+
+    fn category(&self) -> &str {
+        CATEGORY_OVERVIEW
+    }
+
+    fn detect(&self) -> Result<Vec<(String, String)>> {
+        let info = SystemInfo::detect()?;
+        Ok(vec![
+            ("Hostname".to_string(), info.hostname),
+            ("Processor".to_string(), info.cpu),
+            ("Memory".to_string(), info.memory),
+            ("Startup Disk".to_string(), info.startup_disk),
+            ("Graphics".to_string(), info.graphics),
+            ("Serial Number".to_string(), info.serial_number),
+        ])
+    }
+}
+
+struct DistroProvider;
+
+impl InfoProvider for DistroProvider {
+    fn name(&self) -> &str {
+        "Software"
+    }
+
+    fn category(&self) -> &str {
+        CATEGORY_OVERVIEW
+    }
+
+    fn detect(&self) -> Result<Vec<(String, String)>> {
+        let info = DynamicSystemInfo::detect()?;
+        let mut rows = vec![
+            ("Distribution".to_string(), info.distro_name),
+            ("Version".to_string(), info.distro_version),
+        ];
+        if let Some(codename) = info.distro_codename {
+            rows.push(("Codename".to_string(), codename));
+        }
+        rows.push(("Kernel".to_string(), info.kernel));
+        rows.push(("Processes".to_string(), process_count().to_string()));
+        if snap::available() {
+            match snap::detect() {
+                Ok(snaps) => rows.extend(snaps.rows()),
+                Err(e) => eprintln!("Failed to read snaps: {}", e),
+            }
+        }
+        if flatpak::available() {
+            match flatpak::detect() {
+                Ok(inventory) => rows.extend(inventory.rows()),
+                Err(e) => eprintln!("Failed to list Flatpaks: {}", e),
+            }
+        }
+        Ok(rows)
+    }
+}
+
+struct SessionProvider;
+
+impl InfoProvider for SessionProvider {
+    fn name(&self) -> &str {
+        "Session"
+    }
+
+    fn category(&self) -> &str {
+        CATEGORY_OVERVIEW
+    }
+
+    fn detect(&self) -> Result<Vec<(String, String)>> {
+        Ok(session::detect()?.rows())
+    }
+}
+
+struct RegionProvider;
+
+impl InfoProvider for RegionProvider {
+    fn name(&self) -> &str {
+        "Language & Region"
+    }
+
+    fn category(&self) -> &str {
+        CATEGORY_OVERVIEW
+    }
+
+    fn detect(&self) -> Result<Vec<(String, String)>> {
+        Ok(region::detect()?.rows())
+    }
+}
+
+struct DisplayProvider;
+
+impl InfoProvider for DisplayProvider {
+    fn name(&self) -> &str {
+        "Displays"
+    }
+
+    fn category(&self) -> &str {
+        CATEGORY_DISPLAY
+    }
+
+    fn detect(&self) -> Result<Vec<(String, String)>> {
+        let info = DisplayInfo::detect()?;
+        let mut rows = Vec::new();
+
+        for display in info.displays {
+            let name = if display.is_primary {
+                format!("{} (Primary)", display.name)
+            } else {
+                display.name.clone()
+            };
+
+            let properties = vec![
+                ("Resolution", display.resolution),
+                ("Refresh Rate", display.refresh_rate),
+                ("Color Depth", display.color_depth),
+                ("Connection Type", display.connection_type),
+                ("Scale Factor", display.scale_factor),
+                ("Rotation", display.rotation),
+            ];
+
+            for (prop_name, prop_value) in properties {
+                if prop_value != "Unknown" && !prop_value.is_empty() {
+                    rows.push((format!("{} {}", name, prop_name), prop_value));
+                }
+            }
+        }
+
+        Ok(rows)
+    }
+}
+
+struct StorageProvider;
+
+impl InfoProvider for StorageProvider {
+    fn name(&self) -> &str {
+        "Storage"
+    }
+
+    fn category(&self) -> &str {
+        CATEGORY_STORAGE
+    }
+
+    fn detect(&self) -> Result<Vec<(String, String)>> {
+        let info = StorageInfo::detect()?;
+        let mut rows = Vec::new();
+
+        for device in info.devices {
+            rows.push((
+                device.name.clone(),
+                format!("{} {} ({})", device.size, device.model, device.device_type),
+            ));
+            if let Ok(health) = device.health {
+                rows.push((format!("{} Health", device.name), health));
+            }
+        }
+
+        for filesystem in info.filesystems {
+            rows.push((
+                filesystem.mountpoint.clone(),
+                format!(
+                    "{} of {} available ({})",
+                    filesystem.available_size, filesystem.total_size, filesystem.filesystem_type
+                ),
+            ));
+        }
+
+        Ok(rows)
+    }
+}
+
+struct PowerProvider;
+
+impl InfoProvider for PowerProvider {
+    fn name(&self) -> &str {
+        "Power"
+    }
+
+    fn category(&self) -> &str {
+        CATEGORY_POWER
+    }
+
+    fn detect(&self) -> Result<Vec<(String, String)>> {
+        let devices = power::detect()?;
+        let mut rows = Vec::new();
+
+        let adapters: Vec<_> = devices.iter().filter(|device| device.kind == Kind::LinePower).collect();
+        if !adapters.is_empty() {
+            let connected = adapters.iter().any(|adapter| adapter.online);
+            rows.push((
+                "Power Adapter".to_string(),
+                if connected { "Connected" } else { "Not connected" }.to_string(),
+            ));
+        }
+
+        let batteries: Vec<_> = devices.iter().filter(|device| device.kind != Kind::LinePower).collect();
+        for battery in &batteries {
+            // Only tell batteries apart when there is more than one
+            let prefix = match (batteries.len(), battery.kind) {
+                (_, Kind::Ups) => format!("UPS {} ", battery.name),
+                (1, _) => String::new(),
+                _ => format!("{} ", battery.name),
+            };
+            let mut properties = vec![("Charge", battery.charge_text())];
+            if let (Some(energy), Some(full)) = (battery.energy_wh, battery.energy_full_wh) {
+                properties.push(("Energy", format!("{:.1} Wh of {:.1} Wh", energy, full)));
+            }
+            if let Some(rate) = battery.rate_w {
+                properties.push(("Power Draw", format!("{:.1} W", rate)));
+            }
+            if let Some(health) = battery.health_percent() {
+                properties.push(("Maximum Capacity", format!("{:.0}%", health)));
+            }
+            if let Some(cycles) = battery.cycles {
+                properties.push(("Cycle Count", cycles.to_string()));
+            }
+            properties.push(("Manufacturer", battery.vendor.clone()));
+            properties.push(("Model", battery.model.clone()));
+            properties.push(("Technology", battery.technology.clone()));
+
+            for (name, value) in properties {
+                if !value.is_empty() {
+                    rows.push((format!("{}{}", prefix, name), value));
+                }
+            }
+        }
+
+        Ok(rows)
+    }
+}
+
+pub struct ScriptProvider {
+    config: ScriptProviderConfig,
+    category: String,
+}
+
+impl ScriptProvider {
+    pub fn new(config: ScriptProviderConfig) -> Self {
+        let category = config
+            .category
+            .clone()
+            .unwrap_or_else(|| category_slug(&config.name));
+        ScriptProvider { config, category }
+    }
+}
+
+impl InfoProvider for ScriptProvider {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn category(&self) -> &str {
+        &self.category
+    }
+
+    fn detect(&self) -> Result<Vec<(String, String)>> {
+        run_script_provider(&self.config)
+    }
+
+    fn detect_job(&self) -> Option<DetectJob> {
+        let config = self.config.clone();
+        Some(Box::new(move || run_script_provider(&config)))
+    }
+}
+
+fn run_script_provider(config: &ScriptProviderConfig) -> Result<Vec<(String, String)>> {
+    let mut child = host::command("sh")
+        .arg("-c")
+        .arg(&config.command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run script for {}", config.name))?;
+
+    // Read both pipes while waiting so a chatty script can't fill one and stall
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() >= SCRIPT_TIMEOUT {
+            let _ = child.kill();
+            let _ = child.wait();
+            bail!("Script did not finish within {} seconds", SCRIPT_TIMEOUT.as_secs());
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    };
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    if !status.success() {
+        bail!("Script exited with {}: {}", status, stderr.trim());
+    }
+
+    match config.format {
+        ScriptFormat::Json => parse_script_json(&stdout),
+        ScriptFormat::Lines => Ok(parse_script_lines(&stdout)),
+        ScriptFormat::Auto => {
+            parse_script_json(&stdout).or_else(|_| Ok(parse_script_lines(&stdout)))
+        }
+    }
+}
+
+fn read_in_background(pipe: Option<impl Read + Send + 'static>) -> std::thread::JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut output = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut output);
+        }
+        output
+    })
+}
+
+fn parse_script_json(output: &str) -> Result<Vec<(String, String)>> {
+    let json_data: serde_json::Value =
+        serde_json::from_str(output).context("Script output is not valid JSON")?;
+
+    let object = json_data
+        .as_object()
+        .ok_or_else(|| anyhow::anyhow!("Script JSON output must be an object"))?;
+
+    Ok(object
+        .iter()
+        .map(|(key, value)| {
+            let value = match value {
+                serde_json::Value::String(text) => text.clone(),
+                other => other.to_string(),
+            };
+            (key.clone(), value)
+        })
+        .collect())
+}
+
+fn parse_script_lines(output: &str) -> Vec<(String, String)> {
+    let mut rows = Vec::new();
+    for line in output.lines() {
+        if let Some(colon_pos) = line.find(':') {
+            let key = line[..colon_pos].trim();
+            let value = line[colon_pos + 1..].trim();
+            if !key.is_empty() {
+                rows.push((key.to_string(), value.to_string()));
+            }
+        }
+    }
+    rows
+}
+
+fn category_slug(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .trim_matches('-')
+        .to_string()
+}
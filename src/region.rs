@@ -0,0 +1,233 @@
+use anyhow::{bail, Context, Result};
+use gtk::prelude::*;
+use gtk::{gio, glib};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::journal::{self, Outcome};
+use crate::{host, system_info};
+
+// System-wide language, keyboard and time zone, as systemd-localed and
+// systemd-timedated report them, or from the files they manage
+const LOCALE1_NAME: &str = "org.freedesktop.locale1";
+const LOCALE1_PATH: &str = "/org/freedesktop/locale1";
+const TIMEDATE1_NAME: &str = "org.freedesktop.timedate1";
+const TIMEDATE1_PATH: &str = "/org/freedesktop/timedate1";
+const CALL_TIMEOUT_MS: i32 = 2000;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RegionInfo {
+    // LANG, e.g. en_GB.UTF-8
+    pub locale: Option<String>,
+    // LC_* overrides, e.g. LC_TIME=de_DE.UTF-8
+    pub formats: Vec<(String, String)>,
+    // X11 layout and variant, or the console keymap
+    pub keyboard_layout: Option<String>,
+    pub timezone: Option<String>,
+    // Only known through timedated
+    pub ntp_synchronized: Option<bool>,
+}
+
+impl RegionInfo {
+    /// "Europe/London (BST, UTC+01:00)"
+    pub fn timezone_text(&self) -> Option<String> {
+        let timezone = self.timezone.as_deref()?;
+        let now = glib::DateTime::now(&glib::TimeZone::new(Some(timezone))).ok();
+        let text = match now.and_then(|now| Some((now.timezone_abbreviation(), now.format("%:z").ok()?))) {
+            Some((abbreviation, offset)) => format!("{} ({}, UTC{})", timezone, abbreviation, offset),
+            None => timezone.to_string(),
+        };
+        Some(text)
+    }
+
+    /// "en_GB.UTF-8, gb keyboard, Europe/London" for the overview row
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        parts.extend(self.locale.clone());
+        parts.extend(
+            self.keyboard_layout
+                .as_ref()
+                .map(|layout| format!("{} keyboard", layout)),
+        );
+        parts.extend(self.timezone.clone());
+        parts.join(", ")
+    }
+
+    pub fn rows(&self) -> Vec<(String, String)> {
+        let mut rows = Vec::new();
+        if let Some(locale) = &self.locale {
+            rows.push(("Language".to_string(), locale.clone()));
+        }
+        for (category, locale) in &self.formats {
+            rows.push((category.clone(), locale.clone()));
+        }
+        if let Some(layout) = &self.keyboard_layout {
+            rows.push(("Keyboard Layout".to_string(), layout.clone()));
+        }
+        if let Some(timezone) = self.timezone_text() {
+            rows.push(("Time Zone".to_string(), timezone));
+        }
+        if let Some(synchronized) = self.ntp_synchronized {
+            let clock = if synchronized {
+                "Synchronized"
+            } else {
+                "Not synchronized"
+            };
+            rows.push(("Network Time".to_string(), clock.to_string()));
+        }
+        rows
+    }
+}
+
+pub fn detect() -> Result<RegionInfo> {
+    if system_info::detection_disabled() {
+        bail!("detection is disabled (--no-detect)");
+    }
+
+    let mut info = RegionInfo::default();
+    match journal::timed("locale and keyboard", "localed", localed) {
+        Ok((locale, formats, keyboard_layout)) => {
+            info.locale = locale;
+            info.formats = formats;
+            info.keyboard_layout = keyboard_layout;
+        }
+        Err(_) => {
+            fallback("locale and keyboard", "/etc/locale.conf");
+            let settings = read_assignments("/etc/locale.conf");
+            (info.locale, info.formats) =
+                split_locale(settings.into_iter().map(|(key, value)| format!("{}={}", key, value)));
+            info.keyboard_layout = keyboard_from_files();
+        }
+    }
+    match journal::timed("time zone", "timedated", timedated) {
+        Ok((timezone, synchronized)) => {
+            info.timezone = timezone;
+            info.ntp_synchronized = synchronized;
+        }
+        Err(_) => {
+            fallback("time zone", "/etc/localtime");
+            info.timezone = timezone_from_files();
+        }
+    }
+
+    if info.locale.is_none() && info.keyboard_layout.is_none() && info.timezone.is_none() {
+        bail!("no locale, keyboard layout or time zone configured");
+    }
+    Ok(info)
+}
+
+fn fallback(step: &str, source: &str) {
+    journal::record(
+        step,
+        source,
+        Duration::ZERO,
+        Outcome::Fallback(format!("D-Bus service unavailable, reading {}", source)),
+    );
+}
+
+fn get_all(name: &str, path: &str) -> Result<glib::VariantDict> {
+    let connection = gio::bus_get_sync(gio::BusType::System, gio::Cancellable::NONE).context("No system bus")?;
+    let reply = connection
+        .call_sync(
+            Some(name),
+            path,
+            "org.freedesktop.DBus.Properties",
+            "GetAll",
+            Some(&(name,).to_variant()),
+            None,
+            gio::DBusCallFlags::NONE,
+            CALL_TIMEOUT_MS,
+            gio::Cancellable::NONE,
+        )
+        .with_context(|| format!("{} is not available", name))?;
+    Ok(glib::VariantDict::new(Some(&reply.child_value(0))))
+}
+
+fn text(properties: &glib::VariantDict, name: &str) -> Option<String> {
+    properties
+        .lookup_value(name, None)
+        .and_then(|value| value.str().map(|text| text.trim().to_string()))
+        .filter(|text| !text.is_empty())
+}
+
+type LocaleSettings = (Option<String>, Vec<(String, String)>, Option<String>);
+
+fn localed() -> Result<LocaleSettings> {
+    let properties = get_all(LOCALE1_NAME, LOCALE1_PATH)?;
+    let assignments = properties
+        .lookup_value("Locale", None)
+        .and_then(|value| value.get::<Vec<String>>())
+        .unwrap_or_default();
+    let (locale, formats) = split_locale(assignments.into_iter());
+
+    let keyboard_layout = match (text(&properties, "X11Layout"), text(&properties, "X11Variant")) {
+        (Some(layout), Some(variant)) => Some(format!("{} ({})", layout, variant)),
+        (Some(layout), None) => Some(layout),
+        (None, _) => text(&properties, "VConsoleKeymap"),
+    };
+    Ok((locale, formats, keyboard_layout))
+}
+
+fn timedated() -> Result<(Option<String>, Option<bool>)> {
+    let properties = get_all(TIMEDATE1_NAME, TIMEDATE1_PATH)?;
+    let synchronized = properties
+        .lookup_value("NTPSynchronized", None)
+        .and_then(|value| value.get::<bool>());
+    Ok((text(&properties, "Timezone"), synchronized))
+}
+
+// LANG first, then every other LC_* that differs from it
+fn split_locale(assignments: impl Iterator<Item = String>) -> (Option<String>, Vec<(String, String)>) {
+    let mut locale = None;
+    let mut formats = Vec::new();
+    for assignment in assignments {
+        match assignment.split_once('=') {
+            Some(("LANG", value)) => locale = Some(value.to_string()),
+            Some((key, value)) if key.starts_with("LC_") => formats.push((key.to_string(), value.to_string())),
+            _ => {}
+        }
+    }
+    formats.retain(|(_, value)| Some(value) != locale.as_ref());
+    (locale, formats)
+}
+
+// KEY="value" lines of a shell-style config file
+fn read_assignments(path: &str) -> HashMap<String, String> {
+    let content = std::fs::read_to_string(host::path(path)).unwrap_or_default();
+    content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().trim_matches('"').to_string()))
+        .filter(|(_, value)| !value.is_empty())
+        .collect()
+}
+
+// Debian keeps the X11 layout in /etc/default/keyboard, most others only
+// have the console keymap in /etc/vconsole.conf
+fn keyboard_from_files() -> Option<String> {
+    let keyboard = read_assignments("/etc/default/keyboard");
+    match (keyboard.get("XKBLAYOUT"), keyboard.get("XKBVARIANT")) {
+        (Some(layout), Some(variant)) => Some(format!("{} ({})", layout, variant)),
+        (Some(layout), None) => Some(layout.clone()),
+        (None, _) => read_assignments("/etc/vconsole.conf").get("KEYMAP").cloned(),
+    }
+}
+
+// /etc/localtime links into the zoneinfo database, e.g.
+// /usr/share/zoneinfo/Europe/London; Debian also writes /etc/timezone
+fn timezone_from_files() -> Option<String> {
+    let link = std::fs::read_link(host::path("/etc/localtime")).ok();
+    let from_link = link.as_deref().and_then(|link| {
+        let link = link.to_string_lossy();
+        link.split_once("zoneinfo/").map(|(_, zone)| zone.to_string())
+    });
+    from_link.or_else(|| {
+        std::fs::read_to_string(host::path("/etc/timezone"))
+            .ok()
+            .map(|zone| zone.trim().to_string())
+            .filter(|zone| !zone.is_empty() && Path::new(zone).is_relative())
+    })
+}
@@ -0,0 +1,110 @@
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::config::COMMAND_FIELDS;
+use crate::paths;
+
+const FETCH_TIMEOUT_SECONDS: &str = "15";
+
+// Settings that pull in other files, on top of COMMAND_FIELDS. Anyone who
+// can serve or tamper with the URL could use either to run code on this
+// machine, so a fetched config never gets them.
+const FILE_KEYS: [&str; 3] = ["extends", "css_path", "placement_monitor"];
+
+pub fn is_url(location: &str) -> bool {
+    location.starts_with("http://") || location.starts_with("https://")
+}
+
+/// Download an overview config, and its logo if that is a URL too, into the
+/// cache directory and return the local path to load. If the download fails
+/// the copy from the last successful fetch is used.
+pub fn fetch_overview(url: &str) -> Result<PathBuf> {
+    if !url.starts_with("https://") {
+        bail!("Refusing to load {}: only https:// URLs are supported", url);
+    }
+    let dir = paths::cache_dir().join("remote");
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create {}", dir.display()))?;
+    let config_path = dir.join(format!("overview-{}.json", url_key(url)));
+
+    match fetch_config(url, &dir, &config_path) {
+        Ok(()) => Ok(config_path),
+        Err(e) if config_path.exists() => {
+            eprintln!("{:#}; using the cached copy from the last fetch", e);
+            Ok(config_path)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+fn fetch_config(url: &str, dir: &Path, config_path: &Path) -> Result<()> {
+    let content = download(url)?;
+    let mut json: serde_json::Value = serde_json::from_slice(&content)
+        .with_context(|| format!("{} is not a JSON config", url))?;
+    let settings = json
+        .as_object_mut()
+        .with_context(|| format!("{} is not a JSON config", url))?;
+    for key in COMMAND_FIELDS.iter().chain(&FILE_KEYS) {
+        if settings.remove(*key).is_some() {
+            eprintln!("Ignoring `{}` in {}: remote configs can't run commands or load files", key, url);
+        }
+    }
+
+    let logo_url = json
+        .get("distro_image_path")
+        .and_then(|value| value.as_str())
+        .filter(|path| is_url(path))
+        .map(|path| path.to_string());
+    match logo_url {
+        Some(logo_url) if !logo_url.starts_with("https://") => {
+            eprintln!("Ignoring logo {}: only https:// URLs are supported", logo_url);
+            json["distro_image_path"] = serde_json::Value::String(String::new());
+        }
+        Some(logo_url) => {
+            let logo_path = dir.join(format!("logo-{}", url_key(&logo_url)));
+            match download(&logo_url) {
+                Ok(logo) => std::fs::write(&logo_path, logo)
+                    .with_context(|| format!("Failed to write {}", logo_path.display()))?,
+                // An older copy of the logo still beats the Tux fallback
+                Err(e) if logo_path.exists() => eprintln!("{:#}; using the cached logo", e),
+                Err(e) => eprintln!("{:#}", e),
+            }
+            json["distro_image_path"] = serde_json::Value::String(logo_path.to_string_lossy().to_string());
+        }
+        None => {}
+    }
+
+    std::fs::write(config_path, serde_json::to_string_pretty(&json)?)
+        .with_context(|| format!("Failed to write {}", config_path.display()))?;
+    Ok(())
+}
+
+fn download(url: &str) -> Result<Vec<u8>> {
+    let output = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--location"])
+        // Redirects must stay on https too
+        .args(["--proto", "=https", "--proto-redir", "=https"])
+        .args(["--max-time", FETCH_TIMEOUT_SECONDS])
+        .arg(url)
+        .output()
+        .context("Failed to run curl")?;
+
+    if !output.status.success() {
+        bail!(
+            "Failed to download {}: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(output.stdout)
+}
+
+// One file name per URL so a refetch overwrites the previous copy. FNV-1a
+// rather than std's hasher, whose output may change between Rust releases
+fn url_key(url: &str) -> String {
+    let hash = url
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3));
+    format!("{:016x}", hash)
+}
@@ -0,0 +1,306 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::config::Config;
+use crate::flatpak;
+use crate::journal;
+use crate::paths;
+use crate::power;
+use crate::privacy;
+use crate::region;
+use crate::session;
+use crate::snap;
+use crate::providers::{ProviderRegistry, CATEGORY_DISPLAY, CATEGORY_OVERVIEW, CATEGORY_STORAGE};
+use crate::system_info::{self, DisplayInfo, DynamicSystemInfo, Filesystem, StorageInfo};
+use crate::units;
+
+// Sections shown with an error instead of their rows since startup, for the
+// exit status of --print and --json
+static FAILED_SECTIONS: AtomicUsize = AtomicUsize::new(0);
+
+pub fn failed_sections() -> usize {
+    FAILED_SECTIONS.load(Ordering::Relaxed)
+}
+
+// Everything fails on purpose with --no-detect
+fn note_failure() {
+    if !system_info::detection_disabled() {
+        FAILED_SECTIONS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+pub fn overview_fields(config: &Config) -> Vec<(String, String)> {
+    let mut fields = vec![
+        ("Hostname".to_string(), privacy::masked(&config.hostname, privacy::hides_hostname(config))),
+        ("Processor".to_string(), config.cpu.clone()),
+        ("Memory".to_string(), config.memory.clone()),
+        ("Startup Disk".to_string(), config.startup_disk.clone()),
+        ("Graphics".to_string(), config.graphics.clone()),
+        ("Serial Number".to_string(), privacy::masked(&config.serial_num, privacy::hides_serial(config))),
+    ];
+    fields.extend(config.custom_fields.iter().cloned());
+    fields
+}
+
+pub fn summary_line(config: &Config) -> String {
+    let hostname = privacy::masked(&config.hostname, privacy::hides_hostname(config));
+    [&hostname, &config.cpu, &config.memory, &config.graphics]
+        .iter()
+        .filter(|value| !value.is_empty())
+        .map(|value| value.as_str())
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+pub fn format_rows(rows: &[(String, String)]) -> String {
+    let width = rows.iter().map(|(key, _)| key.chars().count()).max().unwrap_or(0);
+    let mut text = String::new();
+    for (key, value) in rows {
+        text.push_str(&format!("{:<width$}  {}\n", format!("{}:", key), value, width = width + 1));
+    }
+    text
+}
+
+pub fn memory_usage_text(used: u64, total: u64) -> String {
+    format!("{} of {}", units::format_bytes(used), units::format_bytes(total))
+}
+
+pub fn volume_usage_text(filesystem: &Filesystem) -> String {
+    format!(
+        "{} of {} used, {} available",
+        filesystem.used_size, filesystem.total_size, filesystem.available_size
+    )
+}
+
+/// The values auto-refresh keeps current: uptime, memory, battery, the usage of every
+/// mounted filesystem and the temperatures of `devices`
+pub fn live_rows(devices: &[String]) -> Vec<(String, String)> {
+    let mut rows = Vec::new();
+    if let Some(uptime) = system_info::uptime() {
+        rows.push(("Uptime".to_string(), units::format_duration(uptime)));
+    }
+    if let Some((used, total)) = system_info::memory_usage() {
+        rows.push(("Memory Used".to_string(), memory_usage_text(used, total)));
+    }
+    if let Some(battery) = power::battery_summary() {
+        rows.push(("Battery".to_string(), battery));
+    }
+    if let Ok(filesystems) = system_info::detect_filesystem_usage() {
+        for filesystem in &filesystems {
+            rows.push((filesystem.mountpoint.clone(), volume_usage_text(filesystem)));
+        }
+    }
+    for device in devices {
+        if let Ok(temperature) = system_info::device_temperature(device) {
+            rows.push((format!("{} temperature", device), temperature));
+        }
+    }
+    rows
+}
+
+pub fn overview_report(config: &Config) -> String {
+    format!("About this Linux\n\n{}", format_rows(&overview_fields(config)))
+}
+
+/// The overview followed by a section for every provider, i.e. everything
+/// the tabs show, for pasting into forum posts and bug reports
+pub fn full_report(config: &Config, registry: &ProviderRegistry) -> String {
+    report_with(config, registry, |_| true)
+}
+
+/// The overview followed by the providers whose category is listed in
+/// `sections`, or every provider for "all"
+pub fn sections_report(config: &Config, registry: &ProviderRegistry, sections: &[String]) -> String {
+    if sections.iter().any(|section| section == "all") {
+        return full_report(config, registry);
+    }
+    report_with(config, registry, |category| sections.iter().any(|section| section == category))
+}
+
+fn report_with(config: &Config, registry: &ProviderRegistry, include: impl Fn(&str) -> bool) -> String {
+    let overview = overview_fields(config);
+    let mut text = format!("About this Linux\n\n{}", format_rows(&overview));
+
+    // The overview already shows the configured values, so its providers
+    // aren't detected again
+    let providers = registry
+        .all()
+        .into_iter()
+        .filter(|provider| provider.category() != CATEGORY_OVERVIEW && include(provider.category()));
+    for provider in providers {
+        let step = format!("provider {}", provider.name());
+        let rows = match journal::timed(&step, provider.category(), || provider.detect()) {
+            Ok(rows) => rows,
+            Err(e) => {
+                note_failure();
+                vec![("Error".to_string(), e.to_string())]
+            }
+        };
+        if rows.is_empty() {
+            continue;
+        }
+
+        text.push_str(&format!("\n{}\n{}\n", provider.name(), "-".repeat(provider.name().chars().count())));
+        text.push_str(&format_rows(&rows));
+    }
+
+    text
+}
+
+/// The full report stamped with the time it was made, for printed asset sheets
+pub fn printable_report(config: &Config, registry: &ProviderRegistry) -> String {
+    format!("{}\nGenerated {}\n", full_report(config, registry), units::format_now())
+}
+
+/// Everything detected, for inventory tools. `sections` picks the top-level
+/// keys (tab names); empty or "all" means every one.
+pub fn json_report(config: &Config, registry: &ProviderRegistry, sections: &[String]) -> Value {
+    let wanted = |section: &str| {
+        sections.is_empty() || sections.iter().any(|wanted| wanted == "all" || wanted == section)
+    };
+    let mut report = Map::new();
+
+    if wanted(CATEGORY_OVERVIEW) {
+        report.insert(CATEGORY_OVERVIEW.to_string(), overview_json(config));
+    }
+    if wanted(CATEGORY_DISPLAY) {
+        report.insert(CATEGORY_DISPLAY.to_string(), detected_json(DisplayInfo::detect()));
+    }
+    if wanted(CATEGORY_STORAGE) {
+        let storage = StorageInfo::detect().map(|mut storage| {
+            let hidden = privacy::hides_serial(config);
+            for device in &mut storage.devices {
+                device.serial = privacy::masked(&device.serial, hidden);
+            }
+            storage
+        });
+        report.insert(CATEGORY_STORAGE.to_string(), detected_json(storage));
+    }
+
+    // Script providers, as { provider name: { key: value } } per category
+    for category in registry.categories() {
+        if [CATEGORY_OVERVIEW, CATEGORY_DISPLAY, CATEGORY_STORAGE].contains(&category.as_str()) || !wanted(&category) {
+            continue;
+        }
+        let providers = registry
+            .by_category(&category)
+            .into_iter()
+            .map(|provider| {
+                let step = format!("provider {}", provider.name());
+                let rows = journal::timed(&step, provider.category(), || provider.detect()).map(|rows| {
+                    rows.into_iter()
+                        .map(|(key, value)| (key, Value::String(value)))
+                        .collect::<Map<_, _>>()
+                });
+                (provider.name().to_string(), detected_json(rows))
+            })
+            .collect::<Map<_, _>>();
+        report.insert(category, Value::Object(providers));
+    }
+
+    Value::Object(report)
+}
+
+/// A flat, ready-to-show summary for a Plasma panel widget: display strings
+/// and percentages rather than raw detection results
+pub fn plasma_widget_json(config: &Config) -> Value {
+    let distribution = DynamicSystemInfo::for_config(config);
+    let rows: Vec<Value> = overview_fields(config)
+        .into_iter()
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(label, value)| json!({ "label": label, "value": value }))
+        .collect();
+    let memory = system_info::memory_usage().map(|(used, total)| {
+        json!({
+            "text": memory_usage_text(used, total),
+            "percent": used as f64 * 100.0 / total.max(1) as f64,
+        })
+    });
+    let disks: Vec<Value> = system_info::detect_filesystem_usage()
+        .unwrap_or_default()
+        .iter()
+        .map(|filesystem| {
+            json!({
+                "mountpoint": filesystem.mountpoint,
+                "text": volume_usage_text(filesystem),
+                "percent": filesystem.usage_percent,
+            })
+        })
+        .collect();
+    // Plasma can only show a logo it can read from disk
+    let logo = Some(config.distro_image_path.as_str()).filter(|path| std::path::Path::new(path).is_absolute());
+
+    json!({
+        "title": "About this Linux",
+        "icon": "tux-logo",
+        "logo": logo,
+        "distribution": format!("{} {}", distribution.distro_name, distribution.distro_version).trim(),
+        "kernel": distribution.kernel,
+        "summary": summary_line(config),
+        "rows": rows,
+        "uptime": system_info::uptime().map(units::format_duration),
+        "memory": memory,
+        "disks": disks,
+        "open_command": "gapplication action com.novik.about-this-linux show-tab ''",
+        "generated": units::format_now(),
+    })
+}
+
+fn overview_json(config: &Config) -> Value {
+    let custom_fields: Map<String, Value> = config
+        .custom_fields
+        .iter()
+        .map(|(label, value)| (label.clone(), Value::String(value.clone())))
+        .collect();
+    let memory_usage = system_info::memory_usage().map(|(used, total)| json!({ "used_bytes": used, "total_bytes": total }));
+
+    json!({
+        "hostname": privacy::masked(&config.hostname, privacy::hides_hostname(config)),
+        "processor": config.cpu,
+        "memory": config.memory,
+        "startup_disk": config.startup_disk,
+        "graphics": config.graphics,
+        "serial_number": privacy::masked(&config.serial_num, privacy::hides_serial(config)),
+        "distribution": detected_json(DynamicSystemInfo::detect()),
+        "uptime_seconds": system_info::uptime().map(|uptime| uptime.as_secs()),
+        "memory_usage": memory_usage,
+        // Left null rather than failing the report where logind isn't running
+        "session": session::detect().ok(),
+        "region": region::detect().ok(),
+        "snaps": snap::available().then(snap::detect).and_then(Result::ok),
+        "flatpaks": flatpak::available().then(flatpak::detect).and_then(Result::ok),
+        "custom_fields": custom_fields,
+    })
+}
+
+// The detected value, or { "error": ... } when detection failed
+fn detected_json<T: Serialize>(result: Result<T>) -> Value {
+    match result.and_then(|value| Ok(serde_json::to_value(value)?)) {
+        Ok(value) => value,
+        Err(e) => {
+            note_failure();
+            json!({ "error": e.to_string() })
+        }
+    }
+}
+
+// One file per process in the user's own runtime directory, so other users
+// can't plant or read it and two windows don't overwrite each other's report
+pub fn write_report(text: &str) -> Result<PathBuf> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let path = paths::runtime_dir().join(format!("report-{}.txt", std::process::id()));
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(&path)
+        .and_then(|mut file| file.write_all(text.as_bytes()))
+        .with_context(|| format!("Failed to write report: {}", path.display()))?;
+    Ok(path)
+}
@@ -0,0 +1,33 @@
+use anyhow::{bail, Context, Result};
+use gtk::prelude::*;
+use gtk::{gdk, graphene};
+use std::path::Path;
+
+/// Render `widget` as it currently appears on screen and save it as a PNG.
+/// The widget has to be mapped, so only the visible page can be captured.
+pub fn save_png(widget: &impl IsA<gtk::Widget>, path: &Path) -> Result<()> {
+    let widget = widget.as_ref();
+    let (width, height) = (widget.width(), widget.height());
+    if !widget.is_mapped() || width <= 0 || height <= 0 {
+        bail!("Nothing to capture, the widget is not shown");
+    }
+    let native = match widget.native() {
+        Some(native) => native,
+        None => bail!("Nothing to capture, the widget is not in a window"),
+    };
+
+    let paintable = gtk::WidgetPaintable::new(Some(widget));
+    let snapshot = gtk::Snapshot::new();
+    paintable.snapshot(&snapshot, width as f64, height as f64);
+    let node = match snapshot.to_node() {
+        Some(node) => node,
+        None => bail!("Nothing was drawn"),
+    };
+
+    // Clip to the widget so window shadows are left out
+    let viewport = graphene::Rect::new(0.0, 0.0, width as f32, height as f32);
+    let texture: gdk::Texture = native.renderer().render_texture(&node, Some(&viewport));
+    texture
+        .save_to_png(path)
+        .with_context(|| format!("Failed to save image: {}", path.display()))
+}
@@ -0,0 +1,229 @@
+use gtk::prelude::*;
+use gtk::{gio, glib};
+use std::collections::HashMap;
+
+use crate::providers::{InfoProvider, ScriptProvider, CATEGORY_DISPLAY, CATEGORY_OVERVIEW, CATEGORY_POWER, CATEGORY_STORAGE};
+
+// GNOME Shell search provider: typing "about" or a hardware term in the
+// overview offers the matching tabs, and picking one opens the window on
+// it. Shell finds it through data/com.novik.about-this-linux.search-provider.ini.
+const OBJECT_PATH: &str = "/com/novik/about-this-linux/SearchProvider";
+const INTERFACE: &str = "org.gnome.Shell.SearchProvider2";
+
+const INTERFACE_XML: &str = r#"
+<node>
+  <interface name="org.gnome.Shell.SearchProvider2">
+    <method name="GetInitialResultSet">
+      <arg name="terms" type="as" direction="in"/>
+      <arg name="results" type="as" direction="out"/>
+    </method>
+    <method name="GetSubsearchResultSet">
+      <arg name="previous_results" type="as" direction="in"/>
+      <arg name="terms" type="as" direction="in"/>
+      <arg name="results" type="as" direction="out"/>
+    </method>
+    <method name="GetResultMetas">
+      <arg name="identifiers" type="as" direction="in"/>
+      <arg name="metas" type="aa{sv}" direction="out"/>
+    </method>
+    <method name="ActivateResult">
+      <arg name="identifier" type="s" direction="in"/>
+      <arg name="terms" type="as" direction="in"/>
+      <arg name="timestamp" type="u" direction="in"/>
+    </method>
+    <method name="LaunchSearch">
+      <arg name="terms" type="as" direction="in"/>
+      <arg name="timestamp" type="u" direction="in"/>
+    </method>
+  </interface>
+</node>
+"#;
+
+/// A tab as a search result, also used by the KRunner runner
+pub struct SearchResult {
+    pub tab: String,
+    pub title: String,
+    pub description: String,
+    pub icon: &'static str,
+    keywords: Vec<String>,
+}
+
+// (tab, title, description, icon, keywords)
+const BUILTIN_RESULTS: [(&str, &str, &str, &str, &str); 6] = [
+    (
+        CATEGORY_OVERVIEW,
+        "About this Linux",
+        "Processor, memory, graphics and distribution",
+        "computer",
+        "about this linux computer system overview hardware specs processor cpu memory ram graphics gpu \
+        serial hostname distribution distro version kernel uptime",
+    ),
+    (
+        CATEGORY_DISPLAY,
+        "Displays",
+        "Resolution, refresh rate and scaling of connected monitors",
+        "video-display",
+        "display displays monitor monitors screen resolution refresh rate hz scale scaling",
+    ),
+    (
+        CATEGORY_STORAGE,
+        "Storage",
+        "Disks, free space, temperatures and health",
+        "drive-harddisk",
+        "storage disk disks drive ssd hdd nvme space free usage filesystem partition temperature smart health",
+    ),
+    (
+        CATEGORY_POWER,
+        "Power",
+        "Battery charge, health and power adapter",
+        "battery",
+        "power battery charge charging health capacity cycles adapter ac charger ups",
+    ),
+    (
+        "support",
+        "Support",
+        "Documentation and help for this system",
+        "help-browser",
+        "support help documentation manual wiki forum",
+    ),
+    (
+        "service",
+        "Service",
+        "Warranty and repair information",
+        "preferences-system",
+        "service warranty repair",
+    ),
+];
+
+/// Export the search provider on the application's bus name
+pub fn register(app: &gtk::Application) {
+    let connection = match app.dbus_connection() {
+        Some(connection) => connection,
+        None => return,
+    };
+
+    let interface = match gio::DBusNodeInfo::for_xml(INTERFACE_XML)
+        .ok()
+        .and_then(|node| node.lookup_interface(INTERFACE))
+    {
+        Some(interface) => interface,
+        None => return,
+    };
+
+    let result = connection.register_object(
+        OBJECT_PATH,
+        &interface,
+        |_, _, _, _, method, parameters, invocation| match method {
+            "GetInitialResultSet" => {
+                let (terms,) = parameters.get::<(Vec<String>,)>().unwrap_or_default();
+                invocation.return_value(Some(&(matching(&terms),).to_variant()));
+            }
+            "GetSubsearchResultSet" => {
+                let (previous, terms) = parameters.get::<(Vec<String>, Vec<String>)>().unwrap_or_default();
+                let results: Vec<String> = matching(&terms).into_iter().filter(|tab| previous.contains(tab)).collect();
+                invocation.return_value(Some(&(results,).to_variant()));
+            }
+            "GetResultMetas" => {
+                let (identifiers,) = parameters.get::<(Vec<String>,)>().unwrap_or_default();
+                invocation.return_value(Some(&(result_metas(&identifiers),).to_variant()));
+            }
+            "ActivateResult" => {
+                let tab = parameters.child_value(0).get::<String>().unwrap_or_default();
+                show_tab(&tab);
+                invocation.return_value(None);
+            }
+            "LaunchSearch" => {
+                show_tab("");
+                invocation.return_value(None);
+            }
+            _ => invocation.return_dbus_error(
+                "org.freedesktop.DBus.Error.UnknownMethod",
+                &format!("Unknown method {}", method),
+            ),
+        },
+        |_, _, _, _, _| ().to_variant(),
+        |_, _, _, _, _, _| false,
+    );
+    if let Err(e) = result {
+        eprintln!("Failed to export the search provider: {}", e);
+    }
+}
+
+pub fn show_tab(tab: &str) {
+    if let Some(app) = gio::Application::default() {
+        app.activate_action("show-tab", Some(&tab.to_variant()));
+    }
+}
+
+// The shown built-in tabs plus one per custom provider tab, read from the
+// saved config on every search so they follow its changes
+fn results() -> Vec<SearchResult> {
+    let settings = crate::load_saved_settings();
+    let mut results: Vec<SearchResult> = BUILTIN_RESULTS
+        .iter()
+        .filter(|(tab, ..)| settings.tabs.iter().any(|shown| shown == tab))
+        .map(|(tab, title, description, icon, keywords)| SearchResult {
+            tab: tab.to_string(),
+            title: title.to_string(),
+            description: description.to_string(),
+            icon,
+            keywords: keywords.split_whitespace().map(str::to_string).collect(),
+        })
+        .collect();
+
+    for config in &settings.custom_providers {
+        let provider = ScriptProvider::new(config.clone());
+        if results.iter().any(|result| result.tab == provider.category()) {
+            continue;
+        }
+        let text = format!("{} {}", provider.name(), provider.category()).to_lowercase();
+        results.push(SearchResult {
+            tab: provider.category().to_string(),
+            title: provider.name().to_string(),
+            description: "About this Linux".to_string(),
+            icon: "utilities-system-monitor",
+            keywords: text
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|word| !word.is_empty())
+                .map(str::to_string)
+                .collect(),
+        });
+    }
+    results
+}
+
+/// Tabs where every term starts one of the keywords, as Shell expects
+pub fn find(terms: &[String]) -> Vec<SearchResult> {
+    if terms.is_empty() {
+        return Vec::new();
+    }
+    results()
+        .into_iter()
+        .filter(|result| {
+            terms.iter().all(|term| {
+                let term = term.to_lowercase();
+                result.keywords.iter().any(|keyword| keyword.starts_with(&term))
+            })
+        })
+        .collect()
+}
+
+fn matching(terms: &[String]) -> Vec<String> {
+    find(terms).into_iter().map(|result| result.tab).collect()
+}
+
+fn result_metas(identifiers: &[String]) -> Vec<HashMap<String, glib::Variant>> {
+    let results = results();
+    identifiers
+        .iter()
+        .filter_map(|id| results.iter().find(|result| &result.tab == id))
+        .map(|result| {
+            HashMap::from([
+                ("id".to_string(), result.tab.to_variant()),
+                ("name".to_string(), result.title.to_variant()),
+                ("description".to_string(), result.description.to_variant()),
+                ("gicon".to_string(), result.icon.to_variant()),
+            ])
+        })
+        .collect()
+}
@@ -0,0 +1,119 @@
+use gtk::prelude::*;
+use gtk::{gio, glib};
+
+use crate::providers::{ProviderRegistry, CATEGORY_STORAGE};
+use crate::report;
+use crate::system_info;
+
+// The detection engine on the session bus, so shell extensions, panels and
+// scripts can query what the window shows and raise it
+const BUS_NAME: &str = "com.novik.AboutThisLinux";
+const OBJECT_PATH: &str = "/com/novik/AboutThisLinux";
+const INTERFACE: &str = "com.novik.AboutThisLinux";
+const ERROR_NAME: &str = "com.novik.AboutThisLinux.Error.Failed";
+
+const INTERFACE_XML: &str = r#"
+<node>
+  <interface name="com.novik.AboutThisLinux">
+    <method name="GetOverview">
+      <arg name="rows" type="a(ss)" direction="out"/>
+    </method>
+    <method name="GetStorage">
+      <arg name="rows" type="a(ss)" direction="out"/>
+    </method>
+    <method name="GetJson">
+      <arg name="sections" type="as" direction="in"/>
+      <arg name="json" type="s" direction="out"/>
+    </method>
+    <method name="ShowWindow">
+      <arg name="tab" type="s" direction="in"/>
+    </method>
+  </interface>
+</node>
+"#;
+
+/// Export the service object and own `com.novik.AboutThisLinux` until the
+/// application exits
+pub fn register(app: &gtk::Application) {
+    let connection = match app.dbus_connection() {
+        Some(connection) => connection,
+        None => {
+            eprintln!("No D-Bus connection, running without the D-Bus service");
+            return;
+        }
+    };
+
+    let interface = match gio::DBusNodeInfo::for_xml(INTERFACE_XML)
+        .ok()
+        .and_then(|node| node.lookup_interface(INTERFACE))
+    {
+        Some(interface) => interface,
+        None => return,
+    };
+
+    let result = connection.register_object(
+        OBJECT_PATH,
+        &interface,
+        |_, _, _, _, method, parameters, invocation| match method {
+            "GetOverview" => reply(invocation, || {
+                let config = crate::create_auto_detected_config(crate::load_saved_settings());
+                Ok(report::overview_fields(&config))
+            }),
+            "GetStorage" => reply(invocation, || {
+                let settings = crate::load_saved_settings();
+                system_info::configure(&settings);
+                let registry = ProviderRegistry::from_config(&settings);
+                let mut rows = Vec::new();
+                for provider in registry.by_category(CATEGORY_STORAGE) {
+                    rows.extend(provider.detect()?);
+                }
+                Ok(rows)
+            }),
+            "GetJson" => {
+                let sections = parameters.get::<(Vec<String>,)>().map(|(sections,)| sections).unwrap_or_default();
+                reply(invocation, move || {
+                    let config = crate::create_auto_detected_config(crate::load_saved_settings());
+                    let registry = ProviderRegistry::from_config(&config);
+                    Ok(report::json_report(&config, &registry, &sections).to_string())
+                })
+            }
+            "ShowWindow" => {
+                let tab = parameters.get::<(String,)>().map(|(tab,)| tab).unwrap_or_default();
+                if let Some(app) = gio::Application::default() {
+                    app.activate_action("show-tab", Some(&tab.to_variant()));
+                }
+                invocation.return_value(None);
+            }
+            _ => invocation.return_dbus_error(ERROR_NAME, &format!("Unknown method {}", method)),
+        },
+        |_, _, _, _, _| ().to_variant(),
+        |_, _, _, _, _, _| false,
+    );
+    if let Err(e) = result {
+        eprintln!("Failed to export the D-Bus service: {}", e);
+        return;
+    }
+
+    gio::bus_own_name_on_connection(
+        &connection,
+        BUS_NAME,
+        gio::BusNameOwnerFlags::NONE,
+        |_, _| {},
+        |_, name| eprintln!("Failed to own {} on the session bus, is another instance running?", name),
+    );
+}
+
+// Detection can take seconds (SMART, lshw), so it runs off the main loop and
+// the caller gets the reply when it is done
+fn reply<T>(invocation: gio::DBusMethodInvocation, detect: impl FnOnce() -> anyhow::Result<T> + Send + 'static)
+where
+    T: ToVariant + StaticVariantType + Send + 'static,
+{
+    glib::spawn_future_local(async move {
+        match gio::spawn_blocking(detect).await {
+            Ok(Ok(value)) => invocation.return_value(Some(&(value,).to_variant())),
+            Ok(Err(e)) => invocation.return_dbus_error(ERROR_NAME, &format!("{:#}", e)),
+            Err(_) => invocation.return_dbus_error(ERROR_NAME, "Detection failed unexpectedly"),
+        }
+    });
+}
@@ -0,0 +1,158 @@
+use anyhow::{bail, Context, Result};
+use gtk::prelude::*;
+use gtk::{gio, glib};
+use serde::Serialize;
+
+use crate::{journal, system_info, units};
+
+// The login session this process runs in, as systemd-logind sees it. Unlike
+// $XDG_SESSION_TYPE it can't be inherited from another terminal or session.
+const LOGIN1_NAME: &str = "org.freedesktop.login1";
+const LOGIN1_PATH: &str = "/org/freedesktop/login1";
+const SESSION_INTERFACE: &str = "org.freedesktop.login1.Session";
+const CALL_TIMEOUT_MS: i32 = 2000;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionInfo {
+    pub id: String,
+    pub user: String,
+    // wayland, x11, mir, tty or unspecified
+    pub session_type: String,
+    pub seat: Option<String>,
+    pub remote: bool,
+    pub remote_host: Option<String>,
+    // PAM service of the login, e.g. gdm-password or sshd
+    pub service: Option<String>,
+    pub tty: Option<String>,
+    // Unix time of the login
+    pub login_time: Option<i64>,
+}
+
+impl SessionInfo {
+    /// "Wayland", "X11" or "TTY" as people say it
+    pub fn type_name(&self) -> String {
+        match self.session_type.as_str() {
+            "wayland" => "Wayland".to_string(),
+            "x11" => "X11".to_string(),
+            "mir" => "Mir".to_string(),
+            "tty" => "TTY".to_string(),
+            other => other.to_string(),
+        }
+    }
+
+    pub fn location(&self) -> String {
+        match (&self.remote_host, self.remote) {
+            (Some(host), true) => format!("Remote, from {}", host),
+            (None, true) => "Remote".to_string(),
+            _ => "Local".to_string(),
+        }
+    }
+
+    /// "Wayland on seat0, local, since 2026-10-17 09:12:44"
+    pub fn summary(&self) -> String {
+        let mut summary = self.type_name();
+        if let Some(seat) = &self.seat {
+            summary.push_str(&format!(" on {}", seat));
+        }
+        summary.push_str(&format!(", {}", self.location().to_lowercase()));
+        if let Some(login_time) = self.login_time {
+            summary.push_str(&format!(", since {}", units::format_timestamp(login_time)));
+        }
+        summary
+    }
+
+    pub fn rows(&self) -> Vec<(String, String)> {
+        let mut rows = vec![("Session Type".to_string(), self.type_name())];
+        if let Some(seat) = &self.seat {
+            rows.push(("Seat".to_string(), seat.clone()));
+        }
+        rows.push(("Login".to_string(), self.location()));
+        if let Some(login_time) = self.login_time {
+            rows.push(("Logged In".to_string(), units::format_timestamp(login_time)));
+        }
+        rows.push(("User".to_string(), self.user.clone()));
+        rows.push(("Session ID".to_string(), self.id.clone()));
+        if let Some(service) = &self.service {
+            rows.push(("Login Service".to_string(), service.clone()));
+        }
+        if let Some(tty) = &self.tty {
+            rows.push(("TTY".to_string(), tty.clone()));
+        }
+        rows
+    }
+}
+
+pub fn detect() -> Result<SessionInfo> {
+    if system_info::detection_disabled() {
+        bail!("detection is disabled (--no-detect)");
+    }
+    journal::timed("session", "logind", detect_untimed)
+}
+
+fn detect_untimed() -> Result<SessionInfo> {
+    let connection = gio::bus_get_sync(gio::BusType::System, gio::Cancellable::NONE).context("No system bus")?;
+    let call = |path: &str, interface: &str, method: &str, args: glib::Variant| {
+        connection
+            .call_sync(
+                Some(LOGIN1_NAME),
+                path,
+                interface,
+                method,
+                Some(&args),
+                None,
+                gio::DBusCallFlags::NONE,
+                CALL_TIMEOUT_MS,
+                gio::Cancellable::NONE,
+            )
+            .with_context(|| format!("{} failed", method))
+    };
+
+    // "auto" is the caller's own session, for when the variable isn't set
+    let id = std::env::var("XDG_SESSION_ID").unwrap_or_else(|_| "auto".to_string());
+    let reply = call(
+        LOGIN1_PATH,
+        "org.freedesktop.login1.Manager",
+        "GetSession",
+        (id.as_str(),).to_variant(),
+    )?;
+    let path = reply
+        .child_value(0)
+        .str()
+        .map(str::to_string)
+        .context("GetSession returned no session")?;
+
+    let reply = call(
+        &path,
+        "org.freedesktop.DBus.Properties",
+        "GetAll",
+        (SESSION_INTERFACE,).to_variant(),
+    )?;
+    let properties = glib::VariantDict::new(Some(&reply.child_value(0)));
+    let value = |name: &str| properties.lookup_value(name, None);
+    let text = |name: &str| {
+        value(name)
+            .and_then(|value| value.str().map(|text| text.to_string()))
+            .filter(|text| !text.is_empty())
+    };
+    // Seat and User are (name, object path) pairs
+    let first_of_pair = |name: &str| {
+        value(name)
+            .and_then(|pair| pair.child_value(0).str().map(|text| text.to_string()))
+            .filter(|text| !text.is_empty())
+    };
+
+    Ok(SessionInfo {
+        id: text("Id").unwrap_or(id),
+        user: text("Name").unwrap_or_default(),
+        session_type: text("Type").unwrap_or_else(|| "unspecified".to_string()),
+        seat: first_of_pair("Seat"),
+        remote: value("Remote").and_then(|remote| remote.get::<bool>()).unwrap_or(false),
+        remote_host: text("RemoteHost"),
+        service: text("Service"),
+        tty: text("TTY"),
+        login_time: value("Timestamp")
+            .and_then(|timestamp| timestamp.get::<u64>())
+            .filter(|microseconds| *microseconds > 0)
+            .map(|microseconds| (microseconds / 1_000_000) as i64),
+    })
+}
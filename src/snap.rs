@@ -0,0 +1,128 @@
+use anyhow::{bail, Context, Result};
+use gtk::glib;
+use serde::Serialize;
+use serde_json::Value;
+use std::io::{Read, Write};
+use std::os::unix::net::UnixStream;
+use std::time::Duration;
+
+use crate::{host, journal, system_info, units};
+
+// snapd answers its REST API on a Unix socket, readable without root
+const SNAPD_SOCKET: &str = "/run/snapd.socket";
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(5);
+// "Hold forever" is stored as a date centuries away
+const INDEFINITE_HOLD_YEAR: i32 = 2100;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapInfo {
+    pub installed: usize,
+    // Snaps held individually with `snap refresh --hold <snap>`
+    pub held: Vec<String>,
+    // Unix time until which all refreshes are held, from `snap refresh --hold`
+    pub system_hold: Option<i64>,
+    pub last_refresh: Option<i64>,
+    pub next_refresh: Option<i64>,
+}
+
+impl SnapInfo {
+    pub fn refresh_text(&self) -> String {
+        match self.system_hold {
+            Some(until) => format!("All held {}", hold_text(until)),
+            None if !self.held.is_empty() => format!("Automatic, except {}", self.held.join(", ")),
+            None => "Automatic".to_string(),
+        }
+    }
+
+    pub fn rows(&self) -> Vec<(String, String)> {
+        let mut rows = vec![
+            ("Snaps".to_string(), format!("{} installed", self.installed)),
+            ("Snap Refreshes".to_string(), self.refresh_text()),
+        ];
+        if let Some(last) = self.last_refresh {
+            rows.push(("Last Snap Refresh".to_string(), units::format_timestamp(last)));
+        }
+        if let Some(next) = self.next_refresh.filter(|_| self.system_hold.is_none()) {
+            rows.push(("Next Snap Refresh".to_string(), units::format_timestamp(next)));
+        }
+        rows
+    }
+}
+
+/// Whether snapd is installed and listening
+pub fn available() -> bool {
+    !system_info::detection_disabled() && host::path(SNAPD_SOCKET).exists()
+}
+
+pub fn detect() -> Result<SnapInfo> {
+    if system_info::detection_disabled() {
+        bail!("detection is disabled (--no-detect)");
+    }
+    journal::timed("snaps", "snapd", detect_untimed)
+}
+
+fn detect_untimed() -> Result<SnapInfo> {
+    let snaps = get("/v2/snaps")?;
+    let snaps = snaps.as_array().context("snapd returned no snap list")?;
+    let held = snaps
+        .iter()
+        .filter(|snap| snap.get("hold").and_then(Value::as_str).is_some())
+        .filter_map(|snap| snap.get("name").and_then(Value::as_str))
+        .map(str::to_string)
+        .collect();
+
+    // Older snapd versions don't report refresh times; the count still stands
+    let system = get("/v2/system-info").unwrap_or(Value::Null);
+    let refresh_time = |name: &str| system.pointer(&format!("/refresh/{}", name)).and_then(parse_time);
+    // An expired hold is left in place until the next refresh
+    let now = glib::DateTime::now_utc().map(|now| now.to_unix()).unwrap_or_default();
+
+    Ok(SnapInfo {
+        installed: snaps.len(),
+        held,
+        system_hold: refresh_time("hold").filter(|until| *until > now),
+        last_refresh: refresh_time("last"),
+        next_refresh: refresh_time("next"),
+    })
+}
+
+fn parse_time(value: &Value) -> Option<i64> {
+    let text = value.as_str()?;
+    glib::DateTime::from_iso8601(text, None).ok().map(|time| time.to_unix())
+}
+
+fn hold_text(until: i64) -> String {
+    let year = glib::DateTime::from_unix_local(until)
+        .map(|time| time.year())
+        .unwrap_or(0);
+    if year >= INDEFINITE_HOLD_YEAR {
+        "indefinitely".to_string()
+    } else {
+        format!("until {}", units::format_timestamp(until))
+    }
+}
+
+// The "result" of a GET; HTTP/1.0 so snapd closes the connection after a
+// plain, unchunked body
+fn get(endpoint: &str) -> Result<Value> {
+    let mut stream = UnixStream::connect(host::path(SNAPD_SOCKET)).context("snapd is not running")?;
+    stream.set_read_timeout(Some(SOCKET_TIMEOUT))?;
+    stream.set_write_timeout(Some(SOCKET_TIMEOUT))?;
+    write!(stream, "GET {} HTTP/1.0\r\nHost: snapd\r\n\r\n", endpoint)?;
+
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .context("Failed to read the snapd response")?;
+    let (_, body) = response
+        .split_once("\r\n\r\n")
+        .context("snapd sent a malformed response")?;
+    let body: Value = serde_json::from_str(body).context("snapd sent invalid JSON")?;
+
+    let result = body.get("result").cloned().unwrap_or(Value::Null);
+    if body.get("type").and_then(Value::as_str) == Some("error") {
+        let message = result.get("message").and_then(Value::as_str).unwrap_or("unknown error");
+        bail!("snapd: {}", message);
+    }
+    Ok(result)
+}
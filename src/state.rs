@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+use crate::paths;
+
+/// Where the window was left when it was last closed, so the next launch
+/// opens on the same tab, size and monitor. Kept apart from the config so
+/// closing the window never rewrites it.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowState {
+    pub page: Option<String>,
+    // Connector name of the monitor, e.g. "DP-1"
+    pub monitor: Option<String>,
+    pub size: Option<[i32; 2]>,
+}
+
+impl WindowState {
+    pub fn load() -> Self {
+        let path = paths::state_path();
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(_) => return WindowState::default(),
+        };
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            eprintln!("Ignoring window state in {}: {}", path.display(), e);
+            WindowState::default()
+        })
+    }
+
+    pub fn save(&self) {
+        let path = paths::state_path();
+        let result = serde_json::to_string_pretty(self)
+            .map_err(anyhow::Error::from)
+            .and_then(|json| std::fs::write(&path, json).map_err(anyhow::Error::from));
+        if let Err(e) = result {
+            eprintln!("Failed to save window state to {}: {}", path.display(), e);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use gtk::prelude::*;
+use gtk::{Box, Image, Label, LevelBar, Orientation};
+
+// Colors for the status-* classes live in data/style.css
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    Ok,
+    Warning,
+    Critical,
+}
+
+impl Status {
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            Status::Ok => "status-ok",
+            Status::Warning => "status-warning",
+            Status::Critical => "status-critical",
+        }
+    }
+
+    pub fn icon_name(&self) -> &'static str {
+        match self {
+            Status::Ok => "emblem-ok-symbolic",
+            Status::Warning => "dialog-warning-symbolic",
+            Status::Critical => "dialog-error-symbolic",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Status::Ok => "OK",
+            Status::Warning => "Warning",
+            Status::Critical => "Critical",
+        }
+    }
+
+    pub fn from_smart_health(health: &str) -> Self {
+        let health = health.to_uppercase();
+        if health.contains("PASSED") || health == "OK" {
+            Status::Ok
+        } else if health.contains("FAIL") {
+            Status::Critical
+        } else {
+            Status::Warning
+        }
+    }
+
+    /// Status of a CPU vulnerability from its sysfs text, e.g. "Not affected"
+    /// or "Mitigation: PTI"
+    pub fn from_vulnerability(status: &str) -> Self {
+        if status.starts_with("Vulnerable") {
+            Status::Critical
+        } else if status.to_lowercase().contains("vulnerable") {
+            // Mitigated, but with a caveat such as "SMT vulnerable"
+            Status::Warning
+        } else if status == "Not affected" || status.starts_with("Mitigation") {
+            Status::Ok
+        } else {
+            Status::Warning
+        }
+    }
+
+    pub fn from_usage_percent(percent: f32) -> Self {
+        if percent >= 90.0 {
+            Status::Critical
+        } else if percent >= 75.0 {
+            Status::Warning
+        } else {
+            Status::Ok
+        }
+    }
+}
+
+/// Icon and text pair styled for the given status
+pub fn status_widget(status: Status, text: &str) -> Box {
+    let status_box = Box::new(Orientation::Horizontal, 6);
+    status_box.add_css_class(status.css_class());
+
+    let icon = Image::from_icon_name(status.icon_name());
+    icon.set_tooltip_text(Some(status.description()));
+    icon.update_property(&[gtk::accessible::Property::Label(status.description())]);
+    status_box.append(&icon);
+
+    let label = Label::new(Some(text));
+    label.set_halign(gtk::Align::Start);
+    status_box.append(&label);
+
+    status_box
+}
+
+/// Usage bar colored by how full it is
+pub fn usage_bar(percent: f32) -> LevelBar {
+    let bar = LevelBar::for_interval(0.0, 100.0);
+    bar.set_size_request(160, -1);
+    bar.set_valign(gtk::Align::Center);
+
+    // Drop the built-in low/high/full offsets so only our class colors it
+    bar.remove_offset_value(Some(gtk::LEVEL_BAR_OFFSET_LOW));
+    bar.remove_offset_value(Some(gtk::LEVEL_BAR_OFFSET_HIGH));
+    bar.remove_offset_value(Some(gtk::LEVEL_BAR_OFFSET_FULL));
+    set_usage(&bar, percent);
+
+    bar
+}
+
+/// Update a bar made by `usage_bar`, recoloring it if the status changed
+pub fn set_usage(bar: &LevelBar, percent: f32) {
+    bar.set_value(percent.clamp(0.0, 100.0) as f64);
+    for status in [Status::Ok, Status::Warning, Status::Critical] {
+        bar.remove_css_class(status.css_class());
+    }
+    bar.add_css_class(Status::from_usage_percent(percent).css_class());
+}
+
+/// Info icon whose tooltip explains why a value could not be detected
+pub fn detection_error_icon(reason: &str) -> Image {
+    let icon = Image::from_icon_name("dialog-information-symbolic");
+    icon.set_tooltip_text(Some(reason));
+    icon.update_property(&[
+        gtk::accessible::Property::Label("Not detected"),
+        gtk::accessible::Property::Description(reason),
+    ]);
+    icon
+}
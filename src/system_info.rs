@@ -1,9 +1,16 @@
 use anyhow::{Context, Result};
 use regex::Regex;
-use std::collections::HashMap;
-use std::process::Command;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
-use crate::config::Config;
+use crate::config::{Backend, Config};
+use crate::host;
+use crate::hostnamed;
+use crate::journal::{self, Outcome};
+use crate::power;
+use crate::units;
 
 #[derive(Debug, Clone)]
 pub struct SystemInfo {
@@ -13,14 +20,106 @@ pub struct SystemInfo {
     pub startup_disk: String,
     pub graphics: String,
     pub serial_number: String,
+    // Why a field fell back to "Unknown", keyed by config field name
+    pub errors: HashMap<String, DetectError>,
 }
 
+/// A detector failure: which tool or source failed, and why
 #[derive(Debug, Clone)]
+pub struct DetectError {
+    pub tool: String,
+    pub reason: String,
+}
+
+impl DetectError {
+    pub fn new(tool: &str, reason: impl Into<String>) -> Self {
+        DetectError {
+            tool: tool.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    fn from_io(tool: &str, error: &std::io::Error) -> Self {
+        let reason = match error.kind() {
+            std::io::ErrorKind::NotFound => "not installed".to_string(),
+            std::io::ErrorKind::PermissionDenied => "permission denied".to_string(),
+            _ => error.to_string(),
+        };
+        DetectError::new(tool, reason)
+    }
+}
+
+impl std::fmt::Display for DetectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.tool, self.reason)
+    }
+}
+
+impl std::error::Error for DetectError {}
+
+/// Run an external tool and return its stdout, turning spawn failures and
+/// empty failed runs into a `DetectError`. Tools like smartctl exit non-zero
+/// with useful output, so output wins over the exit status.
+pub fn run_tool(tool: &str, args: &[&str]) -> std::result::Result<String, DetectError> {
+    let step = format!("{} {}", tool, args.join(" "));
+    journal::timed(step.trim(), tool, || run_tool_untimed(tool, args))
+}
+
+fn run_tool_untimed(tool: &str, args: &[&str]) -> std::result::Result<String, DetectError> {
+    let output = host::command(tool)
+        .args(args)
+        .output()
+        .map_err(|e| DetectError::from_io(tool, &e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    if output.status.success() || !stdout.trim().is_empty() {
+        return Ok(stdout);
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stderr_lower = stderr.to_lowercase();
+    if stderr_lower.contains("permission denied")
+        || stderr_lower.contains("operation not permitted")
+        || stderr_lower.contains("must be root")
+    {
+        return Err(DetectError::new(tool, "permission denied"));
+    }
+
+    let reason = stderr
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .map(|line| line.trim().to_string())
+        .unwrap_or_else(|| format!("exited with {}", output.status));
+    Err(DetectError::new(tool, reason))
+}
+
+fn unwrap_field(
+    key: &str,
+    result: std::result::Result<String, DetectError>,
+    fallback: &str,
+    errors: &mut HashMap<String, DetectError>,
+) -> String {
+    match result {
+        Ok(value) => value,
+        Err(e) => {
+            journal::record(
+                key,
+                &e.tool,
+                Duration::ZERO,
+                Outcome::Fallback(format!("showing \"{}\" because {}", fallback, e)),
+            );
+            errors.insert(key.to_string(), e);
+            fallback.to_string()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct DisplayInfo {
     pub displays: Vec<Display>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Display {
     pub name: String,
     pub resolution: String,
@@ -34,7 +133,7 @@ pub struct Display {
     pub connection_type: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DynamicSystemInfo {
     pub distro_name: String,
     pub distro_version: String,
@@ -42,13 +141,13 @@ pub struct DynamicSystemInfo {
     pub kernel: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct StorageInfo {
     pub devices: Vec<StorageDevice>,
     pub filesystems: Vec<Filesystem>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct StorageDevice {
     pub name: String,
     pub model: String,
@@ -56,11 +155,13 @@ pub struct StorageDevice {
     pub device_type: String, // SSD, HDD, NVMe, etc.
     pub interface: String,   // SATA, NVMe, USB, etc.
     pub serial: String,
-    pub temperature: Option<String>,
-    pub health: Option<String>,
+    #[serde(serialize_with = "serialize_detected")]
+    pub temperature: std::result::Result<String, DetectError>,
+    #[serde(serialize_with = "serialize_detected")]
+    pub health: std::result::Result<String, DetectError>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Filesystem {
     pub device: String,
     pub mountpoint: String,
@@ -71,83 +172,132 @@ pub struct Filesystem {
     pub usage_percent: f32,
 }
 
+// Failed detections are written as null
+fn serialize_detected<S: serde::Serializer>(
+    value: &std::result::Result<String, DetectError>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    value.as_ref().ok().serialize(serializer)
+}
+
 impl SystemInfo {
     pub fn detect() -> Result<Self> {
-        let fastfetch_info = get_fastfetch_info()?;
-        let memory_info = get_memory_info()?;
-        let startup_disk = get_startup_disk()?;
-        let serial_number = get_serial_number().unwrap_or_else(|_| "Unknown".to_string());
-
-        // Parse hostname
-        let hostname = fastfetch_info
-            .get("Host")
-            .cloned()
-            .unwrap_or_else(|| {
-                std::env::var("HOSTNAME")
-                    .or_else(|_| {
-                        Command::new("hostname")
-                            .output()
-                            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
-                    })
-                    .unwrap_or_else(|_| "Unknown Host".to_string())
+        ensure_detection_enabled()?;
+        journal::timed("system info", backend_name(), || {
+            if native_backend() {
+                detect_system_info_native()
+            } else {
+                Self::detect_with_commands()
+            }
+        })
+    }
+
+    fn detect_with_commands() -> Result<Self> {
+        let mut errors = HashMap::new();
+        let fastfetch_info = get_fastfetch_info();
+        let fastfetch_field = |key: &str| match &fastfetch_info {
+            Ok(info) => info
+                .get(key)
+                .cloned()
+                .ok_or_else(|| DetectError::new("fastfetch", format!("no {} entry in output", key))),
+            Err(e) => Err(e.clone()),
+        };
+
+        // hostnamed's vendor and model, then fastfetch's guess, then the
+        // names the user gave the machine
+        let identity = hostnamed::identity();
+        let hostname = identity
+            .and_then(|identity| identity.device_name())
+            .ok_or_else(|| DetectError::new("hostnamed", "no hardware model reported"))
+            .or_else(|_| fastfetch_field("Host"))
+            .or_else(|e| identity.and_then(|identity| identity.pretty_hostname.clone()).ok_or(e))
+            .or_else(|_| {
+                std::env::var("HOSTNAME").or_else(|_| {
+                    run_tool("hostname", &[]).map(|output| output.trim().to_string())
+                })
             });
 
         // Parse CPU
-        let cpu = fastfetch_info
-            .get("CPU")
-            .map(|cpu_info| {
-                // Reverse the order if it contains @ (frequency)
-                if cpu_info.contains('@') {
-                    let parts: Vec<&str> = cpu_info.split('@').collect();
-                    if parts.len() == 2 {
-                        format!("{} {}", parts[1].trim(), parts[0].trim())
-                    } else {
-                        cpu_info.clone()
-                    }
+        let cpu = fastfetch_field("CPU").map(|cpu_info| {
+            // Reverse the order if it contains @ (frequency)
+            if cpu_info.contains('@') {
+                let parts: Vec<&str> = cpu_info.split('@').collect();
+                if parts.len() == 2 {
+                    format!("{} {}", parts[1].trim(), parts[0].trim())
                 } else {
                     cpu_info.clone()
                 }
-            })
-            .unwrap_or_else(|| "Unknown CPU".to_string());
+            } else {
+                cpu_info.clone()
+            }
+        });
 
         // Parse graphics
-        let graphics = fastfetch_info
-            .get("GPU")
-            .cloned()
-            .unwrap_or_else(|| "Unknown Graphics".to_string());
+        let graphics = fastfetch_field("GPU");
 
         Ok(SystemInfo {
-            hostname,
-            cpu,
-            memory: memory_info,
-            startup_disk,
-            graphics,
-            serial_number,
+            hostname: unwrap_field("hostname", hostname, "Unknown Host", &mut errors),
+            cpu: unwrap_field("cpu", cpu, "Unknown CPU", &mut errors),
+            memory: unwrap_field("memory", get_memory_info(), "Unknown Memory", &mut errors),
+            startup_disk: unwrap_field("startup_disk", get_startup_disk(), "Unknown", &mut errors),
+            graphics: unwrap_field("graphics", graphics, "Unknown Graphics", &mut errors),
+            serial_number: unwrap_field("serial_num", get_serial_number(), "Unknown", &mut errors),
+            errors,
         })
     }
 
-    pub fn to_config(&self, image_path: String) -> Config {
-        Config {
-            distro_image_path: image_path,
-            distro_image_size: [512, 512],
-            hostname: self.hostname.clone(),
-            cpu: self.cpu.clone(),
-            memory: self.memory.clone(),
-            startup_disk: self.startup_disk.clone(),
-            graphics: self.graphics.clone(),
-            serial_num: self.serial_number.clone(),
-            overview_margins: [60, 60, 60, 60],
-            section_space: 20,
-            logo_space: 60,
-            system_info_command: "".to_string(),
-            software_update_command: "".to_string(),
-            font_family: None,
-        }
+    /// Fill in the detected overview fields, leaving the rest of `config` alone
+    pub fn apply_to(&self, config: &mut Config) {
+        config.hostname = self.hostname.clone();
+        config.cpu = self.cpu.clone();
+        config.memory = self.memory.clone();
+        config.startup_disk = self.startup_disk.clone();
+        config.graphics = self.graphics.clone();
+        config.serial_num = self.serial_number.clone();
     }
 }
 
 impl DynamicSystemInfo {
     pub fn detect() -> Result<Self> {
+        ensure_detection_enabled()?;
+        journal::timed("distribution", "os-release", Self::detect_untimed)
+    }
+
+    /// What the overview shows: detected values, overridden by the config's
+    /// `distro_name`, `distro_version` and `kernel`. With detection disabled
+    /// anything not in the config is left empty.
+    pub fn for_config(config: &Config) -> Self {
+        let mut info = if detection_disabled() {
+            DynamicSystemInfo {
+                distro_name: String::new(),
+                distro_version: String::new(),
+                distro_codename: None,
+                kernel: String::new(),
+            }
+        } else {
+            Self::detect().unwrap_or_else(|_| DynamicSystemInfo {
+                distro_name: "Unknown Linux".to_string(),
+                distro_version: "Unknown".to_string(),
+                distro_codename: None,
+                kernel: "Unknown".to_string(),
+            })
+        };
+
+        if let Some(name) = &config.distro_name {
+            info.distro_name = name.clone();
+            // The detected codename belongs to the detected name
+            info.distro_codename = None;
+        }
+        if let Some(version) = &config.distro_version {
+            info.distro_version = version.clone();
+        }
+        if let Some(kernel) = &config.kernel {
+            info.kernel = kernel.clone();
+        }
+        info
+    }
+
+    fn detect_untimed() -> Result<Self> {
         let os_release_info = get_os_release_info()?;
         let kernel = get_kernel_version()?;
 
@@ -186,27 +336,18 @@ impl DynamicSystemInfo {
     }
 }
 
-fn get_fastfetch_info() -> Result<HashMap<String, String>> {
-    let output = Command::new("fastfetch")
-        .args(&["--format", "json"])
-        .output()
-        .or_else(|_| {
-            // Fallback to plain text format if JSON fails
-            Command::new("fastfetch")
-                .args(&["--logo", "none"])
-                .output()
-        })
-        .context("Failed to run fastfetch. Please make sure fastfetch is installed.")?;
+fn get_fastfetch_info() -> std::result::Result<HashMap<String, String>, DetectError> {
+    let output_str = run_tool("fastfetch", &["--format", "json"])
+        // Fallback to plain text format if JSON fails
+        .or_else(|_| run_tool("fastfetch", &["--logo", "none"]))?;
 
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    
     // Try to parse as JSON first
     if let Ok(json_data) = serde_json::from_str::<serde_json::Value>(&output_str) {
-        return parse_fastfetch_json(json_data);
+        return parse_fastfetch_json(json_data).map_err(|e| DetectError::new("fastfetch", e.to_string()));
     }
     
     // Fall back to plain text parsing
-    parse_fastfetch_text(&output_str)
+    parse_fastfetch_text(&output_str).map_err(|e| DetectError::new("fastfetch", e.to_string()))
 }
 
 fn parse_fastfetch_json(json_data: serde_json::Value) -> Result<HashMap<String, String>> {
@@ -258,8 +399,7 @@ fn parse_fastfetch_json(json_data: serde_json::Value) -> Result<HashMap<String,
                     "Memory" => {
                         if let Some(result) = item["result"].as_object() {
                             if let Some(total) = result["total"].as_u64() {
-                                let total_gb = total as f64 / 1024.0 / 1024.0 / 1024.0;
-                                info.insert("Memory".to_string(), format!("{:.1} GB", total_gb));
+                                info.insert("Memory".to_string(), units::format_bytes(total));
                             }
                         }
                     },
@@ -285,9 +425,8 @@ fn parse_fastfetch_json(json_data: serde_json::Value) -> Result<HashMap<String,
                                         if mountpoint == "/" {
                                             if let Some(bytes) = disk_obj["bytes"].as_object() {
                                                 if let Some(total) = bytes["total"].as_u64() {
-                                                    let total_gb = total as f64 / 1024.0 / 1024.0 / 1024.0;
                                                     let filesystem = disk_obj["filesystem"].as_str().unwrap_or("Unknown");
-                                                    info.insert("Startup Disk".to_string(), format!("{:.1} GB ({})", total_gb, filesystem));
+                                                    info.insert("Startup Disk".to_string(), format!("{} ({})", units::format_bytes(total), filesystem));
                                                     break;
                                                 }
                                             }
@@ -353,18 +492,34 @@ fn parse_fastfetch_text(output_str: &str) -> Result<HashMap<String, String>> {
 }
 
 fn get_os_release_info() -> Result<HashMap<String, String>> {
-    let output = std::fs::read_to_string("/etc/os-release")
+    // /usr/lib/os-release is the fallback the spec gives for systems
+    // without /etc/os-release
+    let output = std::fs::read_to_string(host::path("/etc/os-release"))
+        .or_else(|_| std::fs::read_to_string(host::path("/usr/lib/os-release")))
         .context("Failed to read /etc/os-release")?;
 
     let mut info = HashMap::new();
     
     for line in output.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
         if let Some(eq_pos) = line.find('=') {
             let key = line[..eq_pos].trim().to_string();
-            let value = line[eq_pos + 1..]
-                .trim()
-                .trim_matches('"')
-                .to_string();
+            let value = line[eq_pos + 1..].trim();
+            // Values may be double or single quoted, with \" escapes
+            let value = value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .map(|value| value.replace("\\\"", "\"").replace("\\$", "$").replace("\\\\", "\\"))
+                .or_else(|| {
+                    value
+                        .strip_prefix('\'')
+                        .and_then(|value| value.strip_suffix('\''))
+                        .map(|value| value.to_string())
+                })
+                .unwrap_or_else(|| value.to_string());
             info.insert(key, value);
         }
     }
@@ -372,7 +527,59 @@ fn get_os_release_info() -> Result<HashMap<String, String>> {
     Ok(info)
 }
 
-fn get_memory_info() -> Result<String> {
+/// Icon names the distribution's logo may have in the icon theme, most
+/// specific first: os-release's LOGO, then names derived from ID and ID_LIKE
+pub fn logo_icon_names() -> Vec<String> {
+    let os_release = get_os_release_info().unwrap_or_default();
+    let mut names: Vec<String> = os_release.get("LOGO").cloned().into_iter().collect();
+
+    for id in distro_ids() {
+        names.push(format!("distributor-logo-{}", id));
+        names.push(format!("{}-logo", id));
+    }
+    names.push("distributor-logo".to_string());
+    names
+}
+
+/// os-release ID followed by the ID_LIKE parents, e.g. ["linuxmint",
+/// "ubuntu", "debian"]
+pub fn distro_ids() -> Vec<String> {
+    let os_release = get_os_release_info().unwrap_or_default();
+    os_release
+        .get("ID")
+        .into_iter()
+        .chain(os_release.get("ID_LIKE"))
+        .flat_map(|ids| ids.split_whitespace())
+        .map(str::to_string)
+        .collect()
+}
+
+/// VERSION_ID and BUILD_ID from os-release and the kernel build string
+/// (`uname -v`), one per line, for the version label's detail view
+pub fn build_details() -> String {
+    let os_release = get_os_release_info().unwrap_or_default();
+    let mut lines = Vec::new();
+    if let Some(version_id) = os_release.get("VERSION_ID") {
+        lines.push(format!("Version {}", version_id));
+    }
+    if let Some(build_id) = os_release.get("BUILD_ID") {
+        lines.push(format!("Build {}", build_id));
+    }
+    if let Ok(output) = host::command("uname").arg("-v").output() {
+        let kernel_build = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !kernel_build.is_empty() {
+            lines.push(kernel_build);
+        }
+    }
+
+    if lines.is_empty() {
+        "No build details available".to_string()
+    } else {
+        lines.join("\n")
+    }
+}
+
+fn get_memory_info() -> std::result::Result<String, DetectError> {
     // Try multiple methods to get memory information without root access
     
     // Method 1: Try /proc/meminfo for total memory
@@ -391,14 +598,13 @@ fn get_memory_info() -> Result<String> {
         }
         
         if total_kb > 0 {
-            let total_gb = total_kb as f64 / 1024.0 / 1024.0;
             
             // Try to get additional info from dmidecode without sudo (if available)
             let mut memory_type = String::new();
             let mut speed = String::new();
             
             // Try dmidecode without sudo first
-            if let Ok(output) = Command::new("dmidecode")
+            if let Ok(output) = host::command("dmidecode")
                 .args(&["--type", "memory"])
                 .output() {
                 let output_str = String::from_utf8_lossy(&output.stdout);
@@ -422,7 +628,7 @@ fn get_memory_info() -> Result<String> {
             
             // Try lshw as alternative (may work without sudo on some systems)
             if memory_type.is_empty() || speed.is_empty() {
-                if let Ok(output) = Command::new("lshw")
+                if let Ok(output) = host::command("lshw")
                     .args(&["-class", "memory", "-short"])
                     .output() {
                     let output_str = String::from_utf8_lossy(&output.stdout);
@@ -440,14 +646,8 @@ fn get_memory_info() -> Result<String> {
                 }
             }
             
-            // Format the result
-            let (size, unit) = if total_gb >= 1024.0 {
-                (total_gb / 1024.0, "TB")
-            } else {
-                (total_gb, "GB")
-            };
-            
-            let mut result = format!("{:.1} {}", size, unit);
+            // MemTotal is reported in KiB
+            let mut result = units::format_bytes(total_kb * 1024);
             
             if !speed.is_empty() {
                 result.push_str(&format!(" {}", speed));
@@ -462,7 +662,7 @@ fn get_memory_info() -> Result<String> {
     }
     
     // Method 2: Try using free command
-    if let Ok(output) = Command::new("free")
+    if let Ok(output) = host::command("free")
         .args(&["-h"])
         .output() {
         let output_str = String::from_utf8_lossy(&output.stdout);
@@ -477,17 +677,11 @@ fn get_memory_info() -> Result<String> {
         }
     }
     
-    // Fallback
-    Ok("Unknown Memory".to_string())
+    Err(DetectError::new("/proc/meminfo", "total memory not found"))
 }
 
-fn get_startup_disk() -> Result<String> {
-    let output = Command::new("lsblk")
-        .args(&["-o", "mountpoint,name,label", "--list"])
-        .output()
-        .context("Failed to run lsblk")?;
-
-    let output_str = String::from_utf8_lossy(&output.stdout);
+fn get_startup_disk() -> std::result::Result<String, DetectError> {
+    let output_str = run_tool("lsblk", &["-o", "mountpoint,name,label", "--list"])?;
     
     for line in output_str.lines() {
         let parts: Vec<&str> = line.split_whitespace().collect();
@@ -500,11 +694,11 @@ fn get_startup_disk() -> Result<String> {
         }
     }
 
-    Ok("Unknown".to_string())
+    Err(DetectError::new("lsblk", "no device mounted at /"))
 }
 
 fn get_kernel_version() -> Result<String> {
-    let output = Command::new("uname")
+    let output = host::command("uname")
         .args(&["-r"])
         .output()
         .context("Failed to get kernel version")?;
@@ -512,16 +706,15 @@ fn get_kernel_version() -> Result<String> {
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
 
-fn get_serial_number() -> Result<String> {
+fn get_serial_number() -> std::result::Result<String, DetectError> {
     // Try multiple methods to get serial number without root access
+    // Report the dmidecode failure if nothing else works, it's the most telling
+    let mut last_error = DetectError::new("dmidecode", "no serial number reported");
     
     // Method 1: Try dmidecode without sudo (may work on some systems)
-    if let Ok(output) = Command::new("dmidecode")
-        .args(&["--type", "baseboard"])
-        .output() {
-        let output_str = String::from_utf8_lossy(&output.stdout);
-        
-        for line in output_str.lines() {
+    match run_tool("dmidecode", &["--type", "baseboard"]) {
+        Err(e) => last_error = e,
+        Ok(output_str) => for line in output_str.lines() {
             let line = line.trim();
             if line.starts_with("Serial Number:") {
                 if let Some(serial_part) = line.split(':').nth(1) {
@@ -531,7 +724,7 @@ fn get_serial_number() -> Result<String> {
                     }
                 }
             }
-        }
+        },
     }
     
     // Method 2: Try reading from /sys/class/dmi/id/board_serial
@@ -551,7 +744,7 @@ fn get_serial_number() -> Result<String> {
     }
     
     // Method 4: Try lshw without sudo
-    if let Ok(output) = Command::new("lshw")
+    if let Ok(output) = host::command("lshw")
         .args(&["-class", "system", "-short"])
         .output() {
         let output_str = String::from_utf8_lossy(&output.stdout);
@@ -570,20 +763,23 @@ fn get_serial_number() -> Result<String> {
     }
     
     // Method 5: Try to get machine-id as fallback (not exactly serial but unique)
-    if let Ok(machine_id) = std::fs::read_to_string("/etc/machine-id") {
+    let machine_id = hostnamed::identity()
+        .and_then(|identity| identity.machine_id.clone())
+        .or_else(|| std::fs::read_to_string(host::path("/etc/machine-id")).ok());
+    if let Some(machine_id) = machine_id {
         let machine_id = machine_id.trim();
         if !machine_id.is_empty() {
             return Ok(format!("machine-{}", &machine_id[..8])); // Use first 8 chars
         }
     }
     
-    // Final fallback
-    Ok("Unknown".to_string())
+    Err(last_error)
 }
 
 impl DisplayInfo {
     pub fn detect() -> Result<Self> {
-        let displays = detect_displays()?;
+        ensure_detection_enabled()?;
+        let displays = journal::timed("displays", "display", detect_displays)?;
         Ok(DisplayInfo { displays })
     }
 }
@@ -592,25 +788,31 @@ fn detect_displays() -> Result<Vec<Display>> {
     let _displays: Vec<Display> = Vec::new();
     
     // Try xrandr first (most common)
-    if let Ok(xrandr_displays) = detect_displays_xrandr() {
+    if let Ok(xrandr_displays) = journal::timed("list outputs", "xrandr", detect_displays_xrandr) {
         if !xrandr_displays.is_empty() {
             return Ok(xrandr_displays);
         }
     }
     
     // Fallback to wlr-randr for Wayland
-    if let Ok(wlr_displays) = detect_displays_wlr_randr() {
+    if let Ok(wlr_displays) = journal::timed("list outputs", "wlr-randr", detect_displays_wlr_randr) {
         if !wlr_displays.is_empty() {
             return Ok(wlr_displays);
         }
     }
     
     // Fallback to basic detection
+    journal::record(
+        "list outputs",
+        "display",
+        Duration::ZERO,
+        Outcome::Fallback("xrandr and wlr-randr found nothing, using basic detection".to_string()),
+    );
     detect_displays_fallback()
 }
 
 fn detect_displays_xrandr() -> Result<Vec<Display>> {
-    let output = Command::new("xrandr")
+    let output = host::command("xrandr")
         .args(&["--verbose"])
         .output()
         .context("Failed to run xrandr")?;
@@ -692,7 +894,7 @@ fn detect_displays_xrandr() -> Result<Vec<Display>> {
 }
 
 fn detect_displays_wlr_randr() -> Result<Vec<Display>> {
-    let output = Command::new("wlr-randr")
+    let output = host::command("wlr-randr")
         .output()
         .context("Failed to run wlr-randr")?;
     
@@ -811,9 +1013,16 @@ fn detect_displays_fallback() -> Result<Vec<Display>> {
 
 impl StorageInfo {
     pub fn detect() -> Result<Self> {
-        let devices = detect_storage_devices()?;
-        let filesystems = detect_filesystems()?;
-        Ok(StorageInfo { devices, filesystems })
+        ensure_detection_enabled()?;
+        journal::timed("storage", backend_name(), || {
+            if native_backend() {
+                return detect_storage_native();
+            }
+
+            let devices = detect_storage_devices()?;
+            let filesystems = detect_filesystems()?;
+            Ok(StorageInfo { devices, filesystems })
+        })
     }
 }
 
@@ -821,8 +1030,8 @@ fn detect_storage_devices() -> Result<Vec<StorageDevice>> {
     let mut devices = Vec::new();
     
     // Get block devices using lsblk
-    let output = Command::new("lsblk")
-        .args(&["-d", "-o", "NAME,SIZE,TYPE,MODEL,SERIAL", "--json"])
+    let output = host::command("lsblk")
+        .args(["-d", "-b", "-o", "NAME,SIZE,TYPE,MODEL,SERIAL", "--json"])
         .output();
     
     if let Ok(output) = output {
@@ -831,7 +1040,12 @@ fn detect_storage_devices() -> Result<Vec<StorageDevice>> {
             if let Some(blockdevices) = json_data["blockdevices"].as_array() {
                 for device in blockdevices {
                     let name = device["name"].as_str().unwrap_or("Unknown").to_string();
-                    let size = device["size"].as_str().unwrap_or("Unknown").to_string();
+                    // Newer lsblk emits byte sizes as numbers, older ones as strings
+                    let size = device["size"]
+                        .as_u64()
+                        .or_else(|| device["size"].as_str().and_then(|size| size.parse().ok()))
+                        .map(units::format_bytes)
+                        .unwrap_or_else(|| "Unknown".to_string());
                     let device_type = device["type"].as_str().unwrap_or("disk").to_string();
                     let model = device["model"].as_str().unwrap_or("Unknown").to_string();
                     let serial = device["serial"].as_str().unwrap_or("Unknown").to_string();
@@ -868,8 +1082,8 @@ fn detect_storage_devices() -> Result<Vec<StorageDevice>> {
 fn detect_storage_devices_fallback() -> Result<Vec<StorageDevice>> {
     let mut devices = Vec::new();
     
-    let output = Command::new("lsblk")
-        .args(&["-d", "-o", "NAME,SIZE,TYPE,MODEL"])
+    let output = host::command("lsblk")
+        .args(["-d", "-b", "-o", "NAME,SIZE,TYPE,MODEL"])
         .output()
         .context("Failed to run lsblk")?;
     
@@ -890,7 +1104,10 @@ fn detect_storage_devices_fallback() -> Result<Vec<StorageDevice>> {
                 devices.push(StorageDevice {
                     name: format!("/dev/{}", name),
                     model,
-                    size: size.to_string(),
+                    size: size
+                        .parse()
+                        .map(units::format_bytes)
+                        .unwrap_or_else(|_| size.to_string()),
                     device_type: storage_type,
                     interface,
                     serial: "Unknown".to_string(),
@@ -939,64 +1156,148 @@ fn detect_storage_type(device_name: &str, model: &str) -> String {
     }
 }
 
-fn get_device_temperature(device_name: &str) -> Option<String> {
+// Detection policy, set at startup from the config and CLI
+static BATTERY_SAVER: AtomicBool = AtomicBool::new(true);
+static FULL_DETECTION_REQUESTED: AtomicBool = AtomicBool::new(false);
+static NATIVE_BACKEND: AtomicBool = AtomicBool::new(false);
+static NO_DETECT_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+// Auto-refresh intervals are multiplied by this while in reduced-refresh mode
+const REDUCED_REFRESH_FACTOR: u32 = 4;
+
+pub fn configure(config: &Config) {
+    units::configure(config);
+    BATTERY_SAVER.store(config.battery_saver, Ordering::Relaxed);
+    NATIVE_BACKEND.store(config.backend == Backend::Native, Ordering::Relaxed);
+}
+
+fn backend_name() -> &'static str {
+    if native_backend() {
+        "native"
+    } else {
+        "commands"
+    }
+}
+
+fn native_backend() -> bool {
+    NATIVE_BACKEND.load(Ordering::Relaxed)
+}
+
+pub fn request_full_detection() {
+    FULL_DETECTION_REQUESTED.store(true, Ordering::Relaxed);
+}
+
+/// Show only what the config says, e.g. on kiosks or for made-up setups:
+/// every detector fails without running anything
+pub fn request_no_detect() {
+    NO_DETECT_REQUESTED.store(true, Ordering::Relaxed);
+}
+
+pub fn detection_disabled() -> bool {
+    NO_DETECT_REQUESTED.load(Ordering::Relaxed)
+}
+
+fn ensure_detection_enabled() -> Result<()> {
+    if detection_disabled() {
+        anyhow::bail!("detection is disabled (--no-detect)");
+    }
+    Ok(())
+}
+
+pub fn is_on_battery() -> bool {
+    power::on_battery()
+}
+
+/// True when running on battery with the battery saver enabled and no
+/// explicit request for full detection. Expensive detectors are skipped and
+/// auto-refresh slows down in this mode.
+pub fn reduced_refresh_mode() -> bool {
+    BATTERY_SAVER.load(Ordering::Relaxed)
+        && !FULL_DETECTION_REQUESTED.load(Ordering::Relaxed)
+        && is_on_battery()
+}
+
+pub fn refresh_interval(base_seconds: u32) -> u32 {
+    if reduced_refresh_mode() {
+        base_seconds.saturating_mul(REDUCED_REFRESH_FACTOR)
+    } else {
+        base_seconds
+    }
+}
+
+fn get_device_temperature(device_name: &str) -> std::result::Result<String, DetectError> {
+    // SMART queries spin up disks and wake the NVMe controller
+    if reduced_refresh_mode() {
+        return Err(DetectError::new("smartctl", "skipped while on battery"));
+    }
+
     // Try to get temperature from various sources
     if device_name.starts_with("nvme") {
-        if let Ok(output) = Command::new("nvme")
-            .args(&["smart-log", &format!("/dev/{}", device_name)])
-            .output() {
-            let output_str = String::from_utf8_lossy(&output.stdout);
-            for line in output_str.lines() {
-                if line.contains("temperature") {
-                    if let Some(temp_part) = line.split(':').nth(1) {
-                        return Some(temp_part.trim().to_string());
-                    }
+        let output_str = run_tool("nvme", &["smart-log", &format!("/dev/{}", device_name)])?;
+        for line in output_str.lines() {
+            if line.contains("temperature") {
+                if let Some(celsius) = line.split(':').nth(1).and_then(leading_number) {
+                    return Ok(units::format_temperature(celsius));
                 }
             }
         }
+        Err(DetectError::new("nvme", "no temperature in SMART log"))
     } else {
         // Try smartctl for SATA drives
-        if let Ok(output) = Command::new("smartctl")
-            .args(&["-A", &format!("/dev/{}", device_name)])
-            .output() {
-            let output_str = String::from_utf8_lossy(&output.stdout);
-            for line in output_str.lines() {
-                if line.contains("Temperature_Celsius") {
-                    let parts: Vec<&str> = line.split_whitespace().collect();
-                    if parts.len() > 9 {
-                        return Some(format!("{}°C", parts[9]));
-                    }
+        let output_str = run_tool("smartctl", &["-A", &format!("/dev/{}", device_name)])?;
+        for line in output_str.lines() {
+            if line.contains("Temperature_Celsius") {
+                let parts: Vec<&str> = line.split_whitespace().collect();
+                if let Some(celsius) = parts.get(9).and_then(|raw| leading_number(raw)) {
+                    return Ok(units::format_temperature(celsius));
                 }
             }
         }
+        Err(smartctl_error(&output_str, "no temperature attribute"))
     }
-    
-    None
 }
 
-fn get_device_health(device_name: &str) -> Option<String> {
+// "36 °C (309 Kelvin)" or "35 C" → 36.0
+fn leading_number(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let end = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    text[..end].parse().ok()
+}
+
+fn get_device_health(device_name: &str) -> std::result::Result<String, DetectError> {
+    if reduced_refresh_mode() {
+        return Err(DetectError::new("smartctl", "skipped while on battery"));
+    }
+
     // Try smartctl for health status
-    if let Ok(output) = Command::new("smartctl")
-        .args(&["-H", &format!("/dev/{}", device_name)])
-        .output() {
-        let output_str = String::from_utf8_lossy(&output.stdout);
-        for line in output_str.lines() {
-            if line.contains("SMART overall-health") {
-                if let Some(health_part) = line.split(':').nth(1) {
-                    return Some(health_part.trim().to_string());
-                }
+    let output_str = run_tool("smartctl", &["-H", &format!("/dev/{}", device_name)])?;
+    for line in output_str.lines() {
+        if line.contains("SMART overall-health") {
+            if let Some(health_part) = line.split(':').nth(1) {
+                return Ok(health_part.trim().to_string());
             }
         }
     }
-    
-    None
+
+    Err(smartctl_error(&output_str, "no health status reported"))
+}
+
+// smartctl prints permission problems on stdout rather than failing outright
+fn smartctl_error(output: &str, fallback: &str) -> DetectError {
+    if output.contains("Permission denied") || output.contains("Operation not permitted") {
+        DetectError::new("smartctl", "permission denied")
+    } else {
+        DetectError::new("smartctl", fallback)
+    }
 }
 
 fn detect_filesystems() -> Result<Vec<Filesystem>> {
     let mut filesystems = Vec::new();
     
-    let output = Command::new("df")
-        .args(&["-h", "-T"])
+    let output = host::command("df")
+        .args(["-B1", "-T"])
         .output()
         .context("Failed to run df command")?;
     
@@ -1007,9 +1308,14 @@ fn detect_filesystems() -> Result<Vec<Filesystem>> {
         if parts.len() >= 7 {
             let device = parts[0].to_string();
             let filesystem_type = parts[1].to_string();
-            let total_size = parts[2].to_string();
-            let used_size = parts[3].to_string();
-            let available_size = parts[4].to_string();
+            let format_size = |size: &str| {
+                size.parse()
+                    .map(units::format_bytes)
+                    .unwrap_or_else(|_| size.to_string())
+            };
+            let total_size = format_size(parts[2]);
+            let used_size = format_size(parts[3]);
+            let available_size = format_size(parts[4]);
             let usage_percent_str = parts[5].replace("%", "");
             let mountpoint = parts[6].to_string();
             
@@ -1054,3 +1360,538 @@ fn detect_connection_type(display_name: &str) -> Option<String> {
         None
     }
 }
+
+fn detect_system_info_native() -> Result<SystemInfo> {
+    use sysinfo::{CpuRefreshKind, Disks, MemoryRefreshKind, RefreshKind, System};
+
+    let system = System::new_with_specifics(
+        RefreshKind::new()
+            .with_cpu(CpuRefreshKind::everything())
+            .with_memory(MemoryRefreshKind::everything()),
+    );
+
+    let hostname = hostnamed::identity()
+        .and_then(|identity| identity.device_name())
+        .or_else(System::host_name)
+        .unwrap_or_else(|| "Unknown Host".to_string());
+
+    let cpu = match system.cpus().first() {
+        Some(first_cpu) => {
+            let mut cpu_info = first_cpu.brand().trim().to_string();
+            if let Some(physical) = system.physical_core_count() {
+                cpu_info.push_str(&format!(" ({} cores, {} threads)", physical, system.cpus().len()));
+            }
+            if first_cpu.frequency() > 0 {
+                cpu_info = format!("{:.1} GHz {}", first_cpu.frequency() as f64 / 1000.0, cpu_info);
+            }
+            cpu_info
+        }
+        None => "Unknown CPU".to_string(),
+    };
+
+    let memory = if system.total_memory() > 0 {
+        units::format_bytes(system.total_memory())
+    } else {
+        "Unknown Memory".to_string()
+    };
+
+    let disks = Disks::new_with_refreshed_list();
+    let startup_disk = disks
+        .iter()
+        .find(|disk| disk.mount_point() == std::path::Path::new("/"))
+        .map(|disk| {
+            format!(
+                "{} ({})",
+                units::format_bytes(disk.total_space()),
+                disk.file_system().to_string_lossy()
+            )
+        })
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    let mut errors = HashMap::new();
+    let serial_number = read_sysfs_serial()
+        .ok_or_else(|| DetectError::new("/sys/class/dmi/id", "serial number not readable"));
+    let graphics = detect_graphics_sysfs()
+        .ok_or_else(|| DetectError::new("/sys/class/drm", "no graphics card found"));
+
+    Ok(SystemInfo {
+        hostname,
+        cpu,
+        memory,
+        startup_disk,
+        graphics: unwrap_field("graphics", graphics, "Unknown Graphics", &mut errors),
+        serial_number: unwrap_field("serial_num", serial_number, "Unknown", &mut errors),
+        errors,
+    })
+}
+
+fn read_sysfs_serial() -> Option<String> {
+    for path in ["/sys/class/dmi/id/product_serial", "/sys/class/dmi/id/board_serial"] {
+        if let Ok(serial) = std::fs::read_to_string(path) {
+            let serial = serial.trim();
+            if !serial.is_empty() && serial != "Not Specified" {
+                return Some(serial.to_string());
+            }
+        }
+    }
+    None
+}
+
+/// The machine's manufacturer, e.g. "LENOVO" or "Dell Inc."
+pub fn system_vendor() -> Option<String> {
+    if detection_disabled() {
+        return None;
+    }
+    hostnamed::identity()
+        .and_then(|identity| identity.vendor.clone())
+        .or_else(|| {
+            std::fs::read_to_string(host::path("/sys/class/dmi/id/sys_vendor"))
+                .ok()
+                .map(|vendor| vendor.trim().to_string())
+        })
+        .filter(|vendor| !vendor.is_empty())
+}
+
+fn detect_graphics_sysfs() -> Option<String> {
+    let entries = std::fs::read_dir("/sys/class/drm").ok()?;
+
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        // Only look at cardN, not the cardN-connector entries
+        if !name.starts_with("card") || name.contains('-') {
+            continue;
+        }
+
+        let vendor_id = std::fs::read_to_string(entry.path().join("device/vendor")).ok()?;
+        let vendor = match vendor_id.trim() {
+            "0x8086" => "Intel",
+            "0x1002" => "AMD",
+            "0x10de" => "NVIDIA",
+            "0x1af4" => "Virtio",
+            "0x15ad" => "VMware",
+            other => other,
+        };
+        let driver = std::fs::read_link(entry.path().join("device/driver"))
+            .ok()
+            .and_then(|link| link.file_name().map(|n| n.to_string_lossy().to_string()));
+
+        return Some(match driver {
+            Some(driver) => format!("{} Graphics ({})", vendor, driver),
+            None => format!("{} Graphics", vendor),
+        });
+    }
+
+    None
+}
+
+fn detect_storage_native() -> Result<StorageInfo> {
+    let mut devices = Vec::new();
+    let entries = std::fs::read_dir("/sys/block").context("Failed to read /sys/block")?;
+
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with("loop") || name.starts_with("ram") || name.starts_with("zram") {
+            continue;
+        }
+
+        let read = |file: &str| {
+            std::fs::read_to_string(entry.path().join(file))
+                .map(|value| value.trim().to_string())
+                .unwrap_or_default()
+        };
+
+        // Size is always reported in 512-byte sectors
+        let sectors = read("size").parse::<u64>().unwrap_or(0);
+        if sectors == 0 {
+            continue;
+        }
+
+        let model = match read("device/model") {
+            model if model.is_empty() => "Unknown".to_string(),
+            model => model,
+        };
+        let serial = match read("device/serial") {
+            serial if serial.is_empty() => "Unknown".to_string(),
+            serial => serial,
+        };
+
+        let device_type = if name.starts_with("nvme") {
+            "NVMe SSD".to_string()
+        } else if read("queue/rotational") == "1" {
+            "HDD".to_string()
+        } else if read("queue/rotational") == "0" {
+            "SSD".to_string()
+        } else {
+            detect_storage_type(&name, &model)
+        };
+
+        devices.push(StorageDevice {
+            name: format!("/dev/{}", name),
+            model,
+            size: units::format_bytes(sectors * 512),
+            device_type,
+            interface: detect_storage_interface(&name),
+            serial,
+            temperature: Err(DetectError::new("sysinfo", "not available with the native backend")),
+            health: Err(DetectError::new("sysinfo", "not available with the native backend")),
+        });
+    }
+
+    Ok(StorageInfo {
+        devices,
+        filesystems: detect_filesystems_native(),
+    })
+}
+
+fn detect_filesystems_native() -> Vec<Filesystem> {
+    use sysinfo::Disks;
+
+    Disks::new_with_refreshed_list()
+        .iter()
+        .map(|disk| {
+            let total = disk.total_space();
+            let available = disk.available_space();
+            let used = total.saturating_sub(available);
+            let usage_percent = if total > 0 {
+                used as f32 / total as f32 * 100.0
+            } else {
+                0.0
+            };
+
+            Filesystem {
+                device: disk.name().to_string_lossy().to_string(),
+                mountpoint: disk.mount_point().to_string_lossy().to_string(),
+                filesystem_type: disk.file_system().to_string_lossy().to_string(),
+                total_size: units::format_bytes(total),
+                used_size: units::format_bytes(used),
+                available_size: units::format_bytes(available),
+                usage_percent,
+            }
+        })
+        .collect()
+}
+
+/// Current usage of mounted filesystems, without the device and SMART
+/// queries a full storage detection makes
+pub fn detect_filesystem_usage() -> Result<Vec<Filesystem>> {
+    if native_backend() {
+        Ok(detect_filesystems_native())
+    } else {
+        detect_filesystems()
+    }
+}
+
+/// Temperature of a device such as `/dev/sda`
+pub fn device_temperature(device: &str) -> std::result::Result<String, DetectError> {
+    get_device_temperature(device.trim_start_matches("/dev/"))
+}
+
+pub fn uptime() -> Option<Duration> {
+    if detection_disabled() {
+        return None;
+    }
+    let content = std::fs::read_to_string("/proc/uptime").ok()?;
+    let seconds = content.split_whitespace().next()?.parse::<f64>().ok()?;
+    Some(Duration::from_secs_f64(seconds))
+}
+
+/// Desktop environment of the session, e.g. "GNOME" or "KDE"
+pub fn desktop_environment() -> Option<String> {
+    if detection_disabled() {
+        return None;
+    }
+    let desktop = std::env::var("XDG_CURRENT_DESKTOP")
+        .or_else(|_| std::env::var("DESKTOP_SESSION"))
+        .ok()?;
+    // Distros prepend their own name, as in "ubuntu:GNOME"
+    desktop
+        .rsplit(':')
+        .map(str::trim)
+        .find(|name| !name.is_empty())
+        .map(|name| name.to_string())
+}
+
+// Process names of window managers and compositors, with display names
+const WINDOW_MANAGERS: [(&str, &str); 28] = [
+    ("gnome-shell", "Mutter"),
+    ("kwin_wayland", "KWin"),
+    ("kwin_x11", "KWin"),
+    ("xfwm4", "Xfwm4"),
+    ("muffin", "Muffin"),
+    ("cinnamon", "Muffin"),
+    ("marco", "Marco"),
+    ("gala", "Gala"),
+    ("budgie-wm", "Budgie WM"),
+    ("openbox", "Openbox"),
+    ("sway", "Sway"),
+    ("Hyprland", "Hyprland"),
+    ("river", "River"),
+    ("wayfire", "Wayfire"),
+    ("labwc", "labwc"),
+    ("niri", "niri"),
+    ("weston", "Weston"),
+    ("i3", "i3"),
+    ("bspwm", "bspwm"),
+    ("awesome", "awesome"),
+    ("dwm", "dwm"),
+    ("qtile", "Qtile"),
+    ("xmonad", "xmonad"),
+    ("herbstluftwm", "herbstluftwm"),
+    ("fluxbox", "Fluxbox"),
+    ("icewm", "IceWM"),
+    ("enlightenment", "Enlightenment"),
+    ("compiz", "Compiz"),
+];
+
+/// Running window manager, with the session type, e.g. "KWin (Wayland)"
+pub fn window_manager() -> Option<String> {
+    if detection_disabled() {
+        return None;
+    }
+    let running: Vec<String> = std::fs::read_dir("/proc")
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().chars().all(|c| c.is_ascii_digit()))
+        .filter_map(|entry| std::fs::read_to_string(entry.path().join("comm")).ok())
+        .map(|comm| comm.trim().to_string())
+        .collect();
+
+    let name = WINDOW_MANAGERS
+        .iter()
+        .find(|(process, _)| running.iter().any(|comm| comm == process))
+        .map(|(_, name)| name.to_string())?;
+
+    match std::env::var("XDG_SESSION_TYPE").as_deref() {
+        Ok("wayland") => Some(format!("{} (Wayland)", name)),
+        Ok("x11") => Some(format!("{} (X11)", name)),
+        _ => Some(name),
+    }
+}
+
+/// (used, total) memory in bytes, counting reclaimable cache as free
+pub fn memory_usage() -> Option<(u64, u64)> {
+    if detection_disabled() {
+        return None;
+    }
+    let content = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let read_kb = |key: &str| {
+        content
+            .lines()
+            .find(|line| line.starts_with(key))
+            .and_then(|line| line.split_whitespace().nth(1))
+            .and_then(|value| value.parse::<u64>().ok())
+    };
+
+    let total = read_kb("MemTotal:")? * 1024;
+    let available = read_kb("MemAvailable:")? * 1024;
+    Some((total.saturating_sub(available), total))
+}
+
+pub fn process_count() -> usize {
+    if native_backend() {
+        let mut system = sysinfo::System::new();
+        system.refresh_processes();
+        return system.processes().len();
+    }
+
+    std::fs::read_dir("/proc")
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.file_name().to_string_lossy().chars().all(|c| c.is_ascii_digit()))
+                .count()
+        })
+        .unwrap_or(0)
+}
+
+/// Microcode, caches, notable instruction sets and vulnerability mitigations
+/// of the processor, for the Processor details window
+#[derive(Debug, Clone, Default)]
+pub struct CpuDetails {
+    /// Cache name and size, e.g. ("L2", "1 MB × 8")
+    pub caches: Vec<(String, String)>,
+    pub instruction_sets: Vec<String>,
+    /// Vulnerability and the kernel's status for it, e.g. ("meltdown", "Not affected")
+    pub vulnerabilities: Vec<(String, String)>,
+    /// Microcode revision the CPU is running, e.g. "0xf4"
+    pub microcode: Option<String>,
+    /// The distribution package that would update the microcode, when none
+    /// of its files are installed
+    pub missing_microcode_package: Option<&'static str>,
+}
+
+// Where the distributions' microcode packages put their files, by CPU
+// vendor, and what the package is usually called
+const MICROCODE_PACKAGES: [(&str, &[&str], &str); 2] = [
+    (
+        "GenuineIntel",
+        &["/lib/firmware/intel-ucode", "/usr/lib/firmware/intel-ucode", "/boot/intel-ucode.img"],
+        "intel-microcode or intel-ucode",
+    ),
+    (
+        "AuthenticAMD",
+        &["/lib/firmware/amd-ucode", "/usr/lib/firmware/amd-ucode", "/boot/amd-ucode.img"],
+        "amd64-microcode or amd-ucode",
+    ),
+];
+
+// Flags from /proc/cpuinfo worth calling out, and how they are usually written
+const NOTABLE_CPU_FLAGS: [(&str, &str); 19] = [
+    ("sse", "SSE"),
+    ("sse2", "SSE2"),
+    ("pni", "SSE3"),
+    ("ssse3", "SSSE3"),
+    ("sse4_1", "SSE4.1"),
+    ("sse4_2", "SSE4.2"),
+    ("avx", "AVX"),
+    ("avx2", "AVX2"),
+    ("fma", "FMA3"),
+    ("avx512f", "AVX-512"),
+    ("avx_vnni", "AVX-VNNI"),
+    ("amx_tile", "AMX"),
+    ("aes", "AES"),
+    ("sha_ni", "SHA"),
+    ("vmx", "VT-x"),
+    ("svm", "AMD-V"),
+    ("asimd", "NEON"),
+    ("sve", "SVE"),
+    ("sha2", "SHA"),
+];
+
+pub fn cpu_details() -> CpuDetails {
+    if detection_disabled() {
+        return CpuDetails::default();
+    }
+    let cpuinfo = std::fs::read_to_string(host::path("/proc/cpuinfo")).unwrap_or_default();
+    CpuDetails {
+        caches: cpu_caches(),
+        instruction_sets: cpu_instruction_sets(&cpuinfo),
+        vulnerabilities: cpu_vulnerabilities(),
+        microcode: cpu_microcode(&cpuinfo),
+        missing_microcode_package: missing_microcode_package(&cpuinfo),
+    }
+}
+
+// The value of a field of the first CPU in /proc/cpuinfo
+fn cpuinfo_field<'a>(cpuinfo: &'a str, key: &str) -> Option<&'a str> {
+    cpuinfo
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim() == key)
+        .map(|(_, value)| value.trim())
+}
+
+fn cpu_microcode(cpuinfo: &str) -> Option<String> {
+    std::fs::read_to_string(host::path("/sys/devices/system/cpu/cpu0/microcode/version"))
+        .ok()
+        .map(|version| version.trim().to_string())
+        .or_else(|| cpuinfo_field(cpuinfo, "microcode").map(str::to_string))
+        .filter(|version| !version.is_empty())
+}
+
+// Virtual machines get their microcode from the host, so only bare metal
+// Intel and AMD CPUs are checked
+fn missing_microcode_package(cpuinfo: &str) -> Option<&'static str> {
+    let vendor = cpuinfo_field(cpuinfo, "vendor_id")?;
+    let virtualized = cpuinfo_field(cpuinfo, "flags")
+        .is_some_and(|flags| flags.split_whitespace().any(|flag| flag == "hypervisor"));
+    if virtualized {
+        return None;
+    }
+    let (_, paths, package) = MICROCODE_PACKAGES.iter().find(|(id, _, _)| *id == vendor)?;
+    if paths.iter().any(|path| host::path(path).exists()) {
+        None
+    } else {
+        Some(package)
+    }
+}
+
+// Each cache is listed once with how many of it there are, going by the
+// distinct sets of CPUs sharing one
+fn cpu_caches() -> Vec<(String, String)> {
+    let mut caches: BTreeMap<(u32, String), (u64, HashSet<String>)> = BTreeMap::new();
+    let cpus = match std::fs::read_dir(host::path("/sys/devices/system/cpu")) {
+        Ok(cpus) => cpus,
+        Err(_) => return Vec::new(),
+    };
+    for cpu in cpus.flatten() {
+        let name = cpu.file_name().to_string_lossy().to_string();
+        if !name.strip_prefix("cpu").is_some_and(|id| !id.is_empty() && id.chars().all(|c| c.is_ascii_digit())) {
+            continue;
+        }
+        let indexes = match std::fs::read_dir(cpu.path().join("cache")) {
+            Ok(indexes) => indexes,
+            Err(_) => continue,
+        };
+        for index in indexes.flatten() {
+            let read = |file: &str| {
+                std::fs::read_to_string(index.path().join(file))
+                    .map(|value| value.trim().to_string())
+                    .unwrap_or_default()
+            };
+            let (Ok(level), Some(size)) = (read("level").parse::<u32>(), parse_cache_size(&read("size"))) else {
+                continue;
+            };
+            let cache = caches.entry((level, read("type"))).or_insert_with(|| (size, HashSet::new()));
+            cache.1.insert(read("shared_cpu_list"));
+        }
+    }
+
+    caches
+        .into_iter()
+        .map(|((level, kind), (size, shared))| {
+            let name = match kind.as_str() {
+                "Data" | "Instruction" => format!("L{} {}", level, kind),
+                _ => format!("L{}", level),
+            };
+            let size = match shared.len() {
+                0 | 1 => units::format_bytes(size),
+                count => format!("{} × {}", units::format_bytes(size), count),
+            };
+            (name, size)
+        })
+        .collect()
+}
+
+// Sizes in sysfs look like "48K" or "32M"
+fn parse_cache_size(size: &str) -> Option<u64> {
+    let (number, multiplier) = match size.chars().last()? {
+        'K' => (&size[..size.len() - 1], 1024),
+        'M' => (&size[..size.len() - 1], 1024 * 1024),
+        'G' => (&size[..size.len() - 1], 1024 * 1024 * 1024),
+        _ => (size, 1),
+    };
+    number.parse::<u64>().ok().map(|number| number * multiplier)
+}
+
+fn cpu_instruction_sets(cpuinfo: &str) -> Vec<String> {
+    // x86 calls the list "flags", ARM "Features"
+    let flags: Vec<&str> = cpuinfo_field(cpuinfo, "flags")
+        .or_else(|| cpuinfo_field(cpuinfo, "Features"))
+        .map(|flags| flags.split_whitespace().collect())
+        .unwrap_or_default();
+
+    let mut sets: Vec<String> = Vec::new();
+    for (flag, name) in NOTABLE_CPU_FLAGS {
+        if flags.contains(&flag) && !sets.iter().any(|set| set == name) {
+            sets.push(name.to_string());
+        }
+    }
+    sets
+}
+
+fn cpu_vulnerabilities() -> Vec<(String, String)> {
+    let entries = match std::fs::read_dir(host::path("/sys/devices/system/cpu/vulnerabilities")) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    let mut vulnerabilities: Vec<(String, String)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let status = std::fs::read_to_string(entry.path()).ok()?;
+            Some((entry.file_name().to_string_lossy().to_string(), status.trim().to_string()))
+        })
+        .collect();
+    vulnerabilities.sort();
+    vulnerabilities
+}
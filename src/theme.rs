@@ -0,0 +1,235 @@
+use gtk::prelude::*;
+use gtk::gdk::Display;
+use gtk::{gio, glib};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::config::ThemePreset;
+use crate::paths;
+
+const DEFAULT_CSS: &str = include_str!("../data/style.css");
+// Scoped to window.atl-macos, so it is always loaded and switched by class
+const MACOS_CSS: &str = include_str!("../data/macos.css");
+const MACOS_CLASS: &str = "atl-macos";
+
+thread_local! {
+    // Kept so a config reload replaces the user stylesheet instead of stacking
+    static USER_PROVIDER: RefCell<Option<gtk::CssProvider>> = const { RefCell::new(None) };
+}
+
+/// Install the bundled stylesheet for the whole application
+pub fn install_default() {
+    let provider = gtk::CssProvider::new();
+    provider.load_from_data(&format!("{}\n{}", DEFAULT_CSS, MACOS_CSS));
+
+    if let Some(display) = Display::default() {
+        gtk::style_context_add_provider_for_display(
+            &display,
+            &provider,
+            gtk::STYLE_PROVIDER_PRIORITY_APPLICATION,
+        );
+        // The "tux-logo" window icon, also when it isn't installed
+        gtk::IconTheme::for_display(&display).add_resource_path(paths::ICONS_RESOURCE);
+    }
+}
+
+/// Switch `window` to the given built-in look
+pub fn apply_preset(window: &impl IsA<gtk::Widget>, preset: ThemePreset) {
+    if preset == ThemePreset::Macos {
+        window.add_css_class(MACOS_CLASS);
+    } else {
+        window.remove_css_class(MACOS_CLASS);
+    }
+}
+
+/// Load the user's stylesheet above the bundled one, replacing any previous one
+pub fn apply_user_css(css_path: Option<&str>) {
+    let display = match Display::default() {
+        Some(display) => display,
+        None => return,
+    };
+
+    USER_PROVIDER.with(|current| {
+        if let Some(previous) = current.borrow_mut().take() {
+            gtk::style_context_remove_provider_for_display(&display, &previous);
+        }
+
+        let path = match css_path {
+            Some(path) if !path.trim().is_empty() => Path::new(path),
+            _ => return,
+        };
+        if !path.exists() {
+            eprintln!("Stylesheet not found: {}", path.display());
+            return;
+        }
+
+        let provider = gtk::CssProvider::new();
+        provider.connect_parsing_error(|_, section, error| {
+            eprintln!("CSS error at {}: {}", section.to_str(), error);
+        });
+        provider.load_from_path(path);
+
+        // USER priority so it wins over both the theme and our defaults
+        gtk::style_context_add_provider_for_display(
+            &display,
+            &provider,
+            gtk::STYLE_PROVIDER_PRIORITY_USER,
+        );
+        *current.borrow_mut() = Some(provider);
+    });
+}
+
+// Desktop-wide light/dark preference from the XDG Settings portal, which
+// GNOME, KDE and most other desktops implement
+const PORTAL_NAME: &str = "org.freedesktop.portal.Desktop";
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+const PORTAL_SETTINGS: &str = "org.freedesktop.portal.Settings";
+const APPEARANCE_NAMESPACE: &str = "org.freedesktop.appearance";
+const COLOR_SCHEME_KEY: &str = "color-scheme";
+const COLOR_SCHEME_DARK: u32 = 1;
+const PORTAL_TIMEOUT_MS: i32 = 1000;
+
+thread_local! {
+    // Kept alive for its SettingChanged subscription
+    static PORTAL_PROXY: RefCell<Option<gio::DBusProxy>> = const { RefCell::new(None) };
+}
+
+/// Follow the desktop's dark mode preference now and whenever it changes.
+/// Without the portal, a GTK theme with "dark" in its name is the only hint.
+pub fn follow_color_scheme() {
+    // libadwaita's style manager already does this, and warns when the
+    // prefer-dark setting is touched
+    if cfg!(feature = "libadwaita") {
+        return;
+    }
+
+    let proxy = match gio::DBusProxy::for_bus_sync(
+        gio::BusType::Session,
+        gio::DBusProxyFlags::DO_NOT_LOAD_PROPERTIES,
+        None,
+        PORTAL_NAME,
+        PORTAL_PATH,
+        PORTAL_SETTINGS,
+        None::<&gio::Cancellable>,
+    ) {
+        Ok(proxy) => proxy,
+        Err(e) => {
+            eprintln!("Settings portal unavailable: {}", e);
+            set_prefer_dark(None);
+            return;
+        }
+    };
+
+    set_prefer_dark(read_color_scheme(&proxy));
+
+    proxy.connect_g_signal(|_, _, signal, parameters| {
+        if signal != "SettingChanged" {
+            return;
+        }
+        let namespace = parameters.child_value(0);
+        let key = parameters.child_value(1);
+        if namespace.str() == Some(APPEARANCE_NAMESPACE) && key.str() == Some(COLOR_SCHEME_KEY) {
+            set_prefer_dark(unbox_variant(parameters.child_value(2)).get::<u32>());
+        }
+    });
+    PORTAL_PROXY.with(|current| *current.borrow_mut() = Some(proxy));
+}
+
+fn read_color_scheme(proxy: &gio::DBusProxy) -> Option<u32> {
+    let args = (APPEARANCE_NAMESPACE, COLOR_SCHEME_KEY).to_variant();
+    // ReadOne is the current method; older portals only have the deprecated
+    // Read, which wraps the value in an extra variant
+    ["ReadOne", "Read"].iter().find_map(|method| {
+        proxy
+            .call_sync(
+                method,
+                Some(&args),
+                gio::DBusCallFlags::NONE,
+                PORTAL_TIMEOUT_MS,
+                None::<&gio::Cancellable>,
+            )
+            .ok()
+            .and_then(|reply| unbox_variant(reply.child_value(0)).get::<u32>())
+    })
+}
+
+fn unbox_variant(mut value: glib::Variant) -> glib::Variant {
+    while let Some(inner) = value.as_variant() {
+        value = inner;
+    }
+    value
+}
+
+// `None` (no portal or no preference) falls back to the GTK theme name
+fn set_prefer_dark(color_scheme: Option<u32>) {
+    let settings = match gtk::Settings::default() {
+        Some(settings) => settings,
+        None => return,
+    };
+
+    let dark = match color_scheme {
+        Some(COLOR_SCHEME_DARK) => true,
+        Some(scheme) if scheme != 0 => false,
+        _ => settings
+            .gtk_theme_name()
+            .map(|name| name.to_lowercase().contains("dark"))
+            .unwrap_or(false),
+    };
+    settings.set_gtk_application_prefer_dark_theme(dark);
+}
+
+/// Class of the main window, whose labels take `font_family`
+pub const FONT_CLASS: &str = "atl-font";
+/// Class of the configurator's preview, which follows the font being edited
+/// without touching any open overview window
+pub const PREVIEW_FONT_CLASS: &str = "atl-preview-font";
+
+thread_local! {
+    // One provider per class the font is scoped to
+    static FONT_PROVIDERS: RefCell<HashMap<&'static str, gtk::CssProvider>> = RefCell::new(HashMap::new());
+}
+
+/// Whether Pango can find `family` on this system
+pub fn font_available(widget: &impl IsA<gtk::Widget>, family: &str) -> bool {
+    widget
+        .pango_context()
+        .list_families()
+        .iter()
+        .any(|candidate| candidate.name().eq_ignore_ascii_case(family.trim()))
+}
+
+/// Use `family` for every label inside widgets with the class `scope`, one
+/// of `FONT_CLASS` and `PREVIEW_FONT_CLASS`. Missing families are reported
+/// and skipped so the theme font is kept.
+pub fn apply_font_family(widget: &impl IsA<gtk::Widget>, scope: &'static str, family: Option<&str>) {
+    let display = match Display::default() {
+        Some(display) => display,
+        None => return,
+    };
+
+    FONT_PROVIDERS.with(|providers| {
+        if let Some(previous) = providers.borrow_mut().remove(scope) {
+            gtk::style_context_remove_provider_for_display(&display, &previous);
+        }
+
+        let family = match family {
+            Some(family) if !family.trim().is_empty() => family.trim(),
+            _ => return,
+        };
+        if !font_available(widget, family) {
+            eprintln!("Font family \"{}\" is not installed, using the default font", family);
+            return;
+        }
+
+        let escaped: String = family.chars().filter(|c| *c != '"' && *c != '\\').collect();
+        let provider = gtk::CssProvider::new();
+        provider.load_from_data(&format!(".{} label {{ font-family: \"{}\"; }}", scope, escaped));
+        gtk::style_context_add_provider_for_display(
+            &display,
+            &provider,
+            gtk::STYLE_PROVIDER_PRIORITY_APPLICATION,
+        );
+        providers.borrow_mut().insert(scope, provider);
+    });
+}
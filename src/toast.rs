@@ -0,0 +1,92 @@
+use gtk::prelude::*;
+#[cfg(not(feature = "libadwaita"))]
+use gtk::{glib, Label, Revealer};
+#[cfg(not(feature = "libadwaita"))]
+use std::cell::RefCell;
+#[cfg(not(feature = "libadwaita"))]
+use std::rc::Rc;
+use std::time::Duration;
+
+const TOAST_DURATION: Duration = Duration::from_millis(1500);
+
+/// Brief notice floating over the bottom of a window; styled by `.atl-toast`
+#[cfg(not(feature = "libadwaita"))]
+#[derive(Clone)]
+pub struct Toast {
+    revealer: Revealer,
+    label: Label,
+    hide_source: Rc<RefCell<Option<glib::SourceId>>>,
+}
+
+#[cfg(not(feature = "libadwaita"))]
+impl Toast {
+    pub fn new() -> Self {
+        let label = Label::new(None);
+        label.add_css_class("atl-toast");
+
+        let revealer = Revealer::new();
+        revealer.set_transition_type(gtk::RevealerTransitionType::Crossfade);
+        revealer.set_halign(gtk::Align::Center);
+        revealer.set_valign(gtk::Align::End);
+        revealer.set_margin_bottom(20);
+        revealer.set_can_target(false);
+        revealer.set_child(Some(&label));
+
+        Toast {
+            revealer,
+            label,
+            hide_source: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    /// Add this as an overlay on top of the window content
+    pub fn widget(&self) -> &Revealer {
+        &self.revealer
+    }
+
+    pub fn show(&self, text: &str) {
+        self.label.set_text(text);
+        self.revealer.set_reveal_child(true);
+
+        // A new toast restarts the timer rather than being cut short
+        if let Some(source) = self.hide_source.borrow_mut().take() {
+            source.remove();
+        }
+        let revealer = self.revealer.clone();
+        let hide_source = self.hide_source.clone();
+        let source = glib::timeout_add_local_once(TOAST_DURATION, move || {
+            hide_source.borrow_mut().take();
+            revealer.set_reveal_child(false);
+        });
+        *self.hide_source.borrow_mut() = Some(source);
+    }
+}
+
+/// With libadwaita the notice is a regular adw toast, styled by the theme
+#[cfg(feature = "libadwaita")]
+#[derive(Clone)]
+pub struct Toast {
+    overlay: adw::ToastOverlay,
+}
+
+#[cfg(feature = "libadwaita")]
+impl Toast {
+    pub fn new() -> Self {
+        let overlay = adw::ToastOverlay::new();
+        overlay.set_can_target(false);
+        Toast { overlay }
+    }
+
+    /// Add this as an overlay on top of the window content
+    pub fn widget(&self) -> &adw::ToastOverlay {
+        &self.overlay
+    }
+
+    pub fn show(&self, text: &str) {
+        let toast = adw::Toast::new(text);
+        toast.set_timeout(TOAST_DURATION.as_secs().max(1) as u32);
+        // Replaces the one still showing instead of queueing behind it
+        toast.set_priority(adw::ToastPriority::High);
+        self.overlay.add_toast(toast);
+    }
+}
@@ -0,0 +1,127 @@
+use gtk::prelude::*;
+use gtk::{gio, glib};
+
+// A minimal StatusNotifierItem, the tray protocol of KDE, GNOME's
+// AppIndicator extension, waybar and most other panels. Clicking the icon
+// activates the application; there is no menu.
+const ITEM_PATH: &str = "/StatusNotifierItem";
+const ITEM_INTERFACE: &str = "org.kde.StatusNotifierItem";
+const WATCHER_NAME: &str = "org.kde.StatusNotifierWatcher";
+const WATCHER_PATH: &str = "/StatusNotifierWatcher";
+const ICON_NAME: &str = "tux-logo";
+const TITLE: &str = "About this Linux";
+
+const ITEM_XML: &str = r#"
+<node>
+  <interface name="org.kde.StatusNotifierItem">
+    <property name="Category" type="s" access="read"/>
+    <property name="Id" type="s" access="read"/>
+    <property name="Title" type="s" access="read"/>
+    <property name="Status" type="s" access="read"/>
+    <property name="IconName" type="s" access="read"/>
+    <property name="ToolTip" type="(sa(iiay)ss)" access="read"/>
+    <property name="ItemIsMenu" type="b" access="read"/>
+    <property name="WindowId" type="i" access="read"/>
+    <method name="Activate">
+      <arg name="x" type="i" direction="in"/>
+      <arg name="y" type="i" direction="in"/>
+    </method>
+    <method name="SecondaryActivate">
+      <arg name="x" type="i" direction="in"/>
+      <arg name="y" type="i" direction="in"/>
+    </method>
+    <method name="ContextMenu">
+      <arg name="x" type="i" direction="in"/>
+      <arg name="y" type="i" direction="in"/>
+    </method>
+    <method name="Scroll">
+      <arg name="delta" type="i" direction="in"/>
+      <arg name="orientation" type="s" direction="in"/>
+    </method>
+  </interface>
+</node>
+"#;
+
+/// Show a tray icon for `app` until it exits. Panels that start later, or
+/// restart, pick the icon up as well.
+pub fn register(app: &gtk::Application) {
+    let connection = match app.dbus_connection() {
+        Some(connection) => connection,
+        None => {
+            eprintln!("No D-Bus connection, running without a tray icon");
+            return;
+        }
+    };
+
+    let interface = match gio::DBusNodeInfo::for_xml(ITEM_XML)
+        .ok()
+        .and_then(|node| node.lookup_interface(ITEM_INTERFACE))
+    {
+        Some(interface) => interface,
+        None => return,
+    };
+
+    let result = connection.register_object(
+        ITEM_PATH,
+        &interface,
+        |_, _, _, _, method, _, invocation| {
+            // There is no menu, so a right click opens the window as well
+            if method == "Activate" || method == "SecondaryActivate" || method == "ContextMenu" {
+                if let Some(app) = gio::Application::default() {
+                    app.activate();
+                }
+            }
+            invocation.return_value(None);
+        },
+        |_, _, _, _, property| item_property(property),
+        |_, _, _, _, _, _| false,
+    );
+    if let Err(e) = result {
+        eprintln!("Failed to export the tray icon: {}", e);
+        return;
+    }
+
+    // The watcher is the panel side; register again whenever one appears
+    gio::bus_watch_name_on_connection(
+        &connection,
+        WATCHER_NAME,
+        gio::BusNameWatcherFlags::NONE,
+        |connection, _, _| {
+            let name = match connection.unique_name() {
+                Some(name) => name.to_string(),
+                None => return,
+            };
+            connection.call(
+                Some(WATCHER_NAME),
+                WATCHER_PATH,
+                WATCHER_NAME,
+                "RegisterStatusNotifierItem",
+                Some(&(name,).to_variant()),
+                None,
+                gio::DBusCallFlags::NONE,
+                -1,
+                None::<&gio::Cancellable>,
+                |result| {
+                    if let Err(e) = result {
+                        eprintln!("Failed to register the tray icon: {}", e);
+                    }
+                },
+            );
+        },
+        |_, _| eprintln!("No tray available, the icon shows once a panel provides one"),
+    );
+}
+
+fn item_property(property: &str) -> glib::Variant {
+    match property {
+        "Category" => "ApplicationStatus".to_variant(),
+        "Id" => "about-this-linux".to_variant(),
+        "Title" => TITLE.to_variant(),
+        "Status" => "Active".to_variant(),
+        "IconName" => ICON_NAME.to_variant(),
+        "ToolTip" => (ICON_NAME, Vec::<(i32, i32, Vec<u8>)>::new(), TITLE, "Click to show").to_variant(),
+        "ItemIsMenu" => false.to_variant(),
+        "WindowId" => 0i32.to_variant(),
+        _ => "".to_variant(),
+    }
+}
@@ -0,0 +1,80 @@
+use gtk::glib;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+use std::time::Duration;
+
+use crate::config::{Config, TemperatureUnit, UnitSystem};
+
+// Display preferences shared by every formatting helper, set from the config
+static DECIMAL_UNITS: AtomicBool = AtomicBool::new(false);
+static FAHRENHEIT: AtomicBool = AtomicBool::new(false);
+static DATE_FORMAT: RwLock<String> = RwLock::new(String::new());
+
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+pub fn configure(config: &Config) {
+    DECIMAL_UNITS.store(config.units == UnitSystem::Decimal, Ordering::Relaxed);
+    FAHRENHEIT.store(config.temperature_unit == TemperatureUnit::Fahrenheit, Ordering::Relaxed);
+    if let Ok(mut format) = DATE_FORMAT.write() {
+        *format = config.date_format.clone();
+    }
+}
+
+/// Format a byte count as GiB/TiB or GB/TB depending on the unit preference
+pub fn format_bytes(bytes: u64) -> String {
+    let (base, giga, tera) = if DECIMAL_UNITS.load(Ordering::Relaxed) {
+        (1000.0, "GB", "TB")
+    } else {
+        (1024.0, "GiB", "TiB")
+    };
+
+    let size_giga = bytes as f64 / base / base / base;
+    if size_giga >= base {
+        format!("{:.1} {}", size_giga / base, tera)
+    } else {
+        format!("{:.1} {}", size_giga, giga)
+    }
+}
+
+pub fn format_temperature(celsius: f64) -> String {
+    if FAHRENHEIT.load(Ordering::Relaxed) {
+        format!("{:.0}°F", celsius * 9.0 / 5.0 + 32.0)
+    } else {
+        format!("{:.0}°C", celsius)
+    }
+}
+
+/// "2 days, 3 h 12 min", dropping leading zero parts
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+    let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+
+    match (days, hours) {
+        (0, 0) => format!("{} min", minutes),
+        (0, _) => format!("{} h {} min", hours, minutes),
+        (1, _) => format!("1 day, {} h {} min", hours, minutes),
+        _ => format!("{} days, {} h {} min", days, hours, minutes),
+    }
+}
+
+/// The current time, formatted like `format_timestamp`
+pub fn format_now() -> String {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or_default();
+    format_timestamp(now)
+}
+
+/// Format a Unix timestamp in local time with the configured `date_format`
+pub fn format_timestamp(timestamp: i64) -> String {
+    let format = match DATE_FORMAT.read() {
+        Ok(format) if !format.is_empty() => format.clone(),
+        _ => DEFAULT_DATE_FORMAT.to_string(),
+    };
+
+    glib::DateTime::from_unix_local(timestamp)
+        .and_then(|time| time.format(&format))
+        .map(|text| text.to_string())
+        .unwrap_or_else(|_| timestamp.to_string())
+}
@@ -0,0 +1,265 @@
+use anyhow::{bail, Context, Result};
+use gtk::prelude::*;
+use gtk::{gio, glib};
+use std::cell::{Cell, RefCell};
+use std::time::Duration;
+
+use crate::host;
+use crate::packagekit;
+
+const NOTIFICATION_ID: &str = "updates";
+
+type PendingListener = Box<dyn Fn(usize)>;
+
+thread_local! {
+    // Result of the last background check
+    static PENDING: Cell<Option<usize>> = const { Cell::new(None) };
+    static LISTENERS: RefCell<Vec<PendingListener>> = const { RefCell::new(Vec::new()) };
+}
+
+// Graphical updaters, preferred over the built-in check when installed
+const UPDATER_GUIS: [(&str, &[&str]); 6] = [
+    ("gnome-software", &["--mode=updates"]),
+    ("plasma-discover", &["--mode", "update"]),
+    ("update-manager", &[]),
+    ("mintupdate", &[]),
+    ("pamac-manager", &["--updates"]),
+    ("dnfdragora", &[]),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PackageManager {
+    Pacman,
+    Apt,
+    Dnf,
+    Zypper,
+}
+
+impl PackageManager {
+    pub fn detect() -> Option<Self> {
+        [
+            (PackageManager::Pacman, "pacman"),
+            (PackageManager::Apt, "apt-get"),
+            (PackageManager::Dnf, "dnf"),
+            (PackageManager::Zypper, "zypper"),
+        ]
+        .into_iter()
+        .find(|(_, program)| host::find_program(program).is_some())
+        .map(|(manager, _)| manager)
+    }
+
+    /// What to run in a terminal to install the updates
+    pub fn upgrade_command(&self) -> &'static str {
+        match self {
+            PackageManager::Pacman => "sudo pacman -Syu",
+            PackageManager::Apt => "sudo apt update && sudo apt upgrade",
+            PackageManager::Dnf => "sudo dnf upgrade",
+            PackageManager::Zypper => "sudo zypper update",
+        }
+    }
+
+    pub fn install_command(&self, packages: &[&str]) -> String {
+        let command = match self {
+            PackageManager::Pacman => "sudo pacman -S",
+            PackageManager::Apt => "sudo apt install",
+            PackageManager::Dnf => "sudo dnf install",
+            PackageManager::Zypper => "sudo zypper install",
+        };
+        format!("{} {}", command, packages.join(" "))
+    }
+
+    /// Packages with an update available, as "name version". Runs without
+    /// root, so apt and the `pacman -Qu` fallback only know about the last
+    /// refresh.
+    pub fn pending_updates(&self) -> Result<Vec<String>> {
+        let updates = match self {
+            PackageManager::Pacman => {
+                // checkupdates (pacman-contrib) syncs a private copy of the
+                // databases and exits with 2 when there is nothing to do;
+                // pacman -Qu exits with 1 instead. Lines are "name old -> new".
+                let stdout = if host::find_program("checkupdates").is_some() {
+                    run("checkupdates", &[], &[0, 2])?
+                } else {
+                    run("pacman", &["-Qu"], &[0, 1])?
+                };
+                stdout
+                    .lines()
+                    .filter_map(|line| {
+                        let fields: Vec<&str> = line.split_whitespace().collect();
+                        Some(format!("{} {}", fields.first()?, fields.last()?))
+                    })
+                    .collect()
+            }
+            PackageManager::Apt => {
+                // "Inst name [old] (new repository [arch])"
+                let stdout = run("apt-get", &["-s", "-o", "Debug::NoLocking=true", "upgrade"], &[0])?;
+                stdout
+                    .lines()
+                    .filter_map(|line| line.strip_prefix("Inst "))
+                    .map(|line| {
+                        let name = line.split_whitespace().next().unwrap_or_default();
+                        match line.split_once('(').and_then(|(_, rest)| rest.split_whitespace().next()) {
+                            Some(version) => format!("{} {}", name, version),
+                            None => name.to_string(),
+                        }
+                    })
+                    .collect()
+            }
+            PackageManager::Dnf => {
+                // Exits with 100 when updates are available; "name.arch version repository"
+                let stdout = run("dnf", &["check-update", "-q"], &[0, 100])?;
+                stdout
+                    .lines()
+                    .take_while(|line| !line.starts_with("Obsoleting"))
+                    .filter(|line| !line.starts_with(' ') && line.split_whitespace().count() == 3)
+                    .map(|line| {
+                        let fields: Vec<&str> = line.split_whitespace().collect();
+                        let name = fields[0].rsplit_once('.').map_or(fields[0], |(name, _)| name);
+                        format!("{} {}", name, fields[1])
+                    })
+                    .collect()
+            }
+            PackageManager::Zypper => {
+                // "v | repository | name | current | available | arch"
+                let stdout = run("zypper", &["--non-interactive", "--quiet", "list-updates"], &[0])?;
+                stdout
+                    .lines()
+                    .filter(|line| line.starts_with("v "))
+                    .filter_map(|line| {
+                        let fields: Vec<&str> = line.split('|').map(str::trim).collect();
+                        Some(format!("{} {}", fields.get(2)?, fields.get(4)?))
+                    })
+                    .collect()
+            }
+        };
+        Ok(updates)
+    }
+}
+
+/// Where pending updates come from: PackageKit when its daemon is
+/// installed, otherwise the package manager's own tools
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UpdateSource {
+    // With the package manager, if known, for the upgrade command
+    PackageKit(Option<PackageManager>),
+    Cli(PackageManager),
+}
+
+impl UpdateSource {
+    pub fn detect() -> Option<Self> {
+        let manager = PackageManager::detect();
+        if packagekit::available() {
+            return Some(UpdateSource::PackageKit(manager));
+        }
+        manager.map(UpdateSource::Cli)
+    }
+
+    pub fn pending_updates(&self) -> Result<Vec<String>> {
+        match self {
+            UpdateSource::PackageKit(_) => packagekit::pending_updates(),
+            UpdateSource::Cli(manager) => manager.pending_updates(),
+        }
+    }
+
+    /// What to run in a terminal to install the updates
+    pub fn upgrade_command(&self) -> &'static str {
+        match self {
+            UpdateSource::PackageKit(Some(manager)) | UpdateSource::Cli(manager) => manager.upgrade_command(),
+            UpdateSource::PackageKit(None) => "pkcon update",
+        }
+    }
+}
+
+/// Start the first graphical updater found; false if there is none
+pub fn launch_updater_gui() -> bool {
+    UPDATER_GUIS
+        .iter()
+        .filter(|(program, _)| host::find_program(program).is_some())
+        .any(|(program, args)| match host::command(program).args(*args).spawn() {
+            Ok(_) => true,
+            Err(e) => {
+                eprintln!("Failed to start {}: {}", program, e);
+                false
+            }
+        })
+}
+
+// Stdout of the command, failing on exit codes outside `ok_codes`
+fn run(program: &str, args: &[&str], ok_codes: &[i32]) -> Result<String> {
+    let output = host::command(program)
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run {}", program))?;
+    if !output.status.code().is_some_and(|code| ok_codes.contains(&code)) {
+        bail!(
+            "{} exited with {}: {}",
+            program,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Updates found by the last background check, if one has run
+pub fn pending() -> Option<usize> {
+    PENDING.with(|pending| pending.get())
+}
+
+/// Call `f` with the new count after every background check
+pub fn connect_pending(f: impl Fn(usize) + 'static) {
+    LISTENERS.with(|listeners| listeners.borrow_mut().push(Box::new(f)));
+}
+
+/// Look for updates now and, when `repeat` is set, every `hours` after
+/// that. A desktop notification is sent whenever the count goes up.
+pub fn start_checker(app: &gtk::Application, hours: u32, repeat: bool) {
+    let source = match UpdateSource::detect() {
+        Some(source) => source,
+        None => {
+            eprintln!("Neither PackageKit nor a supported package manager found, not checking for updates");
+            return;
+        }
+    };
+
+    check(app, source);
+    if repeat {
+        let app = app.downgrade();
+        glib::timeout_add_local(Duration::from_secs(hours as u64 * 3600), move || match app.upgrade() {
+            Some(app) => {
+                check(&app, source);
+                glib::ControlFlow::Continue
+            }
+            None => glib::ControlFlow::Break,
+        });
+    }
+}
+
+fn check(app: &gtk::Application, source: UpdateSource) {
+    let app = app.clone();
+    glib::spawn_future_local(async move {
+        let count = match gio::spawn_blocking(move || source.pending_updates()).await {
+            Ok(Ok(updates)) => updates.len(),
+            Ok(Err(e)) => {
+                eprintln!("Failed to check for updates: {}", e);
+                return;
+            }
+            Err(_) => return,
+        };
+
+        let previous = PENDING.with(|pending| pending.replace(Some(count)));
+        if count > previous.unwrap_or(0) {
+            let notification = gio::Notification::new("Software updates available");
+            notification.set_body(Some(&format!(
+                "{} update{} can be installed with {}",
+                count,
+                if count == 1 { "" } else { "s" },
+                source.upgrade_command()
+            )));
+            app.send_notification(Some(NOTIFICATION_ID), &notification);
+        } else if count == 0 {
+            app.withdraw_notification(NOTIFICATION_ID);
+        }
+        LISTENERS.with(|listeners| listeners.borrow().iter().for_each(|f| f(count)));
+    });
+}
@@ -1,5 +1,6 @@
 use gtk::prelude::*;
-use gtk::{MessageDialog, Window};
+use gtk::{gio, Label, MessageDialog, Window};
+use std::cell::RefCell;
 
 pub fn show_error_dialog(parent: Option<&Window>, _title: &str, message: &str) {
     let dialog = MessageDialog::new(
@@ -41,6 +42,103 @@ pub fn show_info_dialog(parent: Option<&Window>, _title: &str, message: &str) {
     dialog.present();
 }
 
+/// Ask before doing something that can't be undone from the UI; `on_confirm`
+/// runs only if the user picks `action`
+pub fn confirm(parent: &Window, question: &str, details: &str, action: &str, on_confirm: impl Fn() + 'static) {
+    let dialog = MessageDialog::new(
+        Some(parent),
+        gtk::DialogFlags::MODAL,
+        gtk::MessageType::Question,
+        gtk::ButtonsType::None,
+        question,
+    );
+    dialog.set_secondary_text(Some(details));
+    dialog.add_button("Cancel", gtk::ResponseType::Cancel);
+    let button = dialog.add_button(action, gtk::ResponseType::Accept);
+    button.add_css_class("destructive-action");
+    dialog.set_default_response(gtk::ResponseType::Cancel);
+
+    dialog.connect_response(move |dialog, response| {
+        dialog.close();
+        if response == gtk::ResponseType::Accept {
+            on_confirm();
+        }
+    });
+    dialog.present();
+}
+
+/// Show a native file chooser, which goes through the file chooser portal
+/// inside Flatpak. GTK doesn't keep native dialogs alive, so it is held here
+/// until it has been answered.
+pub fn show_file_chooser(chooser: &gtk::FileChooserNative) {
+    // Portals only keep the picker above its parent window when modal
+    chooser.set_modal(true);
+    let held = RefCell::new(Some(chooser.clone()));
+    chooser.connect_response(move |_, _| {
+        held.borrow_mut().take();
+    });
+    chooser.show();
+}
+
+/// Open `uri` with the default handler, telling the user in a dialog when
+/// none is registered instead of failing silently
+pub fn open_uri(widget: &impl IsA<gtk::Widget>, uri: &str) {
+    let widget = widget.as_ref();
+    let context = widget.display().app_launch_context();
+    if let Err(e) = gio::AppInfo::launch_default_for_uri(uri, Some(&context)) {
+        eprintln!("Failed to open {}: {}", uri, e);
+        let parent = widget.root().and_downcast::<Window>();
+        show_error_dialog(
+            parent.as_ref(),
+            "Can't Open Link",
+            &format!("No application could open {}\n\n{}", uri, e),
+        );
+    }
+}
+
+/// Route the links in `label` through `open_uri`; a middle click copies the
+/// link under the pointer and then calls `on_copied`
+pub fn handle_links(label: &Label, on_copied: impl Fn() + 'static) {
+    label.connect_activate_link(|label, uri| {
+        open_uri(label, uri);
+        gtk::glib::Propagation::Stop
+    });
+
+    let middle_click = gtk::GestureClick::new();
+    middle_click.set_button(gtk::gdk::BUTTON_MIDDLE);
+    middle_click.connect_pressed(move |gesture, _, _, _| {
+        let label = match gesture.widget().downcast::<Label>() {
+            Ok(label) => label,
+            Err(_) => return,
+        };
+        if let Some(uri) = label.current_uri() {
+            label.clipboard().set_text(&uri);
+            on_copied();
+        }
+    });
+    label.add_controller(middle_click);
+}
+
+/// Largest scale factor of any monitor, so images can be loaded sharp enough
+/// for whichever screen the window ends up on
+pub fn max_scale_factor() -> i32 {
+    if !gtk::is_initialized_main_thread() {
+        return 1;
+    }
+    let display = match gtk::gdk::Display::default() {
+        Some(display) => display,
+        None => return 1,
+    };
+
+    let monitors = display.monitors();
+    (0..monitors.n_items())
+        .filter_map(|i| monitors.item(i).and_downcast::<gtk::gdk::Monitor>())
+        .map(|monitor| monitor.scale_factor())
+        .max()
+        .unwrap_or(1)
+        .max(1)
+}
+
 pub fn escape_markup(text: &str) -> String {
     text.replace('&', "&amp;")
         .replace('<', "&lt;")
@@ -0,0 +1,62 @@
+use gtk::glib;
+
+use crate::hostnamed;
+use crate::system_info;
+
+// Firmware placeholders that aren't a real serial number
+const PLACEHOLDER_SERIALS: [&str; 7] = [
+    "unknown",
+    "to be filled by o.e.m.",
+    "system serial number",
+    "default string",
+    "not specified",
+    "none",
+    "0",
+];
+
+#[derive(Debug, Clone)]
+pub struct WarrantyLink {
+    pub vendor: &'static str,
+    pub url: String,
+}
+
+/// The vendor's warranty lookup page for this machine, with the product
+/// serial number filled in. Nothing is sent anywhere until the link is opened.
+pub fn link() -> Option<WarrantyLink> {
+    let vendor = system_info::system_vendor()?.to_lowercase();
+    let serial = glib::Uri::escape_string(&product_serial()?, None, false);
+    let (vendor, url) = if vendor.starts_with("dell") {
+        (
+            "Dell",
+            format!(
+                "https://www.dell.com/support/home/product-support/servicetag/{}/overview",
+                serial
+            ),
+        )
+    } else if vendor.starts_with("lenovo") {
+        (
+            "Lenovo",
+            format!("https://pcsupport.lenovo.com/products/{}/warranty", serial),
+        )
+    } else if vendor == "hp" || vendor.starts_with("hewlett-packard") {
+        (
+            "HP",
+            format!("https://support.hp.com/check-warranty?serialnumber={}", serial),
+        )
+    } else {
+        return None;
+    };
+    Some(WarrantyLink { vendor, url })
+}
+
+// Vendors look warranties up by the product serial, not the board serial the
+// overview may show, nor its machine-id fallback
+fn product_serial() -> Option<String> {
+    let serial = std::fs::read_to_string("/sys/class/dmi/id/product_serial")
+        .ok()
+        .map(|serial| serial.trim().to_string())
+        .filter(|serial| !serial.is_empty())
+        .or_else(|| hostnamed::hardware_serial().map(str::to_string))?;
+    let placeholder = PLACEHOLDER_SERIALS.contains(&serial.to_lowercase().as_str());
+    (!placeholder && !serial.starts_with("machine-")).then_some(serial)
+}